@@ -0,0 +1,35 @@
+use clap::ValueEnum;
+use clap_complete::{Generator, Shell};
+use std::{fs, io, path::Path};
+
+#[allow(dead_code)]
+mod cli {
+    include!("src/cli.rs");
+}
+
+// The directory of the pre-generated completion scripts for packaging.
+const COMPLETIONS_DIR: &str = "assets/completions";
+
+fn main() -> io::Result<()> {
+    println!("cargo::rerun-if-changed=src/cli.rs");
+    println!("cargo::rerun-if-changed={COMPLETIONS_DIR}");
+
+    // Building the published crate must not modify its source directory.
+    if !Path::new(".git").exists() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(COMPLETIONS_DIR)?;
+    for shell in Shell::value_variants() {
+        let mut script = Vec::with_capacity(4096);
+        cli::write_completion_script(*shell, &mut script)?;
+
+        let path = Path::new(COMPLETIONS_DIR).join(shell.file_name("rustlings"));
+        // Only written on changes to keep the modification time.
+        if fs::read(&path).ok().as_ref() != Some(&script) {
+            fs::write(path, script)?;
+        }
+    }
+
+    Ok(())
+}
@@ -59,7 +59,7 @@ mod tests {
         println!("reference count = {}", Rc::strong_count(&sun)); // 6 references
         jupiter.details();
 
-        // TODO
+        // TODO: Share the same sun with `Rc::clone` like above.
         let saturn = Planet::Saturn(Rc::new(Sun));
         println!("reference count = {}", Rc::strong_count(&sun)); // 7 references
         saturn.details();
@@ -91,7 +91,7 @@ mod tests {
         drop(mars);
         println!("reference count = {}", Rc::strong_count(&sun)); // 4 references
 
-        // TODO
+        // TODO: Drop one more planet to decrease the reference count.
         println!("reference count = {}", Rc::strong_count(&sun)); // 3 references
 
         // TODO
@@ -6,6 +6,10 @@ use serde::Deserialize;
 struct ExerciseInfo {
     name: String,
     dir: String,
+    #[serde(default)]
+    extra_files: Vec<String>,
+    #[serde(default)]
+    additional_files: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -27,6 +31,34 @@ pub fn include_files(_: TokenStream) -> TokenStream {
         .iter()
         .map(|exercise| format!("../solutions/{}/{}.rs", exercise.dir, exercise.name));
 
+    let additional_files = exercises.iter().map(|exercise| {
+        let names = exercise
+            .extra_files
+            .iter()
+            .chain(&exercise.additional_files)
+            .collect::<Vec<_>>();
+        let paths = names
+            .iter()
+            .map(|name| format!("../exercises/{}/{name}", exercise.dir));
+
+        quote! {
+            &[#(AdditionalFile { name: #names, content: include_bytes!(#paths) }),*]
+        }
+    });
+
+    // Solutions only have their own copies of the extra Rust files.
+    // Additional files like data files are read from the exercise's directory.
+    let solution_extra_files = exercises.iter().map(|exercise| {
+        let names = &exercise.extra_files;
+        let paths = names
+            .iter()
+            .map(|name| format!("../solutions/{}/{name}", exercise.dir));
+
+        quote! {
+            &[#(AdditionalFile { name: #names, content: include_bytes!(#paths) }),*]
+        }
+    });
+
     let mut dirs = Vec::with_capacity(32);
     let mut dir_inds = vec![0; exercises.len()];
 
@@ -48,7 +80,7 @@ pub fn include_files(_: TokenStream) -> TokenStream {
     quote! {
         EmbeddedFiles {
             info_file: #info_file,
-            exercise_files: &[#(ExerciseFiles { exercise: include_bytes!(#exercise_files), solution: include_bytes!(#solution_files), dir_ind: #dir_inds }),*],
+            exercise_files: &[#(ExerciseFiles { exercise: include_bytes!(#exercise_files), solution: include_bytes!(#solution_files), additional_files: #additional_files, solution_extra_files: #solution_extra_files, dir_ind: #dir_inds }),*],
             exercise_dirs: &[#(ExerciseDir { name: #dirs, readme: include_bytes!(#readmes) }),*]
         }
     }
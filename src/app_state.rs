@@ -1,29 +1,33 @@
 use anyhow::{bail, Context, Error, Result};
 use crossterm::{cursor, terminal, QueueableCommand};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     env,
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::{Read, Seek, StdoutLock, Write},
+    iter,
     path::{Path, MAIN_SEPARATOR_STR},
     process::{Command, Stdio},
+    str,
     sync::{
         atomic::{AtomicUsize, Ordering::Relaxed},
         mpsc,
     },
     thread,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use crate::{
     clear_terminal,
+    cli::SandboxArgs,
     cmd::CmdRunner,
     embedded::EMBEDDED_FILES,
     exercise::{Exercise, RunnableExercise},
-    info_file::ExerciseInfo,
+    info_file::{exercises_dir, ExerciseInfo, TestMode},
     term::{self, CheckProgressVisualizer},
 };
 
-const STATE_FILE_NAME: &str = ".rustlings-state.txt";
+pub const STATE_FILE_NAME: &str = ".rustlings-state.txt";
 const DEFAULT_CHECK_PARALLELISM: usize = 8;
 
 #[must_use]
@@ -39,6 +43,9 @@ pub enum ExercisesProgress {
 pub enum StateFileStatus {
     Read,
     NotRead,
+    /// The current exercise in the state file doesn't exist anymore.
+    /// Contains its name.
+    UnknownCurrentExercise(String),
 }
 
 #[derive(Clone, Copy)]
@@ -49,6 +56,22 @@ pub enum CheckProgress {
     Pending,
 }
 
+/// Read the names of the done exercises from the state file without creating it.
+/// Empty if the file doesn't exist.
+pub fn done_exercise_names() -> HashSet<String> {
+    let Ok(content) = fs::read_to_string(STATE_FILE_NAME) else {
+        return HashSet::new();
+    };
+
+    // See `AppState::write` for information about the file format.
+    content
+        .split('\n')
+        .skip(4)
+        .take_while(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 pub struct AppState {
     current_exercise_ind: usize,
     exercises: Vec<Exercise>,
@@ -62,14 +85,19 @@ pub struct AppState {
     cmd_runner: CmdRunner,
     // Running in VS Code.
     vs_code: bool,
+    // Unix timestamps in seconds of when exercises were done.
+    completion_timestamps: Vec<u64>,
+    // The terminal bell and a flash after runs in the watch mode.
+    bell: bool,
 }
 
 impl AppState {
     pub fn new(
-        exercise_infos: Vec<ExerciseInfo>,
+        exercise_infos: Vec<&ExerciseInfo>,
         final_message: String,
+        sandbox_args: SandboxArgs,
     ) -> Result<(Self, StateFileStatus)> {
-        let cmd_runner = CmdRunner::build()?;
+        let cmd_runner = CmdRunner::build(sandbox_args)?;
         let mut state_file = OpenOptions::new()
             .create(true)
             .read(true)
@@ -80,7 +108,7 @@ impl AppState {
                 format!("Failed to open or create the state file {STATE_FILE_NAME}")
             })?;
 
-        let dir_canonical_path = term::canonicalize("exercises");
+        let dir_canonical_path = term::canonicalize(exercises_dir());
         let mut exercises = exercise_infos
             .into_iter()
             .map(|exercise_info| {
@@ -88,9 +116,38 @@ impl AppState {
                 // Leaking is not a problem because the `AppState` instance lives until
                 // the end of the program.
                 let path = exercise_info.path().leak();
-                let name = exercise_info.name.leak();
-                let dir = exercise_info.dir.map(|dir| &*dir.leak());
-                let hint = exercise_info.hint.leak().trim_ascii();
+                let extra_paths = exercise_info
+                    .extra_file_paths(exercises_dir())
+                    .chain(exercise_info.additional_file_paths())
+                    .map(|path| &*path.leak())
+                    .collect();
+                let name = exercise_info.name.clone().leak();
+                let dir = exercise_info.dir.clone().map(|dir| &*dir.leak());
+                let hints = exercise_info
+                    .hints
+                    .iter()
+                    .map(|hint| hint.clone().leak().trim_ascii())
+                    .collect();
+                let prerequisites = exercise_info
+                    .prerequisites
+                    .iter()
+                    .map(|prerequisite| &*prerequisite.clone().leak())
+                    .collect();
+                let tags = exercise_info
+                    .tags
+                    .iter()
+                    .map(|tag| &*tag.clone().leak())
+                    .collect();
+                let features = exercise_info
+                    .features
+                    .iter()
+                    .map(|feature| &*feature.clone().leak())
+                    .collect();
+                let test_command = exercise_info
+                    .test_command
+                    .clone()
+                    .filter(|_| exercise_info.test == TestMode::Custom)
+                    .map(|test_command| &*test_command.leak());
 
                 let canonical_path = dir_canonical_path.as_deref().map(|dir_canonical_path| {
                     let mut canonical_path;
@@ -117,12 +174,22 @@ impl AppState {
                     dir,
                     name,
                     path,
+                    extra_paths,
                     canonical_path,
-                    test: exercise_info.test,
+                    test: exercise_info.test != TestMode::Off,
+                    test_command,
+                    features,
                     strict_clippy: exercise_info.strict_clippy,
-                    hint,
+                    should_fail: exercise_info.should_fail,
+                    hints,
+                    prerequisites,
+                    tags,
                     // Updated below.
                     done: false,
+                    skipped: false,
+                    secs_spent: 0,
+                    completed_at: None,
+                    started_at: None,
                 }
             })
             .collect::<Vec<_>>();
@@ -130,6 +197,8 @@ impl AppState {
         let mut current_exercise_ind = 0;
         let mut n_done = 0;
         let mut file_buf = Vec::with_capacity(2048);
+        let mut completion_timestamps = Vec::new();
+        let mut bell = false;
         let state_file_status = 'block: {
             if state_file.read_to_end(&mut file_buf).is_err() {
                 break 'block StateFileStatus::NotRead;
@@ -146,33 +215,87 @@ impl AppState {
                 break 'block StateFileStatus::NotRead;
             }
 
+            let mut current_exercise_found = false;
             let mut done_exercises = HashSet::with_capacity(exercises.len());
 
-            for done_exercise_name in lines {
+            for done_exercise_name in lines.by_ref() {
                 if done_exercise_name.is_empty() {
                     break;
                 }
                 done_exercises.insert(done_exercise_name);
             }
 
+            completion_timestamps.extend(
+                lines
+                    .by_ref()
+                    .take_while(|line| !line.is_empty())
+                    .filter_map(|line| str::from_utf8(line).ok()?.parse::<u64>().ok()),
+            );
+
+            let progress = lines
+                .by_ref()
+                .take_while(|line| !line.is_empty())
+                .filter_map(|line| {
+                    let mut fields = str::from_utf8(line).ok()?.split(' ');
+                    let name = fields.next()?;
+                    let secs_spent = fields.next()?.parse::<u64>().ok()?;
+                    // `-` for a missing completion timestamp.
+                    let completed_at = fields.next().and_then(|field| field.parse::<u64>().ok());
+                    let started_at = fields.next().and_then(|field| field.parse::<u64>().ok());
+                    Some((name.as_bytes(), (secs_spent, completed_at, started_at)))
+                })
+                .collect::<HashMap<_, _>>();
+
+            for setting in lines.by_ref().take_while(|line| !line.is_empty()) {
+                if setting == b"bell" {
+                    bell = true;
+                }
+            }
+
+            let skipped_exercises = lines
+                .take_while(|line| !line.is_empty())
+                .collect::<HashSet<_>>();
+
             for (ind, exercise) in exercises.iter_mut().enumerate() {
                 if done_exercises.contains(exercise.name.as_bytes()) {
                     exercise.done = true;
                     n_done += 1;
+                } else if skipped_exercises.contains(exercise.name.as_bytes()) {
+                    exercise.skipped = true;
+                }
+                if let Some((secs_spent, completed_at, started_at)) =
+                    progress.get(exercise.name.as_bytes())
+                {
+                    exercise.secs_spent = *secs_spent;
+                    exercise.completed_at = *completed_at;
+                    exercise.started_at = *started_at;
                 }
 
                 if exercise.name.as_bytes() == current_exercise_name {
                     current_exercise_ind = ind;
+                    current_exercise_found = true;
                 }
             }
 
+            if !current_exercise_found {
+                // Continue at the first pending exercise instead.
+                current_exercise_ind = exercises
+                    .iter()
+                    .position(|exercise| !exercise.done)
+                    .unwrap_or(0);
+
+                break 'block StateFileStatus::UnknownCurrentExercise(
+                    String::from_utf8_lossy(current_exercise_name).into_owned(),
+                );
+            }
+
             StateFileStatus::Read
         };
 
         file_buf.clear();
         file_buf.extend_from_slice(STATE_FILE_HEADER);
 
-        let slf = Self {
+        let mut slf = Self {
             current_exercise_ind,
             exercises,
             n_done,
@@ -182,7 +305,11 @@ impl AppState {
             official_exercises: !Path::new("info.toml").exists(),
             cmd_runner,
             vs_code: env::var_os("TERM_PROGRAM").is_some_and(|v| v == "vscode"),
+            completion_timestamps,
+            bell,
         };
+        // Saved with the next write of the state file.
+        slf.start_exercise(current_exercise_ind);
 
         Ok((slf, state_file_status))
     }
@@ -222,6 +349,70 @@ impl AppState {
         self.vs_code
     }
 
+    #[inline]
+    pub fn completion_timestamps(&self) -> &[u64] {
+        &self.completion_timestamps
+    }
+
+    #[inline]
+    pub fn bell(&self) -> bool {
+        self.bell
+    }
+
+    pub fn toggle_bell(&mut self) -> Result<()> {
+        self.bell = !self.bell;
+        self.write()
+    }
+
+    /// Add the seconds spent on the exercise and write them to the state file.
+    pub fn add_secs_spent(&mut self, exercise_ind: usize, secs: u64) -> Result<()> {
+        if secs == 0 {
+            return Ok(());
+        }
+
+        let exercise = &mut self.exercises[exercise_ind];
+        exercise.secs_spent = exercise.secs_spent.saturating_add(secs);
+        self.write()
+    }
+
+    // Only called when the learner solved a pending exercise to not inflate the stats.
+    fn record_completion(&mut self, exercise_ind: usize) {
+        // A clock before the Unix epoch is ignored.
+        if let Ok(duration) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            self.completion_timestamps.push(duration.as_secs());
+            self.exercises[exercise_ind].completed_at = Some(duration.as_secs());
+        }
+    }
+
+    // Remember when a pending exercise became the current one for the duration in the stats.
+    // Restarting an exercise that was already started keeps the first timestamp.
+    fn start_exercise(&mut self, exercise_ind: usize) {
+        let exercise = &mut self.exercises[exercise_ind];
+        if exercise.done || exercise.started_at.is_some() {
+            return;
+        }
+
+        // A clock before the Unix epoch is ignored.
+        if let Ok(duration) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            exercise.started_at = Some(duration.as_secs());
+        }
+    }
+
+    // Remove the completion of a reset exercise from the stats.
+    fn forget_completion(&mut self, exercise_ind: usize) {
+        let Some(completed_at) = self.exercises[exercise_ind].completed_at.take() else {
+            return;
+        };
+
+        if let Some(ind) = self
+            .completion_timestamps
+            .iter()
+            .rposition(|timestamp| *timestamp == completed_at)
+        {
+            self.completion_timestamps.remove(ind);
+        }
+    }
+
     // Write the state file.
     // The file's format is very simple:
     // - The first line is a comment.
@@ -229,7 +420,19 @@ impl AppState {
     // - The third line is the name of the current exercise. It must end with `\n` even if there
     // are no done exercises.
     // - The fourth line is an empty line.
-    // - All remaining lines are the names of done exercises.
+    // - The next lines are the names of done exercises.
+    // - If exercises were done since tracking completion times, the names are followed by an empty
+    // line and the Unix timestamps of the completions. Older versions stop reading at that line.
+    // - If time was spent on exercises in the watch mode or exercises were done since tracking it,
+    // the timestamps are followed by an empty line and a line per exercise with its name, the
+    // seconds spent on it, optionally the Unix timestamp of its last completion (`-` if it has
+    // none) and optionally the Unix timestamp of when it became the current exercise separated by
+    // spaces. Older versions ignore these lines because they aren't timestamps.
+    // - If settings of the watch mode are activated, they follow after an empty line with one line
+    // per setting. Currently, only `bell` exists.
+    // - If exercises were skipped in the watch mode, the settings are followed by an empty line and
+    // the names of the skipped exercises.
+    // A section is written, even if it is empty, if a later section isn't empty.
     fn write(&mut self) -> Result<()> {
         self.file_buf.truncate(STATE_FILE_HEADER.len());
 
@@ -244,6 +447,57 @@ impl AppState {
             }
         }
 
+        let has_progress = |exercise: &Exercise| {
+            exercise.secs_spent > 0
+                || exercise.completed_at.is_some()
+                || exercise.started_at.is_some()
+        };
+        let any_skipped = self.exercises.iter().any(|exercise| exercise.skipped);
+        let write_settings = self.bell || any_skipped;
+        let write_progress = write_settings || self.exercises.iter().any(has_progress);
+        if !self.completion_timestamps.is_empty() || write_progress {
+            self.file_buf.push(b'\n');
+            for timestamp in &self.completion_timestamps {
+                write!(self.file_buf, "\n{timestamp}")?;
+            }
+        }
+
+        if write_progress {
+            self.file_buf.push(b'\n');
+            for exercise in &self.exercises {
+                if !has_progress(exercise) {
+                    continue;
+                }
+
+                write!(self.file_buf, "\n{} {}", exercise.name, exercise.secs_spent)?;
+                match (exercise.completed_at, exercise.started_at) {
+                    (Some(completed_at), Some(started_at)) => {
+                        write!(self.file_buf, " {completed_at} {started_at}")?;
+                    }
+                    (Some(completed_at), None) => write!(self.file_buf, " {completed_at}")?,
+                    (None, Some(started_at)) => write!(self.file_buf, " - {started_at}")?,
+                    (None, None) => (),
+                }
+            }
+        }
+
+        if write_settings {
+            self.file_buf.push(b'\n');
+            if self.bell {
+                self.file_buf.extend_from_slice(b"\nbell");
+            }
+        }
+
+        if any_skipped {
+            self.file_buf.push(b'\n');
+            for exercise in &self.exercises {
+                if exercise.skipped {
+                    self.file_buf.push(b'\n');
+                    self.file_buf.extend_from_slice(exercise.name.as_bytes());
+                }
+            }
+        }
+
         self.state_file
             .rewind()
             .with_context(|| format!("Failed to rewind the state file {STATE_FILE_NAME}"))?;
@@ -267,24 +521,79 @@ impl AppState {
         }
 
         self.current_exercise_ind = exercise_ind;
+        self.start_exercise(exercise_ind);
 
         self.write()
     }
 
-    pub fn set_current_exercise_by_name(&mut self, name: &str) -> Result<()> {
+    pub fn exercise_ind_by_name(&self, name: &str) -> Result<usize> {
         // O(N) is fine since this method is used only once until the program exits.
         // Building a hashmap would have more overhead.
-        self.current_exercise_ind = self
-            .exercises
+        self.exercises
             .iter()
             .position(|exercise| exercise.name == name)
-            .with_context(|| format!("No exercise found for '{name}'!"))?;
+            .with_context(|| format!("No exercise found for '{name}'!"))
+    }
+
+    /// Find the exercise with the name or the only one whose name starts with the prefix.
+    pub fn exercise_ind_by_prefix(&self, prefix: &str) -> Result<usize> {
+        if let Some(ind) = self
+            .exercises
+            .iter()
+            .position(|exercise| exercise.name == prefix)
+        {
+            return Ok(ind);
+        }
+
+        let mut matches = self
+            .exercises
+            .iter()
+            .enumerate()
+            .filter(|(_, exercise)| exercise.name.starts_with(prefix));
+        let Some((ind, _)) = matches.next() else {
+            bail!("No exercise name starts with `{prefix}`");
+        };
+        if matches.next().is_some() {
+            let names = self
+                .exercises
+                .iter()
+                .filter(|exercise| exercise.name.starts_with(prefix))
+                .map(|exercise| exercise.name)
+                .collect::<Vec<_>>();
+            bail!(
+                "Multiple exercise names start with `{prefix}`: {}",
+                names.join(", "),
+            );
+        }
+
+        Ok(ind)
+    }
+
+    pub fn set_current_exercise_by_name(&mut self, name: &str) -> Result<()> {
+        self.current_exercise_ind = self.exercise_ind_by_name(name)?;
+        self.start_exercise(self.current_exercise_ind);
 
         self.write()
     }
 
+    // Return the names of the prerequisites of the exercise that aren't done yet.
+    pub fn pending_prerequisites(&self, exercise_ind: usize) -> Vec<&'static str> {
+        self.exercises[exercise_ind]
+            .prerequisites
+            .iter()
+            .copied()
+            .filter(|prerequisite| {
+                !self
+                    .exercises
+                    .iter()
+                    .any(|exercise| exercise.name == *prerequisite && exercise.done)
+            })
+            .collect()
+    }
+
     // Set the status of an exercise without saving. Returns `true` if the
     // status actually changed (and thus needs saving later).
+    // Completions aren't recorded because checking all exercises also calls it.
     pub fn set_status(&mut self, exercise_ind: usize, done: bool) -> Result<bool> {
         let exercise = self
             .exercises
@@ -297,6 +606,7 @@ impl AppState {
 
         exercise.done = done;
         if done {
+            exercise.skipped = false;
             self.n_done += 1;
         } else {
             self.n_done -= 1;
@@ -314,28 +624,44 @@ impl AppState {
         Ok(())
     }
 
-    // Official exercises: Dump the original file from the binary.
-    // Third-party exercises: Reset the exercise file with `git stash`.
-    fn reset(&self, exercise_ind: usize, path: &str) -> Result<()> {
+    // Set the status of an exercise to "done" after the learner solved it and save.
+    pub fn set_done(&mut self, exercise_ind: usize) -> Result<()> {
+        if self.set_status(exercise_ind, true)? {
+            self.record_completion(exercise_ind);
+            self.write()?;
+        }
+
+        Ok(())
+    }
+
+    // Official exercises: Dump the original files from the binary.
+    // Third-party exercises: Reset the exercise file and its extra files with `git stash`.
+    fn reset(&self, exercise_ind: usize) -> Result<()> {
+        let exercise = &self.exercises[exercise_ind];
+        let path = exercise.path;
         if self.official_exercises {
             return EMBEDDED_FILES
                 .write_exercise_to_disk(exercise_ind, path)
                 .with_context(|| format!("Failed to reset the exercise {path}"));
         }
 
+        let paths = iter::once(path)
+            .chain(exercise.extra_paths.iter().copied())
+            .collect::<Vec<_>>();
         let output = Command::new("git")
             .arg("stash")
             .arg("push")
             .arg("--")
-            .arg(path)
+            .args(&paths)
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .output()
-            .with_context(|| format!("Failed to run `git stash push -- {path}`"))?;
+            .with_context(|| format!("Failed to run `git stash push -- {}`", paths.join(" ")))?;
 
         if !output.status.success() {
             bail!(
-                "`git stash push -- {path}` didn't run successfully: {}",
+                "`git stash push -- {}` didn't run successfully: {}",
+                paths.join(" "),
                 String::from_utf8_lossy(&output.stderr),
             );
         }
@@ -343,12 +669,25 @@ impl AppState {
         Ok(())
     }
 
+    // Set the exercise to pending, clear its skipped mark and forget its completion
+    // before resetting its files. The current exercise starts again.
+    fn set_pending_for_reset(&mut self, exercise_ind: usize) -> Result<()> {
+        self.set_status(exercise_ind, false)?;
+        let exercise = &mut self.exercises[exercise_ind];
+        exercise.skipped = false;
+        exercise.started_at = None;
+        self.forget_completion(exercise_ind);
+        if exercise_ind == self.current_exercise_ind {
+            self.start_exercise(exercise_ind);
+        }
+        self.write()
+    }
+
     pub fn reset_current_exercise(&mut self) -> Result<&'static str> {
-        self.set_pending(self.current_exercise_ind)?;
-        let exercise = self.current_exercise();
-        self.reset(self.current_exercise_ind, exercise.path)?;
+        self.set_pending_for_reset(self.current_exercise_ind)?;
+        self.reset(self.current_exercise_ind)?;
 
-        Ok(exercise.path)
+        Ok(self.current_exercise().path)
     }
 
     // Reset the exercise by index and return its name.
@@ -357,33 +696,56 @@ impl AppState {
             bail!(BAD_INDEX_ERR);
         }
 
-        self.set_pending(exercise_ind)?;
-        let exercise = &self.exercises[exercise_ind];
-        self.reset(exercise_ind, exercise.path)?;
+        self.set_pending_for_reset(exercise_ind)?;
+        self.reset(exercise_ind)?;
 
-        Ok(exercise.name)
+        Ok(self.exercises[exercise_ind].name)
     }
 
-    // Return the index of the next pending exercise or `None` if all exercises are done.
-    fn next_pending_exercise_ind(&self) -> Option<usize> {
+    // The index of the next exercise after the current one that matches the predicate.
+    // The search wraps around to the start.
+    fn next_exercise_ind_by(&self, pred: impl Fn(&Exercise) -> bool) -> Option<usize> {
         let next_ind = self.current_exercise_ind + 1;
         self.exercises
-            // If the exercise done isn't the last, search for pending exercises after it.
+            // If the exercise done isn't the last, search for matching exercises after it.
             .get(next_ind..)
             .and_then(|later_exercises| {
                 later_exercises
                     .iter()
-                    .position(|exercise| !exercise.done)
+                    .position(&pred)
                     .map(|ind| next_ind + ind)
             })
             // Search from the start.
             .or_else(|| {
                 self.exercises[..self.current_exercise_ind]
                     .iter()
-                    .position(|exercise| !exercise.done)
+                    .position(&pred)
             })
     }
 
+    /// Return the index of the next pending exercise after the current one
+    /// or `None` if all other exercises are done.
+    /// Skipped exercises are only returned after all other pending exercises are done.
+    pub fn next_pending_exercise_ind(&self) -> Option<usize> {
+        self.next_exercise_ind_by(|exercise| !exercise.done && !exercise.skipped)
+            .or_else(|| self.next_exercise_ind_by(|exercise| !exercise.done))
+    }
+
+    /// Mark the current exercise as skipped and continue at the next pending exercise.
+    /// Returns `false` if there is no other pending exercise to continue at.
+    pub fn skip_current_exercise(&mut self) -> Result<bool> {
+        self.exercises[self.current_exercise_ind].skipped = true;
+
+        let next_ind = self.next_pending_exercise_ind();
+        if let Some(ind) = next_ind {
+            self.current_exercise_ind = ind;
+            self.start_exercise(ind);
+        }
+        self.write()?;
+
+        Ok(next_ind.is_some())
+    }
+
     /// Official exercises: Dump the solution file from the binary and return its path.
     /// Third-party exercises: Check if a solution file exists and return its path in that case.
     pub fn current_solution_path(&self) -> Result<Option<String>> {
@@ -409,9 +771,15 @@ impl AppState {
     }
 
     fn check_all_exercises_impl(&mut self, stdout: &mut StdoutLock) -> Result<Option<usize>> {
-        let term_width = terminal::size()
-            .context("Failed to get the terminal size")?
-            .0;
+        let term_width = if term::is_ansi_terminal(stdout) {
+            Some(
+                terminal::size()
+                    .context("Failed to get the terminal size")?
+                    .0,
+            )
+        } else {
+            None
+        };
         let mut progress_visualizer = CheckProgressVisualizer::build(stdout, term_width)?;
 
         let next_exercise_ind = AtomicUsize::new(0);
@@ -511,6 +879,10 @@ impl AppState {
 
     // Return the exercise index of the first pending exercise found.
     pub fn check_all_exercises(&mut self, stdout: &mut StdoutLock) -> Result<Option<usize>> {
+        if !term::is_ansi_terminal(stdout) {
+            return self.check_all_exercises_impl(stdout);
+        }
+
         stdout.queue(cursor::Hide)?;
         let res = self.check_all_exercises_impl(stdout);
         stdout.queue(cursor::Show)?;
@@ -528,7 +900,9 @@ impl AppState {
         let exercise = &mut self.exercises[self.current_exercise_ind];
         if !exercise.done {
             exercise.done = true;
+            exercise.skipped = false;
             self.n_done += 1;
+            self.record_completion(self.current_exercise_ind);
         }
 
         if let Some(ind) = self.next_pending_exercise_ind() {
@@ -600,27 +974,46 @@ mod tests {
             dir: None,
             name: "0",
             path: "exercises/0.rs",
+            extra_paths: Vec::new(),
             canonical_path: None,
             test: false,
+            test_command: None,
+            features: Vec::new(),
             strict_clippy: false,
-            hint: "",
+            should_fail: false,
+            hints: Vec::new(),
+            prerequisites: Vec::new(),
+            tags: Vec::new(),
             done: false,
+            skipped: false,
+            secs_spent: 0,
+            completed_at: None,
+            started_at: None,
         }
     }
 
-    #[test]
-    fn next_pending_exercise() {
-        let mut app_state = AppState {
+    fn dummy_app_state(exercises: Vec<Exercise>, n_done: u16) -> AppState {
+        AppState {
             current_exercise_ind: 0,
-            exercises: vec![dummy_exercise(), dummy_exercise(), dummy_exercise()],
-            n_done: 0,
+            exercises,
+            n_done,
             final_message: String::new(),
             state_file: tempfile::tempfile().unwrap(),
             file_buf: Vec::new(),
             official_exercises: true,
-            cmd_runner: CmdRunner::build().unwrap(),
+            cmd_runner: CmdRunner::build(SandboxArgs::default()).unwrap(),
             vs_code: false,
-        };
+            completion_timestamps: Vec::new(),
+            bell: false,
+        }
+    }
+
+    #[test]
+    fn next_pending_exercise() {
+        let mut app_state = dummy_app_state(
+            vec![dummy_exercise(), dummy_exercise(), dummy_exercise()],
+            0,
+        );
 
         let mut assert = |done: [bool; 3], expected: [Option<usize>; 3]| {
             for (exercise, done) in app_state.exercises.iter_mut().zip(done) {
@@ -645,4 +1038,140 @@ mod tests {
         assert([false, true, false], [Some(2), Some(2), Some(0)]);
         assert([false, false, true], [Some(1), Some(0), Some(0)]);
     }
+
+    #[test]
+    fn next_pending_exercise_with_skipped() {
+        let mut exercises = [dummy_exercise(), dummy_exercise(), dummy_exercise()];
+        exercises[1].skipped = true;
+
+        let mut app_state = dummy_app_state(exercises.into(), 0);
+
+        // Skipped exercises come after the other pending ones.
+        assert_eq!(app_state.next_pending_exercise_ind(), Some(2));
+        app_state.current_exercise_ind = 2;
+        assert_eq!(app_state.next_pending_exercise_ind(), Some(0));
+
+        // Only skipped exercises are left.
+        app_state.exercises[0].done = true;
+        assert_eq!(app_state.next_pending_exercise_ind(), Some(1));
+
+        assert!(app_state.skip_current_exercise().unwrap());
+        assert_eq!(app_state.current_exercise_ind, 1);
+        assert!(app_state.exercises[2].skipped);
+        // Cycle through the skipped exercises.
+        assert!(app_state.skip_current_exercise().unwrap());
+        assert_eq!(app_state.current_exercise_ind, 2);
+
+        app_state.exercises[1].done = true;
+        assert!(!app_state.skip_current_exercise().unwrap());
+        assert_eq!(app_state.current_exercise_ind, 2);
+    }
+
+    #[test]
+    fn pending_for_reset() {
+        let mut exercises = [dummy_exercise(), dummy_exercise()];
+        exercises[0].done = true;
+        exercises[1].skipped = true;
+        let mut app_state = dummy_app_state(exercises.into(), 1);
+
+        app_state.set_pending_for_reset(0).unwrap();
+        app_state.set_pending_for_reset(1).unwrap();
+        assert_eq!(app_state.n_done, 0);
+        assert!(!app_state.exercises[0].done);
+        assert!(!app_state.exercises[1].skipped);
+    }
+
+    #[test]
+    fn completions() {
+        let mut app_state = dummy_app_state(vec![dummy_exercise(), dummy_exercise()], 0);
+
+        // Checking all exercises doesn't record completions.
+        assert!(app_state.set_status(0, true).unwrap());
+        assert!(app_state.completion_timestamps.is_empty());
+        assert_eq!(app_state.exercises[0].completed_at, None);
+
+        // Only solving a pending exercise does.
+        app_state.set_done(0).unwrap();
+        app_state.set_done(1).unwrap();
+        app_state.set_done(1).unwrap();
+        assert_eq!(app_state.completion_timestamps.len(), 1);
+        assert!(app_state.exercises[1].completed_at.is_some());
+
+        app_state.set_pending_for_reset(1).unwrap();
+        assert!(app_state.completion_timestamps.is_empty());
+        assert_eq!(app_state.exercises[1].completed_at, None);
+    }
+
+    #[test]
+    fn start_timestamps() {
+        let mut exercises = [dummy_exercise(), dummy_exercise()];
+        exercises[1].name = "1";
+        let mut app_state = dummy_app_state(exercises.into(), 0);
+
+        app_state.set_current_exercise_ind(1).unwrap();
+        let started_at = app_state.exercises[1].started_at.unwrap();
+        let state = String::from_utf8_lossy(&app_state.file_buf);
+        assert!(
+            state.ends_with(&format!("\n\n1 0 - {started_at}")),
+            "{state}"
+        );
+
+        app_state.set_done(1).unwrap();
+        let completed_at = app_state.exercises[1].completed_at.unwrap();
+        let state = String::from_utf8_lossy(&app_state.file_buf);
+        assert!(
+            state.ends_with(&format!("\n\n1 0 {completed_at} {started_at}")),
+            "{state}",
+        );
+
+        // Done exercises aren't started again.
+        app_state.set_current_exercise_ind(0).unwrap();
+        app_state.exercises[1].started_at = None;
+        app_state.set_current_exercise_ind(1).unwrap();
+        assert_eq!(app_state.exercises[1].started_at, None);
+    }
+
+    #[test]
+    fn pending_prerequisites() {
+        let mut exercises = [dummy_exercise(), dummy_exercise(), dummy_exercise()];
+        exercises[0].name = "a";
+        exercises[0].done = true;
+        exercises[1].name = "b";
+        exercises[2].name = "c";
+        exercises[2].prerequisites = vec!["a", "b"];
+
+        let app_state = dummy_app_state(exercises.into(), 1);
+
+        assert!(app_state.pending_prerequisites(0).is_empty());
+        assert_eq!(app_state.pending_prerequisites(2), ["b"]);
+    }
+
+    #[test]
+    fn exercise_by_prefix() {
+        let mut exercises = [dummy_exercise(), dummy_exercise(), dummy_exercise()];
+        exercises[0].name = "move_semantics1";
+        exercises[1].name = "move_semantics2";
+        exercises[2].name = "move";
+
+        let app_state = dummy_app_state(exercises.into(), 0);
+        let find = |prefix| {
+            app_state
+                .exercise_ind_by_prefix(prefix)
+                .map_err(|e| e.to_string())
+        };
+
+        assert_eq!(find("move"), Ok(2));
+        assert_eq!(find("move_semantics2"), Ok(1));
+        assert_eq!(find("move_semantics1"), Ok(0));
+        assert_eq!(
+            find("move_"),
+            Err(String::from(
+                "Multiple exercise names start with `move_`: move_semantics1, move_semantics2"
+            )),
+        );
+        assert_eq!(
+            find("x"),
+            Err(String::from("No exercise name starts with `x`")),
+        );
+    }
 }
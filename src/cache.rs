@@ -0,0 +1,54 @@
+use anyhow::{Context, Error, Result};
+use std::{
+    fs::{self, create_dir_all},
+    path::PathBuf,
+};
+
+/// The directory of all persisted caches like the outputs of successful runs and the results of `dev check`.
+pub const CACHE_DIR: &str = ".rustlings-cache";
+
+fn entry_path(name: &str) -> PathBuf {
+    let mut path = PathBuf::from(CACHE_DIR);
+    path.push(name);
+    path
+}
+
+/// Return the content of the entry if it was written with the same `key`.
+/// The key is a hash of everything that the content depends on (see `hash::StableHasher`).
+pub fn read(name: &str, key: u64) -> Option<Vec<u8>> {
+    // The first line is the key in hexadecimal followed by the content.
+    let entry = fs::read(entry_path(name)).ok()?;
+    let key_line = format!("{key:016x}\n");
+    entry
+        .strip_prefix(key_line.as_bytes())
+        .map(|content| content.to_vec())
+}
+
+/// Write the entry atomically to never leave a partially written entry if interrupted.
+pub fn write(name: &str, key: u64, content: &[u8]) -> Result<()> {
+    create_dir_all(CACHE_DIR)
+        .with_context(|| format!("Failed to create the directory {CACHE_DIR}"))?;
+
+    let mut entry = format!("{key:016x}\n").into_bytes();
+    entry.extend_from_slice(content);
+
+    let path = entry_path(name);
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, entry)
+        .with_context(|| format!("Failed to write the file {}", tmp_path.display()))?;
+    if let Err(e) = fs::rename(&tmp_path, &path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(Error::from(e).context(format!(
+            "Failed to rename the file {} to {}",
+            tmp_path.display(),
+            path.display(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Remove an outdated entry. Errors are ignored because a missing entry is also outdated.
+pub fn remove(name: &str) {
+    let _ = fs::remove_file(entry_path(name));
+}
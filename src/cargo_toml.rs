@@ -1,31 +1,106 @@
-use anyhow::{Context, Result};
-use std::path::Path;
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::Path,
+};
+use toml_edit::DocumentMut;
 
 use crate::{exercise::RunnableExercise, info_file::ExerciseInfo};
 
 /// Initial capacity of the bins buffer.
 pub const BINS_BUFFER_CAPACITY: usize = 1 << 14;
 
+/// The edition if `Cargo.toml` doesn't specify one.
+pub const DEFAULT_EDITION: &str = "2021";
+
+/// The editions that an exercise can specify in `info.toml`.
+pub const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
+
+/// Return the `edition` of the `package` section in `Cargo.toml`.
+/// `None` if it is missing, inherited from the workspace or the file is invalid.
+pub fn package_edition(cargo_toml: &str) -> Option<String> {
+    let document = cargo_toml.parse::<DocumentMut>().ok()?;
+
+    document
+        .get("package")?
+        .get("edition")?
+        .as_str()
+        .map(String::from)
+}
+
+/// Return the names of the features in the `features` table of `Cargo.toml`.
+/// Empty if the table is missing or the file is invalid.
+pub fn declared_features(cargo_toml: &str) -> Vec<String> {
+    let Ok(document) = cargo_toml.parse::<DocumentMut>() else {
+        return Vec::new();
+    };
+
+    document
+        .get("features")
+        .and_then(|features| features.as_table_like())
+        .map(|features| features.iter().map(|(name, _)| name.to_string()).collect())
+        .unwrap_or_default()
+}
+
+const BINS_START: &str = "bin = [";
+
 /// Return the start and end index of the content of the list `bin = […]`.
 /// bin = [xxxxxxxxxxxxxxxxx]
 ///        |start_ind       |
 ///                         |end_ind
+///
+/// Fails if the list is missing, duplicated or not closed to never touch anything outside of it.
 pub fn bins_start_end_ind(cargo_toml: &str) -> Result<(usize, usize)> {
-    let start_ind = cargo_toml
-        .find("bin = [")
-        .context("Failed to find the start of the `bin` list (`bin = [`)")?
-        + 7;
+    // Only at the start of a line to skip comments like `# bin = [`.
+    let mut start_inds = cargo_toml
+        .match_indices(BINS_START)
+        .map(|(ind, _)| ind)
+        .filter(|&ind| ind == 0 || cargo_toml.as_bytes()[ind - 1] == b'\n');
+
+    let start_ind = start_inds
+        .next()
+        .context("Failed to find the start of the `bin` list (`bin = [` at the start of a line)")?
+        + BINS_START.len();
+    if start_inds.next().is_some() {
+        bail!("Found multiple `bin` lists (`bin = [`). Only one is allowed");
+    }
+
     let end_ind = start_ind
-        + cargo_toml
-            .get(start_ind..)
-            .and_then(|slice| slice.as_bytes().iter().position(|c| *c == b']'))
+        + cargo_toml[start_ind..]
+            .find(']')
             .context("Failed to find the end of the `bin` list (`]`)")?;
+    // The entries are inline tables without brackets.
+    // A bracket means that the found `]` belongs to something after the list like a section header.
+    if cargo_toml[start_ind..end_ind].contains('[') {
+        bail!("Failed to find the end of the `bin` list (`]`) before the next `[`");
+    }
 
     Ok((start_ind, end_ind))
 }
 
+// Append the path with the prefix unless it is absolute.
+fn append_path(buf: &mut Vec<u8>, path: &str, exercise_path_prefix: &[u8]) {
+    if !Path::new(path).is_absolute() {
+        buf.extend_from_slice(exercise_path_prefix);
+    }
+    buf.extend_from_slice(path.as_bytes());
+}
+
+// Close the entry after the path with the edition of the exercise if it has one.
+// Cargo supports the edition per target.
+fn append_edition(buf: &mut Vec<u8>, exercise_info: &ExerciseInfo) {
+    buf.push(b'"');
+    if let Some(edition) = &exercise_info.edition {
+        buf.extend_from_slice(b", edition = \"");
+        buf.extend_from_slice(edition.as_bytes());
+        buf.push(b'"');
+    }
+    buf.extend_from_slice(b" },\n");
+}
+
 /// Generate and append the content of the `bin` list in `Cargo.toml`.
-/// The `exercise_path_prefix` is the prefix of the `path` field of every list entry.
+/// The `exercise_path_prefix` is the prefix of the `path` field of every list entry
+/// with a relative path.
 pub fn append_bins(
     buf: &mut Vec<u8>,
     exercise_infos: &[ExerciseInfo],
@@ -36,14 +111,8 @@ pub fn append_bins(
         buf.extend_from_slice(b"  { name = \"");
         buf.extend_from_slice(exercise_info.name.as_bytes());
         buf.extend_from_slice(b"\", path = \"");
-        buf.extend_from_slice(exercise_path_prefix);
-        buf.extend_from_slice(b"exercises/");
-        if let Some(dir) = &exercise_info.dir {
-            buf.extend_from_slice(dir.as_bytes());
-            buf.push(b'/');
-        }
-        buf.extend_from_slice(exercise_info.name.as_bytes());
-        buf.extend_from_slice(b".rs\" },\n");
+        append_path(buf, &exercise_info.path(), exercise_path_prefix);
+        append_edition(buf, exercise_info);
 
         let sol_path = exercise_info.sol_path();
         if !Path::new(&sol_path).exists() {
@@ -54,15 +123,54 @@ pub fn append_bins(
         buf.extend_from_slice(exercise_info.name.as_bytes());
         buf.extend_from_slice(b"_sol");
         buf.extend_from_slice(b"\", path = \"");
-        buf.extend_from_slice(exercise_path_prefix);
-        buf.extend_from_slice(b"solutions/");
-        if let Some(dir) = &exercise_info.dir {
-            buf.extend_from_slice(dir.as_bytes());
-            buf.push(b'/');
+        append_path(buf, &sol_path, exercise_path_prefix);
+        append_edition(buf, exercise_info);
+    }
+}
+
+// Binary names that Cargo rejects because they conflict with its build directories.
+const CARGO_RESERVED_BIN_NAMES: [&str; 4] = ["build", "deps", "examples", "incremental"];
+// File names without an extension that Windows reserves for devices.
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Check that the names of the binaries of all exercises and solutions in the `bin` list
+/// are unique and accepted by Cargo on all platforms.
+/// The binary of a solution is included even if the solution doesn't exist yet.
+/// Cargo accepts leading digits and has no length limit for binary names.
+pub fn check_bin_names(exercise_infos: &[ExerciseInfo]) -> Result<()> {
+    // Binary name -> exercise name
+    let mut bin_names = HashMap::with_capacity(2 * exercise_infos.len());
+
+    for exercise_info in exercise_infos {
+        let name = exercise_info.name.as_str();
+        for bin_name in [name.to_string(), format!("{name}_sol")] {
+            if CARGO_RESERVED_BIN_NAMES.contains(&bin_name.as_str()) {
+                bail!("The binary name `{bin_name}` of the exercise `{name}` is reserved by Cargo for its build directories. Rename the exercise");
+            }
+            if WINDOWS_RESERVED_NAMES.contains(&bin_name.to_ascii_lowercase().as_str()) {
+                bail!("The binary name `{bin_name}` of the exercise `{name}` is a reserved file name on Windows. Rename the exercise");
+            }
+
+            match bin_names.entry(bin_name) {
+                Entry::Vacant(entry) => {
+                    entry.insert(name);
+                }
+                Entry::Occupied(entry) if *entry.get() == name => {
+                    bail!("The exercise name `{name}` is used more than once. Exercise names must all be unique");
+                }
+                Entry::Occupied(entry) => bail!(
+                    "The exercises `{}` and `{name}` result in the same binary name `{}` in `Cargo.toml`. Rename one of them",
+                    entry.get(),
+                    entry.key(),
+                ),
+            }
         }
-        buf.extend_from_slice(exercise_info.name.as_bytes());
-        buf.extend_from_slice(b".rs\" },\n");
     }
+
+    Ok(())
 }
 
 /// Update the `bin` list and leave everything else unchanged.
@@ -71,16 +179,17 @@ pub fn updated_cargo_toml(
     current_cargo_toml: &str,
     exercise_path_prefix: &[u8],
 ) -> Result<Vec<u8>> {
+    check_bin_names(exercise_infos)?;
     let (bins_start_ind, bins_end_ind) = bins_start_end_ind(current_cargo_toml)?;
 
     let mut updated_cargo_toml = Vec::with_capacity(BINS_BUFFER_CAPACITY);
-    updated_cargo_toml.extend_from_slice(current_cargo_toml[..bins_start_ind].as_bytes());
+    updated_cargo_toml.extend_from_slice(&current_cargo_toml.as_bytes()[..bins_start_ind]);
     append_bins(
         &mut updated_cargo_toml,
         exercise_infos,
         exercise_path_prefix,
     );
-    updated_cargo_toml.extend_from_slice(current_cargo_toml[bins_end_ind..].as_bytes());
+    updated_cargo_toml.extend_from_slice(&current_cargo_toml.as_bytes()[bins_end_ind..]);
 
     Ok(updated_cargo_toml)
 }
@@ -88,6 +197,7 @@ pub fn updated_cargo_toml(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::info_file::TestMode;
 
     #[test]
     fn test_bins_start_end_ind() {
@@ -100,27 +210,102 @@ mod tests {
         assert_eq!(bins_start_end_ind("bin =[]").ok(), None);
         assert_eq!(bins_start_end_ind("bin=[]").ok(), None);
         assert_eq!(bins_start_end_ind("bin = [\nxxx\n]").ok(), Some((7, 12)));
+        assert_eq!(
+            bins_start_end_ind("# bin = []\nbin = []").ok(),
+            Some((18, 18))
+        );
+        assert_eq!(bins_start_end_ind("bin = []\nbin = []").ok(), None);
+        assert_eq!(bins_start_end_ind("bin = [\n\n[package]").ok(), None);
+    }
+
+    #[test]
+    fn test_package_edition() {
+        assert_eq!(package_edition(""), None);
+        assert_eq!(package_edition("bin = [\n"), None);
+        assert_eq!(package_edition("[package]\nname = \"x\""), None);
+        assert_eq!(package_edition("[package]\nedition.workspace = true"), None);
+        assert_eq!(
+            package_edition("bin = []\n\n[package]\nedition = \"2024\"").as_deref(),
+            Some("2024"),
+        );
+    }
+
+    #[test]
+    fn test_declared_features() {
+        assert!(declared_features("").is_empty());
+        assert!(declared_features("[features\n").is_empty());
+        assert_eq!(
+            declared_features("bin = []\n\n[features]\nfancy = []\nextra-output = [\"fancy\"]\n"),
+            ["fancy", "extra-output"],
+        );
+    }
+
+    fn exercise_info(name: &str, dir: Option<&str>) -> ExerciseInfo {
+        ExerciseInfo {
+            name: String::from(name),
+            dir: dir.map(String::from),
+            test: TestMode::Off,
+            test_command: None,
+            features: Vec::new(),
+            strict_clippy: false,
+            should_fail: false,
+            hints: Vec::new(),
+            skip_check_unsolved: false,
+            skip_check_lint: false,
+            allowed_lints: Vec::new(),
+            forbidden_patterns: Vec::new(),
+            skip_fmt_check: false,
+            difficulty: None,
+            estimated_minutes: None,
+            tags: Vec::new(),
+            group: None,
+            extra_files: Vec::new(),
+            additional_files: Vec::new(),
+            stdin_fixture: None,
+            prerequisites: Vec::new(),
+            timeout_secs: None,
+            edition: None,
+            author: None,
+            source_url: None,
+        }
+    }
+
+    #[test]
+    fn bin_names() {
+        let check = |names: &[&str]| {
+            let exercise_infos = names
+                .iter()
+                .map(|name| exercise_info(name, None))
+                .collect::<Vec<_>>();
+            check_bin_names(&exercise_infos).map_err(|e| e.to_string())
+        };
+
+        assert_eq!(check(&["intro1", "intro2", "1st"]), Ok(()));
+        assert_eq!(
+            check(&["iterators", "iterators_sol"]).unwrap_err(),
+            "The exercises `iterators` and `iterators_sol` result in the same binary name `iterators_sol` in `Cargo.toml`. Rename one of them",
+        );
+        assert!(check(&["intro1", "intro1"])
+            .unwrap_err()
+            .contains("used more than once"));
+        assert!(check(&["deps"]).unwrap_err().contains("reserved by Cargo"));
+        assert!(check(&["COM1"]).unwrap_err().contains("on Windows"));
+        assert!(check(&["aux"]).unwrap_err().contains("on Windows"));
     }
 
     #[test]
     fn test_bins() {
         let exercise_infos = [
             ExerciseInfo {
-                name: String::from("1"),
-                dir: None,
-                test: true,
+                test: TestMode::Cargo,
                 strict_clippy: true,
-                hint: String::new(),
-                skip_check_unsolved: false,
+                ..exercise_info("1", None)
             },
             ExerciseInfo {
-                name: String::from("2"),
-                dir: Some(String::from("d")),
-                test: false,
-                strict_clippy: false,
-                hint: String::new(),
-                skip_check_unsolved: false,
+                edition: Some(String::from("2018")),
+                ..exercise_info("2", Some("d"))
             },
+            exercise_info("error-handling-3", Some("error-handling")),
         ];
 
         let mut buf = Vec::with_capacity(128);
@@ -129,7 +314,8 @@ mod tests {
             buf,
             br#"
   { name = "1", path = "exercises/1.rs" },
-  { name = "2", path = "exercises/d/2.rs" },
+  { name = "2", path = "exercises/d/2.rs", edition = "2018" },
+  { name = "error-handling-3", path = "exercises/error-handling/error-handling-3.rs" },
 "#,
         );
 
@@ -138,9 +324,49 @@ mod tests {
             br#"abc
 bin = [
   { name = "1", path = "../exercises/1.rs" },
-  { name = "2", path = "../exercises/d/2.rs" },
+  { name = "2", path = "../exercises/d/2.rs", edition = "2018" },
+  { name = "error-handling-3", path = "../exercises/error-handling/error-handling-3.rs" },
 ]
 123"#,
         );
     }
+
+    #[test]
+    fn custom_sections() {
+        let cargo_toml = r#"# The exercises of the course.
+bin = [
+  { name = "old", path = "../exercises/old.rs" },
+]
+
+[package]
+name   =  "exercises"
+edition = "2021"   # bin = [
+publish = false
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+
+[profile.dev]
+opt-level  = 1
+"#
+        .replace("publish = false\n", "publish = false\r\n");
+        let (start_ind, end_ind) = bins_start_end_ind(&cargo_toml).unwrap();
+
+        let exercise_infos = [exercise_info("1", None), exercise_info("2", Some("d"))];
+        let updated = updated_cargo_toml(&exercise_infos, &cargo_toml, b"../").unwrap();
+        let updated = String::from_utf8(updated).unwrap();
+        let (updated_start_ind, updated_end_ind) = bins_start_end_ind(&updated).unwrap();
+
+        assert_eq!(updated[..updated_start_ind], cargo_toml[..start_ind]);
+        assert_eq!(updated[updated_end_ind..], cargo_toml[end_ind..]);
+        assert_eq!(
+            updated[updated_start_ind..updated_end_ind],
+            *"\n  { name = \"1\", path = \"../exercises/1.rs\" },\n  { name = \"2\", path = \"../exercises/d/2.rs\" },\n",
+        );
+        // Updating again doesn't change anything.
+        assert_eq!(
+            updated_cargo_toml(&exercise_infos, &updated, b"../").unwrap(),
+            updated.as_bytes(),
+        );
+    }
 }
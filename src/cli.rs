@@ -0,0 +1,435 @@
+// The command line interface.
+// `build.rs` includes this file to pre-generate the completion scripts.
+// Therefore, it must only depend on `clap`, `clap_complete` and `std`.
+
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::{env::Shells, Shell};
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+/// The default of `--debounce-ms`.
+pub const DEFAULT_DEBOUNCE_MS: u64 = 200;
+/// The default path of the archive created by `rustlings export`.
+pub const DEFAULT_EXPORT_PATH: &str = "rustlings-export.zip";
+
+/// The help of the commands that respect `RUSTLINGS_EXERCISES_DIR` and `RUSTLINGS_SOLUTIONS_DIR`.
+pub const DIRS_ENV_VARS_HELP: &str = "Environment variables:
+  RUSTLINGS_EXERCISES_DIR  The directory of the exercise files instead of `exercises`
+  RUSTLINGS_SOLUTIONS_DIR  The directory of the solution files instead of `solutions`";
+
+/// Rustlings is a collection of small exercises to get you used to writing and reading Rust code
+#[derive(Parser)]
+#[command(version, after_help = DIRS_ENV_VARS_HELP)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Subcommands>,
+    /// Manually run the current exercise using `r` in the watch mode.
+    /// Only use this if Rustlings fails to detect exercise file changes.
+    #[arg(long)]
+    pub manual_run: bool,
+    /// Send a desktop notification in the watch mode when the current exercise is done
+    /// and when all exercises are done. Can be muted using `m`
+    #[arg(long)]
+    pub notify: bool,
+    /// Wait this many milliseconds after the last change of an exercise file before rerunning it.
+    /// Increase it if the exercise is run multiple times when saving
+    #[arg(long, value_name = "MS", default_value_t = DEFAULT_DEBOUNCE_MS)]
+    pub debounce_ms: u64,
+    /// Don't build the next pending exercise in the background in the watch mode
+    /// after the current one is done. Saves memory on low-memory machines
+    #[arg(long)]
+    pub no_prefetch: bool,
+    #[command(flatten)]
+    pub sandbox: SandboxArgs,
+}
+
+/// Write the script that registers the completion of the subcommands, options and exercise names.
+/// Like the script printed by `COMPLETE=<SHELL> rustlings`, it calls `rustlings` on every completion
+/// request to complete the exercise names of the current directory.
+pub fn write_completion_script(shell: Shell, writer: &mut dyn Write) -> io::Result<()> {
+    let cmd = Args::command();
+    let name = cmd.get_name();
+    let shells = Shells::builtins();
+    // All variants of `Shell` are built in.
+    let completer = shells.completer(&shell.to_string()).unwrap();
+
+    completer.write_registration("COMPLETE", name, name, name, writer)
+}
+
+#[derive(Subcommand)]
+pub enum Subcommands {
+    /// Initialize the official Rustlings exercises
+    Init {
+        /// Initialize in this directory instead of `rustlings/` in the current directory.
+        /// It is created if it doesn't exist
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// Only add missing files if the directory is already initialized.
+        /// Existing files aren't overwritten
+        #[arg(long, requires = "path")]
+        force: bool,
+    },
+    /// Run a single exercise. Runs the next pending exercise if the exercise name is not specified
+    #[command(after_help = DIRS_ENV_VARS_HELP)]
+    Run {
+        /// The name of the exercise
+        name: Option<String>,
+        /// Run the exercise even if its prerequisites aren't done yet
+        #[arg(long)]
+        force: bool,
+        /// Rerun the exercise whenever its file changes without moving on to the next exercise
+        #[arg(long)]
+        watch: bool,
+        /// Only compile the exercise and print the compiler diagnostics as a JSON array
+        /// in the format of `rustc --error-format=json` for editor integrations.
+        /// The current exercise isn't changed
+        #[arg(long, conflicts_with = "watch")]
+        json_errors: bool,
+        /// Suppress the output of the exercise and only print `PASS` or `FAIL` with its name
+        /// for log parsers. The output of `--json-errors` isn't affected
+        #[arg(long, conflicts_with = "watch")]
+        no_output: bool,
+    },
+    /// Check all the exercises, marking them as done or pending accordingly.
+    CheckAll,
+    /// Run all exercises one after the other in their current state and report every failure.
+    /// Doesn't change the progress. Exits with failure if any exercise fails
+    Verify,
+    /// Print the list of all exercises
+    List(ListArgs),
+    /// Print the metadata of an exercise like its difficulty, tags and author
+    Info {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Reset a single exercise
+    Reset {
+        /// The name of the exercise
+        name: String,
+        /// Don't ask for confirmation before undoing all changes to the exercise file
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Show a hint. Shows the hint of the next pending exercise if the exercise name is not specified
+    Hint {
+        /// The name of the exercise
+        name: Option<String>,
+        /// The level of the hint from 1 (a nudge) up to a near-solution
+        #[arg(long, default_value_t = 1, conflicts_with = "all")]
+        level: usize,
+        /// Show the hints of all levels
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show the progress: the done exercises, the current streak and the completion per tag
+    Stats {
+        /// Print the statistics as JSON
+        #[arg(long, conflicts_with = "export")]
+        json: bool,
+        /// Export a row per done exercise with its completion time and duration instead
+        #[arg(long, value_enum)]
+        export: Option<StatsExportFormat>,
+        /// The file to write the export to instead of stdout
+        #[arg(long, requires = "export")]
+        out: Option<PathBuf>,
+        /// Also export the exercises that aren't done yet
+        #[arg(long, requires = "export")]
+        include_incomplete: bool,
+    },
+    /// Commands for the exercise packs listed in `info.toml`
+    #[command(subcommand)]
+    Pack(PackCommands),
+    /// Commands for developing (third-party) Rustlings exercises
+    #[command(subcommand)]
+    Dev(DevCommands),
+    /// Create a standalone exercise pack with an example exercise that passes `rustlings dev check`
+    NewPack {
+        /// The name of the pack and its directory
+        name: String,
+        /// Don't try to initialize a Git repository in the pack directory
+        #[arg(long)]
+        no_git: bool,
+    },
+    /// Bundle the exercises into an archive to share them for offline use.
+    /// The progress isn't included
+    Export {
+        /// The format of the archive
+        #[arg(long, value_enum, default_value_t = ExportFormat::Zip)]
+        format: ExportFormat,
+        /// Also include the solutions
+        #[arg(long)]
+        include_solutions: bool,
+        /// The path of the archive
+        #[arg(short, long, default_value = DEFAULT_EXPORT_PATH)]
+        output: PathBuf,
+    },
+    /// Extract an archive created by `rustlings export` into a new directory and update its `Cargo.toml`
+    Import {
+        /// The path of the archive
+        file: PathBuf,
+        /// The directory to extract the archive into.
+        /// Defaults to the file name of the archive without its extension
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Check the Rust toolchain, the current directory and the exercises.
+    /// Exits with failure if any check fails
+    Doctor,
+    /// Print the shell completion script for subcommands, options and exercise names
+    Completions {
+        /// The shell to print the completion script for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// Building outside of the `target/` directory, e.g. in CI or on a read-only file system.
+#[derive(clap::Args, Clone, Copy, Default)]
+pub struct SandboxArgs {
+    /// Build every exercise in its own target directory in the temporary directory instead of `target/`.
+    /// Also activated by setting `RUSTLINGS_TARGET_DIR` to the directory of the target directories
+    #[arg(long)]
+    pub sandbox: bool,
+    /// Keep the target directories of the sandbox mode to reuse them in later runs
+    #[arg(long)]
+    pub keep_artifacts: bool,
+}
+
+/// Options of the `list` command.
+#[derive(clap::Args)]
+pub struct ListArgs {
+    /// Sort the exercises instead of showing them in their default order
+    #[arg(long, value_enum)]
+    pub sort: Option<ListSort>,
+    /// Only show exercises with this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+    /// Only show the exercises of this group
+    #[arg(long)]
+    pub group: Option<String>,
+    /// Show the exercises of groups in which all exercises are done instead of only their count
+    #[arg(long)]
+    pub expand: bool,
+    /// The output format
+    #[arg(long, value_enum, default_value_t)]
+    pub output: ListOutput,
+    /// Show the estimated time of every exercise and the total estimated time
+    #[arg(long)]
+    pub show_time: bool,
+}
+
+/// How to sort the printed list of exercises.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListSort {
+    /// Sort by difficulty (easiest first). Exercises without a difficulty come last.
+    Difficulty,
+}
+
+/// The format of the printed list of exercises.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum ListOutput {
+    /// A human-readable table
+    #[default]
+    Table,
+    /// A JSON array of the exercises' metadata for editors and scripts
+    Json,
+}
+
+/// The format of `rustlings stats --export`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StatsExportFormat {
+    /// A row per exercise with its completion time and the time spent on it
+    Csv,
+}
+
+/// The format of the archive created by `rustlings export`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// A ZIP archive with uncompressed files
+    Zip,
+}
+
+#[derive(Subcommand)]
+pub enum PackCommands {
+    /// Download the exercise packs listed in `info.toml`, verify their checksums
+    /// and update `Cargo.toml`
+    Fetch,
+}
+
+#[derive(Subcommand)]
+pub enum DevCommands {
+    /// Create a new project for third-party Rustlings exercises
+    New {
+        /// The path to create the project in
+        path: PathBuf,
+        /// Don't try to initialize a Git repository in the project directory
+        #[arg(long)]
+        no_git: bool,
+    },
+    /// Run checks on the exercises
+    ///
+    /// Exit codes:
+    ///   0  All checks passed
+    ///   1  Any other error like a missing program or invalid arguments
+    ///   2  Problems in `info.toml`, `Cargo.toml`, editor files or unexpected files
+    ///   3  An exercise is already solved or an intentionally solved one fails
+    ///   4  A solution is missing, unfinished or fails
+    ///   5  A solution isn't formatted
+    ///
+    /// If checks of multiple categories fail, the lowest code is used.
+    #[command(after_help = DIRS_ENV_VARS_HELP, verbatim_doc_comment)]
+    Check(CheckArgs),
+    /// Run Clippy on all exercises and solutions and report all warnings
+    Lint,
+    /// Update the `Cargo.toml` file for the exercises
+    #[command(after_help = DIRS_ENV_VARS_HELP)]
+    Update {
+        /// Only print the changes to `Cargo.toml` without writing them.
+        /// Fails if there are any changes
+        #[arg(long, visible_alias = "check")]
+        dry_run: bool,
+        /// Build the workspace after writing `Cargo.toml`.
+        /// The original file is restored if the build fails
+        #[arg(long, conflicts_with = "dry_run")]
+        validate: bool,
+        /// Also generate a file for editor support with rust-analyzer.
+        /// An existing `rust-project.json` is always updated and verified by `dev check`
+        #[arg(long, value_enum, default_value_t = Editor::None)]
+        editor: Editor,
+    },
+    /// Add a new exercise with a solution to `info.toml` and create their files
+    #[command(visible_alias = "new-exercise")]
+    Scaffold(ScaffoldArgs),
+    /// Remove an exercise from `info.toml` and delete its files including its solution
+    RemoveExercise(RemoveArgs),
+    /// Rename an exercise in `info.toml` and rename its files including its solution
+    RenameExercise(RenameArgs),
+    /// Migrate `info.toml` to the format version supported by this Rustlings version
+    Migrate {
+        /// Only print the migrated `info.toml` without writing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Options of `dev check`.
+#[derive(clap::Args)]
+pub struct CheckArgs {
+    /// Require that every exercise has a solution
+    #[arg(short, long)]
+    pub require_solutions: bool,
+    /// The maximum number of exercises or solutions to run in parallel.
+    /// Defaults to the number of available cores. `0` runs all of them at once
+    #[arg(short, long)]
+    pub jobs: Option<usize>,
+    /// Print a JSON report of all checks instead of stopping at the first error.
+    /// The exit code is still nonzero if any check fails
+    #[arg(long)]
+    pub json: bool,
+    /// Write a JSON report for CI systems with the status of every exercise and solution
+    /// to this file. Like with `--json`, all checks run instead of stopping at the first error.
+    /// The report is also written if a check fails
+    #[arg(long, value_name = "FILE")]
+    pub emit_report: Option<PathBuf>,
+    /// Stop at the first problem in `info.toml` or the first unexpected file
+    /// instead of reporting all of them
+    #[arg(long)]
+    pub fail_fast: bool,
+    /// Don't run Clippy on the solutions to save time
+    #[arg(long)]
+    pub no_clippy: bool,
+    /// Check all exercises and solutions even if they didn't change since the last successful run
+    #[arg(long)]
+    pub force: bool,
+    /// Print the slowest exercises and solutions with their durations.
+    /// The durations are added to the JSON report instead if `--json` is used
+    #[arg(long)]
+    pub timings: bool,
+    /// Only check the exercises with this name or matching this glob pattern like `structs*`.
+    /// Can be repeated. Checks of the whole course like the one of `Cargo.toml` are skipped
+    #[arg(long, value_name = "NAME")]
+    pub only: Vec<String>,
+    /// Fail on warnings like lines longer than 100 characters or trailing whitespace
+    /// in exercise files instead of only printing them
+    #[arg(long)]
+    pub strict: bool,
+    /// Require the first hint of every exercise to have at least 3 sentences or 100 characters
+    #[arg(long)]
+    pub strict_hints: bool,
+    /// Require at least one guiding `// TODO: …` comment with text in every exercise
+    /// instead of only bare `// TODO` placeholders
+    #[arg(long)]
+    pub pedantic: bool,
+    /// Fix mechanical problems instead of failing: Update an outdated `Cargo.toml`
+    /// and format the solutions with `rustfmt`. The modified files are printed
+    #[arg(long)]
+    pub fix: bool,
+    /// Only run the static checks of `Cargo.toml`, `info.toml` and unexpected files
+    /// without running any exercise or solution
+    #[arg(long, conflicts_with_all = ["solutions_only", "exercises_only"])]
+    pub no_run: bool,
+    /// Only run the solutions and check their formatting. All other checks are skipped
+    #[arg(long, conflicts_with = "exercises_only")]
+    pub solutions_only: bool,
+    /// Only run the exercises to check that they aren't already solved. All other checks are skipped
+    #[arg(long)]
+    pub exercises_only: bool,
+    /// Also build all solutions with this Rust version (or another toolchain) installed by rustup,
+    /// e.g. the minimum supported Rust version `1.75`. Defaults to `msrv` in `info.toml`
+    #[arg(long, value_name = "VERSION")]
+    pub msrv: Option<String>,
+}
+
+/// Options of `dev scaffold`.
+#[derive(clap::Args)]
+pub struct ScaffoldArgs {
+    /// The name of the new exercise
+    pub name: String,
+    /// The directory of the exercise inside the exercises directory
+    #[arg(long)]
+    pub dir: Option<String>,
+    /// The hint of the exercise. `dev check` fails until the default placeholder is replaced
+    #[arg(long, default_value = "TODO")]
+    pub hint: String,
+    /// The difficulty of the exercise from 1 (easiest) to 5 (hardest)
+    #[arg(long)]
+    pub difficulty: Option<u8>,
+    /// Create an exercise that is checked by tests instead of running its `main` function
+    #[arg(long)]
+    pub test: bool,
+}
+
+/// Options of `dev remove-exercise`.
+#[derive(clap::Args)]
+pub struct RemoveArgs {
+    /// The name of the exercise to remove
+    pub name: String,
+    /// Only print what would be removed without changing any files
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Options of `dev rename-exercise`.
+#[derive(clap::Args)]
+pub struct RenameArgs {
+    /// The current name of the exercise
+    pub old: String,
+    /// The new name of the exercise
+    pub new: String,
+    /// Also rename the exercise in the state file `.rustlings-state.txt` to keep its progress
+    #[arg(long)]
+    pub keep_progress: bool,
+}
+
+/// The editor support file generated by `dev update`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Editor {
+    /// `.vscode/settings.json` pointing rust-analyzer at the `Cargo.toml` file
+    Vscode,
+    /// `rust-project.json` for rust-analyzer without Cargo
+    RaProject,
+    /// No editor support file
+    None,
+}
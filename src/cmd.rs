@@ -1,19 +1,77 @@
 use anyhow::{bail, Context, Result};
 use serde::Deserialize;
 use std::{
-    io::Read,
-    path::PathBuf,
-    process::{Command, Stdio},
+    env, fs,
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cache,
+    cli::SandboxArgs,
+    hash::{toolchain_versions, StableHasher},
+};
+
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Overrides the directory of the sandbox target directories and activates the sandbox mode.
+pub const TARGET_DIR_ENV_VAR: &str = "RUSTLINGS_TARGET_DIR";
+
+// A hack to make `cargo run` work when developing Rustlings.
+const CARGO_TOML_PATH: &str = if cfg!(debug_assertions) {
+    "dev/Cargo.toml"
+} else {
+    "Cargo.toml"
 };
 
 /// Run a command with a description for a possible error and append the merged stdout and stderr.
+/// The `input` is written to the command's stdin. Without it, stdin is closed.
+/// The command is killed if it doesn't exit before the `timeout`.
+/// On Unix, a command with a timeout runs in its own process group which is killed as a whole.
+/// Otherwise, the processes spawned by `cargo test` or a custom test command would keep running.
 /// The boolean in the returned `Result` is true if the command's exit status is success.
-fn run_cmd(mut cmd: Command, description: &str, output: Option<&mut Vec<u8>>) -> Result<bool> {
-    let spawn = |mut cmd: Command| {
+fn run_cmd(
+    mut cmd: Command,
+    description: &str,
+    input: Option<&[u8]>,
+    output: Option<&mut Vec<u8>>,
+    timeout: Option<Duration>,
+) -> Result<bool> {
+    #[cfg(not(windows))]
+    if timeout.is_some() {
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    }
+
+    let spawn = |mut cmd: Command| -> Result<Child> {
         // NOTE: The closure drops `cmd` which prevents a pipe deadlock.
-        cmd.stdin(Stdio::null())
+        let mut child = cmd
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
             .spawn()
-            .with_context(|| format!("Failed to run the command `{description}`"))
+            .with_context(|| format!("Failed to run the command `{description}`"))?;
+
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            let input = input.to_vec();
+            // Write in another thread to not block on a full pipe while the output isn't read.
+            // Errors are ignored because the command doesn't have to read all of its input.
+            // Dropping `stdin` at the end closes it.
+            thread::Builder::new()
+                .spawn(move || {
+                    let _ = stdin.write_all(&input);
+                })
+                .with_context(|| {
+                    format!("Failed to spawn a thread to write the input of the command `{description}`")
+                })?;
+        }
+
+        Ok(child)
     };
 
     let mut handle = if let Some(output) = output {
@@ -26,7 +84,31 @@ fn run_cmd(mut cmd: Command, description: &str, output: Option<&mut Vec<u8>>) ->
         })?;
 
         cmd.stdout(writer_clone).stderr(writer);
-        let handle = spawn(cmd)?;
+        let mut handle = spawn(cmd)?;
+
+        if let Some(timeout) = timeout {
+            // Read in another thread to be able to stop waiting for the output.
+            let reader_handle = thread::Builder::new()
+                .spawn(move || {
+                    let mut cmd_output = Vec::new();
+                    reader.read_to_end(&mut cmd_output).map(|_| cmd_output)
+                })
+                .with_context(|| {
+                    format!("Failed to spawn a thread to read the output of the command `{description}`")
+                })?;
+
+            // NOTE: The reader thread isn't joined on a timeout because child processes
+            // of the killed command might still hold the pipe open.
+            let status = wait_timeout(&mut handle, timeout, description)?;
+
+            let cmd_output = reader_handle.join().unwrap().with_context(|| {
+                format!("Failed to read the output of the command `{description}`")
+            })?;
+            output.extend_from_slice(&cmd_output);
+            output.push(b'\n');
+
+            return Ok(status.success());
+        }
 
         reader
             .read_to_end(output)
@@ -40,12 +122,75 @@ fn run_cmd(mut cmd: Command, description: &str, output: Option<&mut Vec<u8>>) ->
         spawn(cmd)?
     };
 
+    if let Some(timeout) = timeout {
+        return wait_timeout(&mut handle, timeout, description).map(|status| status.success());
+    }
+
     handle
         .wait()
         .with_context(|| format!("Failed to wait on the command `{description}` to exit"))
         .map(|status| status.success())
 }
 
+// Wait on the child to exit and kill it if it takes longer than `timeout`.
+fn wait_timeout(child: &mut Child, timeout: Duration, description: &str) -> Result<ExitStatus> {
+    let start = Instant::now();
+    // Start with a short interval because most commands exit quickly.
+    let mut poll_interval = Duration::from_millis(1);
+
+    loop {
+        if let Some(status) = child
+            .try_wait()
+            .with_context(|| format!("Failed to wait on the command `{description}` to exit"))?
+        {
+            return Ok(status);
+        }
+
+        if start.elapsed() >= timeout {
+            kill(child).with_context(|| format!("Failed to kill the command `{description}`"))?;
+            // Reap the killed process.
+            let _ = child.wait();
+
+            bail!(
+                "The command `{description}` didn't exit within the timeout of {} seconds and was killed",
+                timeout.as_secs(),
+            );
+        }
+
+        thread::sleep(poll_interval);
+        poll_interval = (poll_interval * 2).min(MAX_POLL_INTERVAL);
+    }
+}
+
+// Kill the child's process group which was created by `run_cmd`.
+#[cfg(not(windows))]
+fn kill(child: &mut Child) -> io::Result<()> {
+    rustix::process::kill_process_group(
+        rustix::process::Pid::from_child(child),
+        rustix::process::Signal::Kill,
+    )
+    .map_err(io::Error::from)
+}
+
+#[cfg(windows)]
+fn kill(child: &mut Child) -> io::Result<()> {
+    child.kill()
+}
+
+// Add `--features a,b` if there are any features to activate.
+fn add_features_arg(cmd: &mut Command, features: &[impl AsRef<str>]) {
+    let Some((first, rest)) = features.split_first() else {
+        return;
+    };
+
+    let mut arg = String::from(first.as_ref());
+    for feature in rest {
+        arg.push(',');
+        arg.push_str(feature.as_ref());
+    }
+    cmd.arg("--features").arg(arg);
+}
+
 // Parses parts of the output of `cargo metadata`.
 #[derive(Deserialize)]
 struct CargoMetadata {
@@ -54,10 +199,13 @@ struct CargoMetadata {
 
 pub struct CmdRunner {
     target_dir: PathBuf,
+    // The directory containing a target directory per binary in the sandbox mode.
+    sandbox_root: Option<PathBuf>,
+    keep_artifacts: bool,
 }
 
 impl CmdRunner {
-    pub fn build() -> Result<Self> {
+    pub fn build(sandbox_args: SandboxArgs) -> Result<Self> {
         // Get the target directory from Cargo.
         let metadata_output = Command::new("cargo")
             .arg("metadata")
@@ -79,50 +227,236 @@ impl CmdRunner {
                 "Failed to read the field `target_directory` from the output of the command `cargo metadata …`",
             )?;
 
+        let sandbox_root = match env::var_os(TARGET_DIR_ENV_VAR) {
+            Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+            _ => sandbox_args
+                .sandbox
+                .then(|| env::temp_dir().join("rustlings-sandbox")),
+        };
+
         Ok(Self {
             target_dir: metadata.target_directory,
+            sandbox_root,
+            keep_artifacts: sandbox_args.keep_artifacts,
         })
     }
 
+    // The target directory of the binary in the sandbox mode.
+    // It is deterministic to be reusable with `--keep-artifacts`.
+    // The project's default target directory is hashed to separate the binaries of different projects.
+    fn sandbox_target_dir(&self, bin_name: &str) -> Option<PathBuf> {
+        let sandbox_root = self.sandbox_root.as_ref()?;
+
+        let mut hasher = StableHasher::default();
+        self.target_dir.hash(&mut hasher);
+        bin_name.hash(&mut hasher);
+
+        Some(sandbox_root.join(format!("{bin_name}-{:016x}", hasher.finish())))
+    }
+
+    // The target directory containing the builds of the binary.
+    fn bin_target_dir(&self, bin_name: &str) -> PathBuf {
+        self.sandbox_target_dir(bin_name)
+            .unwrap_or_else(|| self.target_dir.clone())
+    }
+
+    /// Remove the target directory of the binary in the sandbox mode unless `--keep-artifacts` is passed.
+    /// Errors are ignored because the directory is only temporary.
+    pub fn clean_sandbox(&self, bin_name: &str) {
+        if self.keep_artifacts {
+            return;
+        }
+
+        if let Some(sandbox_target_dir) = self.sandbox_target_dir(bin_name) {
+            let _ = fs::remove_dir_all(sandbox_target_dir);
+        }
+    }
+
     pub fn cargo<'out>(
         &self,
         subcommand: &str,
         bin_name: &str,
+        features: &[impl AsRef<str>],
         output: Option<&'out mut Vec<u8>>,
+        timeout: Option<Duration>,
     ) -> CargoSubcommand<'out> {
         let mut cmd = Command::new("cargo");
         cmd.arg(subcommand).arg("-q").arg("--bin").arg(bin_name);
+        add_features_arg(&mut cmd, features);
 
         // A hack to make `cargo run` work when developing Rustlings.
         #[cfg(debug_assertions)]
-        cmd.arg("--manifest-path")
-            .arg("dev/Cargo.toml")
-            .arg("--target-dir")
-            .arg(&self.target_dir);
+        cmd.arg("--manifest-path").arg("dev/Cargo.toml");
+
+        if cfg!(debug_assertions) || self.sandbox_root.is_some() {
+            cmd.arg("--target-dir").arg(self.bin_target_dir(bin_name));
+        }
 
         if output.is_some() {
             cmd.arg("--color").arg("always");
         }
 
-        CargoSubcommand { cmd, output }
+        CargoSubcommand {
+            cmd,
+            output,
+            timeout,
+        }
     }
 
+    /// Run the custom test command of an exercise instead of `cargo test`.
+    /// The command is split at whitespace and not run in a shell.
+    /// Cargo subcommands get the same arguments as `cargo` like `--bin`.
+    /// Other programs get the path of the built binary in the environment variable `RUSTLINGS_BIN`.
     /// The boolean in the returned `Result` is true if the command's exit status is success.
-    pub fn run_debug_bin(&self, bin_name: &str, output: Option<&mut Vec<u8>>) -> Result<bool> {
-        // 7 = "/debug/".len()
-        let mut bin_path =
-            PathBuf::with_capacity(self.target_dir.as_os_str().len() + 7 + bin_name.len());
-        bin_path.push(&self.target_dir);
+    pub fn run_test_command(
+        &self,
+        test_command: &str,
+        bin_name: &str,
+        features: &[impl AsRef<str>],
+        output: Option<&mut Vec<u8>>,
+        timeout: Option<Duration>,
+    ) -> Result<bool> {
+        let mut words = test_command.split_whitespace();
+        let Some(program) = words.next() else {
+            bail!("The test command is empty");
+        };
+
+        if program == "cargo" {
+            let Some(subcommand) = words.next() else {
+                bail!("The test command `{test_command}` has no Cargo subcommand");
+            };
+            let mut cmd = self.cargo(subcommand, bin_name, features, output, timeout);
+            cmd.args(words);
+            return cmd.run(test_command);
+        }
+
+        let mut cmd = Command::new(program);
+        cmd.args(words)
+            .env("RUSTLINGS_BIN", self.debug_bin_path(bin_name));
+        run_cmd(cmd, test_command, None, output, timeout)
+    }
+
+    /// Build the binary with the `toolchain` installed by rustup, e.g. the minimum supported Rust version.
+    /// A separate target directory is used to not invalidate the builds with the default toolchain.
+    /// The boolean in the returned `Result` is true if the build succeeded.
+    pub fn build_with_toolchain(
+        &self,
+        toolchain: &str,
+        bin_name: &str,
+        features: &[impl AsRef<str>],
+        output: Option<&mut Vec<u8>>,
+    ) -> Result<bool> {
+        let mut cmd = Command::new("cargo");
+        cmd.arg(format!("+{toolchain}"))
+            .arg("build")
+            .arg("-q")
+            .arg("--bin")
+            .arg(bin_name);
+        add_features_arg(&mut cmd, features);
+        cmd.arg("--target-dir").arg(
+            self.bin_target_dir(bin_name)
+                .join(format!("toolchain-{toolchain}")),
+        );
+
+        // A hack to make `cargo run` work when developing Rustlings.
+        #[cfg(debug_assertions)]
+        cmd.arg("--manifest-path").arg("dev/Cargo.toml");
+
+        if output.is_some() {
+            cmd.arg("--color").arg("always");
+        }
+
+        run_cmd(
+            cmd,
+            &format!("cargo +{toolchain} build …"),
+            None,
+            output,
+            None,
+        )
+    }
+
+    fn debug_bin_path(&self, bin_name: &str) -> PathBuf {
+        let mut bin_path = self.bin_target_dir(bin_name);
         bin_path.push("debug");
         bin_path.push(bin_name);
+        bin_path
+    }
 
-        run_cmd(Command::new(&bin_path), &bin_path.to_string_lossy(), output)
+    /// The `input` is written to the binary's stdin. Without it, stdin is closed.
+    /// The boolean in the returned `Result` is true if the command's exit status is success.
+    pub fn run_debug_bin(
+        &self,
+        bin_name: &str,
+        input: Option<&[u8]>,
+        output: Option<&mut Vec<u8>>,
+        timeout: Option<Duration>,
+    ) -> Result<bool> {
+        let bin_path = self.debug_bin_path(bin_name);
+
+        run_cmd(
+            Command::new(&bin_path),
+            &bin_path.to_string_lossy(),
+            input,
+            output,
+            timeout,
+        )
+    }
+
+    /// Hash everything that affects running a binary: The toolchain, `Cargo.toml`,
+    /// the source file with the files next to it (like modules) and the `options` of the run.
+    /// `None` if the toolchain version can't be determined.
+    pub fn run_hash(&self, src_path: &str, options: impl Hash) -> Option<u64> {
+        let toolchain_versions = toolchain_versions()?;
+
+        let mut hasher = StableHasher::default();
+        // The output might change between versions.
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        toolchain_versions.hash(&mut hasher);
+        options.hash(&mut hasher);
+        fs::read(CARGO_TOML_PATH).ok().hash(&mut hasher);
+        src_path.hash(&mut hasher);
+        fs::read(src_path).ok().hash(&mut hasher);
+
+        if let Some(Ok(read_dir)) = Path::new(src_path).parent().map(fs::read_dir) {
+            let mut sibling_paths = read_dir
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.is_file() && path.as_os_str() != src_path)
+                .collect::<Vec<_>>();
+            sibling_paths.sort_unstable();
+            for path in sibling_paths {
+                path.hash(&mut hasher);
+                fs::read(&path).ok().hash(&mut hasher);
+            }
+        }
+
+        Some(hasher.finish())
+    }
+
+    /// Return the output of the last run of the binary if it succeeded with the same `run_hash`
+    /// and the binary still exists.
+    pub fn cached_success(&self, bin_name: &str, run_hash: u64) -> Option<Vec<u8>> {
+        if !self.debug_bin_path(bin_name).exists() {
+            return None;
+        }
+
+        cache::read(bin_name, run_hash)
+    }
+
+    /// Record the output of a successful run or remove an outdated entry after a failure.
+    /// Errors are ignored because the cache is only an optimization.
+    pub fn cache_run(&self, bin_name: &str, run_hash: u64, success: bool, output: &[u8]) {
+        if success {
+            let _ = cache::write(bin_name, run_hash, output);
+        } else {
+            cache::remove(bin_name);
+        }
     }
 }
 
 pub struct CargoSubcommand<'out> {
     cmd: Command,
     output: Option<&'out mut Vec<u8>>,
+    timeout: Option<Duration>,
 }
 
 impl CargoSubcommand<'_> {
@@ -138,7 +472,29 @@ impl CargoSubcommand<'_> {
     /// The boolean in the returned `Result` is true if the command's exit status is success.
     #[inline]
     pub fn run(self, description: &str) -> Result<bool> {
-        run_cmd(self.cmd, description, self.output)
+        run_cmd(self.cmd, description, None, self.output, self.timeout)
+    }
+
+    /// Spawn the command in the background without any input or output.
+    /// On Unix, it runs with the lowest priority to not slow down commands in the foreground.
+    /// The output and the timeout are ignored.
+    pub fn spawn_background(mut self, description: &str) -> Result<Child> {
+        let child = self
+            .cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to run the command `{description}`"))?;
+
+        // Ignored because the priority is only an optimization.
+        #[cfg(not(windows))]
+        let _ = rustix::process::setpriority_process(
+            Some(rustix::process::Pid::from_child(&child)),
+            19,
+        );
+
+        Ok(child)
     }
 }
 
@@ -156,8 +512,41 @@ mod tests {
         cmd.arg("Hello");
 
         let mut output = Vec::with_capacity(8);
-        run_cmd(cmd, "echo …", Some(&mut output)).unwrap();
+        run_cmd(cmd, "echo …", None, Some(&mut output), None).unwrap();
 
         assert_eq!(output, b"Hello\n\n");
     }
+
+    #[test]
+    fn test_run_cmd_input() {
+        let mut output = Vec::with_capacity(8);
+        run_cmd(
+            Command::new("cat"),
+            "cat",
+            Some(b"Hello\n"),
+            Some(&mut output),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(output, b"Hello\n\n");
+    }
+
+    #[test]
+    fn test_run_cmd_timeout() {
+        let mut cmd = Command::new("sleep");
+        cmd.arg("10");
+
+        let mut output = Vec::new();
+        let e = run_cmd(
+            cmd,
+            "sleep …",
+            None,
+            Some(&mut output),
+            Some(Duration::from_millis(100)),
+        )
+        .unwrap_err();
+
+        assert!(e.to_string().contains("timeout"));
+    }
 }
@@ -0,0 +1,50 @@
+use anyhow::{Context, Result};
+use clap::{Command, CommandFactory};
+use clap_complete::{engine::ArgValueCompleter, CompletionCandidate, Shell};
+use std::{ffi::OsStr, io};
+
+use crate::{
+    cli::{write_completion_script, Args},
+    info_file::InfoFile,
+};
+
+// The subcommands with the exercise name as their argument `name`.
+const EXERCISE_NAME_SUBCOMMANDS: [&str; 4] = ["run", "info", "reset", "hint"];
+
+/// Print the completion script which is also pre-generated into `assets/completions/` by `build.rs`.
+pub fn print(shell: Shell) -> Result<()> {
+    write_completion_script(shell, &mut io::stdout().lock())
+        .context("Failed to write the completion script to stdout")
+}
+
+/// The command for the completion requests of the script, including the completion of exercise names.
+pub fn command() -> Command {
+    let mut cmd = Args::command();
+    for subcommand in EXERCISE_NAME_SUBCOMMANDS {
+        cmd = cmd.mut_subcommand(subcommand, |subcommand| {
+            subcommand.mut_arg("name", |arg| arg.add(exercise_names()))
+        });
+    }
+
+    cmd
+}
+
+// Complete the names of the exercises in the current directory, including fetched packs.
+fn exercise_names() -> ArgValueCompleter {
+    ArgValueCompleter::new(|current: &OsStr| {
+        let Some(current) = current.to_str() else {
+            return Vec::new();
+        };
+        // No candidates instead of an error if `info.toml` is invalid.
+        let Ok(info_file) = InfoFile::parse() else {
+            return Vec::new();
+        };
+
+        info_file
+            .exercises
+            .into_iter()
+            .filter(|exercise_info| exercise_info.name.starts_with(current))
+            .map(|exercise_info| CompletionCandidate::new(exercise_info.name))
+            .collect()
+    })
+}
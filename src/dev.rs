@@ -1,44 +1,42 @@
 use anyhow::{bail, Context, Result};
-use clap::Subcommand;
-use std::path::PathBuf;
+use std::process::ExitCode;
 
-mod check;
-mod new;
-mod update;
+use crate::cli::DevCommands;
 
-#[derive(Subcommand)]
-pub enum DevCommands {
-    /// Create a new project for third-party Rustlings exercises
-    New {
-        /// The path to create the project in
-        path: PathBuf,
-        /// Don't try to initialize a Git repository in the project directory
-        #[arg(long)]
-        no_git: bool,
-    },
-    /// Run checks on the exercises
-    Check {
-        /// Require that every exercise has a solution
-        #[arg(short, long)]
-        require_solutions: bool,
-    },
-    /// Update the `Cargo.toml` file for the exercises
-    Update,
-}
+mod check;
+mod lint;
+mod migrate;
+pub mod new;
+mod remove;
+mod rename;
+mod scaffold;
+pub mod update;
 
 impl DevCommands {
-    pub fn run(self) -> Result<()> {
+    pub fn run(self) -> Result<ExitCode> {
         match self {
             Self::New { path, no_git } => {
                 if cfg!(debug_assertions) {
                     bail!("Disabled in the debug build");
                 }
 
-                new::new(&path, no_git).context(INIT_ERR)
+                new::new(&path, no_git).context(INIT_ERR)?;
             }
-            Self::Check { require_solutions } => check::check(require_solutions),
-            Self::Update => update::update(),
+            // The exit code depends on the category of the failed check.
+            Self::Check(args) => return check::check(args),
+            Self::Lint => lint::lint()?,
+            Self::Update {
+                dry_run,
+                validate,
+                editor,
+            } => update::update(dry_run, validate, editor)?,
+            Self::Scaffold(args) => scaffold::scaffold(args)?,
+            Self::RemoveExercise(args) => remove::remove(args)?,
+            Self::RenameExercise(args) => rename::rename(args)?,
+            Self::Migrate { dry_run } => migrate::migrate(dry_run)?,
         }
+
+        Ok(ExitCode::SUCCESS)
     }
 }
 
@@ -1,36 +1,299 @@
 use anyhow::{anyhow, bail, Context, Error, Result};
 use std::{
     cmp::Ordering,
-    collections::HashSet,
+    collections::{BTreeMap, HashMap, HashSet},
     fs::{self, read_dir, OpenOptions},
-    io::{self, Read, Write},
-    path::{Path, PathBuf},
-    process::{Command, Stdio},
+    io::{self, ErrorKind, Read, Write},
+    iter,
+    path::{self, Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering::Relaxed},
+        mpsc,
+    },
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
-    cargo_toml::{append_bins, bins_start_end_ind, BINS_BUFFER_CAPACITY},
+    cargo_toml::{
+        self, append_bins, bins_start_end_ind, check_bin_names, package_edition,
+        updated_cargo_toml, BINS_BUFFER_CAPACITY, DEFAULT_EDITION, EDITIONS,
+    },
+    cli::{CheckArgs, SandboxArgs},
     cmd::CmdRunner,
-    exercise::{RunnableExercise, OUTPUT_CAPACITY},
-    info_file::{ExerciseInfo, InfoFile},
+    exercise::{failed_bin_output, RunnableExercise, OUTPUT_CAPACITY},
+    info_file::{exercises_dir, solutions_dir, ExerciseInfo, InfoFile, TestMode},
     CURRENT_FORMAT_VERSION,
 };
 
+use self::{
+    cache::CheckCache,
+    problems::Problems,
+    report::{handle_err, CategoryReport, CheckError, CheckReport, Failure, Status},
+    selection::{matching_exercises, Selection},
+    timings::Timings,
+    warnings::{allowed_lints, hint_identifiers, hint_warning, style_warnings, Warnings},
+};
+
+use super::update::editor::{rust_project_json, RUST_PROJECT_JSON_PATH};
+
+mod cache;
+pub(super) mod problems;
+mod report;
+mod selection;
+mod timings;
+mod warnings;
+
 const MAX_N_EXERCISES: usize = 999;
-const MAX_EXERCISE_NAME_LEN: usize = 32;
+// A hack to make `cargo run -- dev check` work when developing Rustlings.
+const CARGO_TOML_PATH: &str = if cfg!(debug_assertions) {
+    "dev/Cargo.toml"
+} else {
+    "Cargo.toml"
+};
+// Used if the number of available cores can't be determined.
+pub(super) const DEFAULT_JOBS: usize = 8;
+pub(super) const MAX_EXERCISE_NAME_LEN: usize = 32;
+pub(super) const MIN_DIFFICULTY: u8 = 1;
+pub(super) const MAX_DIFFICULTY: u8 = 5;
+const MIN_ESTIMATED_MINUTES: u8 = 1;
+const MAX_ESTIMATED_MINUTES: u8 = 120;
+// The first hint must reach one of them with `--strict-hints`.
+const MIN_STRICT_HINT_SENTENCES: usize = 3;
+const MIN_STRICT_HINT_LEN: usize = 100;
+
+// Explains the chars that `forbidden_char` allows.
+// `-` is allowed because Cargo accepts it in the names of binaries.
+pub(super) const ALLOWED_NAME_CHARS_MSG: &str = "Only alphanumeric chars, `_` and `-` are allowed";
 
 // Find a char that isn't allowed in the exercise's `name` or `dir`.
-fn forbidden_char(input: &str) -> Option<char> {
-    input.chars().find(|c| !c.is_alphanumeric() && *c != '_')
+pub(super) fn forbidden_char(input: &str) -> Option<char> {
+    input
+        .chars()
+        .find(|c| !c.is_alphanumeric() && *c != '_' && *c != '-')
+}
+
+// Find a char that isn't allowed in a tag of an exercise.
+fn forbidden_tag_char(tag: &str) -> Option<char> {
+    tag.chars()
+        .find(|c| !c.is_ascii_lowercase() && !c.is_ascii_digit() && *c != '-')
+}
+
+// Hints that were never written like "TODO" or "TODO: write a hint".
+fn is_placeholder_hint(hint: &str) -> bool {
+    let hint = hint.trim_ascii().to_ascii_lowercase();
+    ["todo", "tbd", "fixme"].into_iter().any(|placeholder| {
+        hint.strip_prefix(placeholder).is_some_and(|rest| {
+            rest.starts_with(':') || rest.chars().all(|c| c.is_ascii_punctuation())
+        })
+    })
+}
+
+// The number of sentences ending with `.`, `!` or `?` followed by whitespace or the end.
+fn n_sentences(text: &str) -> usize {
+    let mut chars = text.chars().peekable();
+    let mut n = 0;
+    while let Some(c) = chars.next() {
+        if matches!(c, '.' | '!' | '?') && chars.peek().map_or(true, |next| next.is_whitespace()) {
+            n += 1;
+        }
+    }
+    n
+}
+
+// Return the length in chars and the number of sentences of the hint if it is too short for `--strict-hints`.
+fn short_hint(hint: &str) -> Option<(usize, usize)> {
+    let hint = hint.trim();
+    let len = hint.chars().count();
+    let n_sentences = n_sentences(hint);
+    (len < MIN_STRICT_HINT_LEN && n_sentences < MIN_STRICT_HINT_SENTENCES)
+        .then_some((len, n_sentences))
+}
+
+// Return why the URL is invalid.
+// Only the structure is checked because Rustlings has no URL parser.
+fn url_problem(url: &str) -> Option<&'static str> {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return Some("It must start with `https://` or `http://`");
+    };
+
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() {
+        return Some("It has no host");
+    }
+    if url.contains(|c: char| c.is_whitespace() || c.is_control()) {
+        return Some("It contains whitespace");
+    }
+
+    None
+}
+
+// A name of a file in the same directory as the exercise file without any path separators.
+// Backslashes are rejected too because they are path separators on Windows.
+fn is_file_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\'])
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Return the line number starting at 1 and the line of the first occurrence of the pattern.
+// If the pattern starts or ends with an identifier character, the occurrence can't be directly
+// preceded or followed by one to not find `clone()` in `clone_from()` or `unwrap` in `unwrap_or`.
+fn find_forbidden_pattern<'a>(content: &'a str, pattern: &str) -> Option<(usize, &'a str)> {
+    let check_start = pattern.starts_with(is_ident_char);
+    let check_end = pattern.ends_with(is_ident_char);
+
+    (1..).zip(content.lines()).find_map(|(line_number, line)| {
+        line.match_indices(pattern)
+            .any(|(ind, _)| {
+                (!check_start || !line[..ind].ends_with(is_ident_char))
+                    && (!check_end || !line[ind + pattern.len()..].starts_with(is_ident_char))
+            })
+            .then_some((line_number, line))
+    })
+}
+
+// Whether the content contains a `// TODO` marker inside of a comment.
+fn contains_todo_comment(content: &str) -> bool {
+    any_comment(content, |comment| comment.contains("// TODO"))
+}
+
+// Whether the content contains a guiding `// TODO: …` comment with text after the colon.
+// A bare `// TODO` is only a placeholder that doesn't tell the user what to change.
+fn contains_guiding_todo_comment(content: &str) -> bool {
+    any_comment(content, |comment| {
+        comment.match_indices("// TODO:").any(|(ind, marker)| {
+            let text = comment[ind + marker.len()..]
+                .lines()
+                .next()
+                .unwrap_or_default();
+            !text.trim().trim_end_matches("*/").trim().is_empty()
+        })
+    })
+}
+
+// Whether any line or block comment in the content matches the predicate.
+// A simple lexer skips string and char literals so that `"// TODO"` doesn't count.
+// Raw strings aren't supported.
+fn any_comment(content: &str, predicate: impl Fn(&str) -> bool) -> bool {
+    let bytes = content.as_bytes();
+    let mut ind = 0;
+    while ind < bytes.len() {
+        match bytes[ind] {
+            b'/' if bytes.get(ind + 1) == Some(&b'/') => {
+                let end = content[ind..]
+                    .find('\n')
+                    .map_or(content.len(), |len| ind + len);
+                if predicate(&content[ind..end]) {
+                    return true;
+                }
+                ind = end;
+            }
+            b'/' if bytes.get(ind + 1) == Some(&b'*') => {
+                let end = content[ind + 2..]
+                    .find("*/")
+                    .map_or(content.len(), |len| ind + 2 + len + 2);
+                if predicate(&content[ind..end]) {
+                    return true;
+                }
+                ind = end;
+            }
+            b'"' => {
+                ind += 1;
+                while ind < bytes.len() {
+                    match bytes[ind] {
+                        b'\\' => ind += 2,
+                        b'"' => break,
+                        _ => ind += 1,
+                    }
+                }
+                ind += 1;
+            }
+            b'\'' => {
+                // A char literal like `'a'` or `'\''`. Otherwise, it is a lifetime or a label.
+                let rest = &content[ind + 1..];
+                let escaped_len = rest
+                    .strip_prefix('\\')
+                    .and_then(|escaped| escaped.get(1..)?.find('\''));
+                let char_len = rest
+                    .chars()
+                    .next()
+                    .filter(|c| rest[c.len_utf8()..].starts_with('\''))
+                    .map(char::len_utf8);
+                ind += match (escaped_len, char_len) {
+                    (Some(len), _) => 4 + len,
+                    (None, Some(len)) => 2 + len,
+                    (None, None) => 1,
+                };
+            }
+            _ => ind += 1,
+        }
+    }
+
+    false
+}
+
+// Extend the path of exercise indices through the prerequisites until it returns to its start.
+// Only exercises after the start are visited so that a cycle is only found from its first exercise.
+fn extend_prerequisite_path(
+    exercise_infos: &[ExerciseInfo],
+    visited: &mut [bool],
+    path: &mut Vec<usize>,
+) -> bool {
+    let start = path[0];
+    let current = path[path.len() - 1];
+    for prerequisite in &exercise_infos[current].prerequisites {
+        let Some(ind) = exercise_infos
+            .iter()
+            .position(|exercise_info| exercise_info.name == *prerequisite)
+        else {
+            continue;
+        };
+
+        // Being its own prerequisite is reported separately.
+        if ind == start && current != start {
+            path.push(start);
+            return true;
+        }
+        if ind <= start || visited[ind] {
+            continue;
+        }
+
+        visited[ind] = true;
+        path.push(ind);
+        if extend_prerequisite_path(exercise_infos, visited, path) {
+            return true;
+        }
+        path.pop();
+    }
+
+    false
+}
+
+// Return the indices of a cycle in the prerequisites starting and ending with the exercise
+// if it is the first exercise of that cycle in `info.toml`.
+fn prerequisite_cycle(exercise_infos: &[ExerciseInfo], exercise_ind: usize) -> Option<Vec<usize>> {
+    let mut visited = vec![false; exercise_infos.len()];
+    let mut path = vec![exercise_ind];
+    extend_prerequisite_path(exercise_infos, &mut visited, &mut path).then_some(path)
 }
 
 // Check that the `Cargo.toml` file is up-to-date.
+// With `fix`, update an outdated file instead of failing and return `true`.
 fn check_cargo_toml(
     exercise_infos: &[ExerciseInfo],
     cargo_toml_path: &str,
     exercise_path_prefix: &[u8],
-) -> Result<()> {
+    fix: bool,
+) -> Result<bool> {
+    check_bin_names(exercise_infos)?;
+
     let current_cargo_toml = fs::read_to_string(cargo_toml_path)
         .with_context(|| format!("Failed to read the file `{cargo_toml_path}`"))?;
 
@@ -41,6 +304,15 @@ fn check_cargo_toml(
     append_bins(&mut new_bins, exercise_infos, exercise_path_prefix);
 
     if old_bins != new_bins {
+        if fix {
+            let updated_cargo_toml =
+                updated_cargo_toml(exercise_infos, &current_cargo_toml, exercise_path_prefix)?;
+            fs::write(cargo_toml_path, updated_cargo_toml)
+                .with_context(|| format!("Failed to write the file `{cargo_toml_path}`"))?;
+
+            return Ok(true);
+        }
+
         if cfg!(debug_assertions) {
             bail!("The file `dev/Cargo.toml` is outdated. Run `cargo run -- dev update` to update it. Then run `cargo run -- dev check` again");
         }
@@ -48,84 +320,659 @@ fn check_cargo_toml(
         bail!("The file `Cargo.toml` is outdated. Run `rustlings dev update` to update it. Then run `rustlings dev check` again");
     }
 
-    Ok(())
+    Ok(false)
+}
+
+// Check that `rust-project.json` is up-to-date if it exists.
+// With `fix`, update an outdated file instead of failing and return `true`.
+fn check_rust_project_json(exercise_infos: &[ExerciseInfo], fix: bool) -> Result<bool> {
+    let Ok(current_rust_project_json) = fs::read_to_string(RUST_PROJECT_JSON_PATH) else {
+        return Ok(false);
+    };
+
+    let rust_project_json = rust_project_json(exercise_infos);
+    if current_rust_project_json != rust_project_json {
+        if fix {
+            fs::write(RUST_PROJECT_JSON_PATH, rust_project_json)
+                .with_context(|| format!("Failed to write the file `{RUST_PROJECT_JSON_PATH}`"))?;
+
+            return Ok(true);
+        }
+
+        if cfg!(debug_assertions) {
+            bail!("The file `{RUST_PROJECT_JSON_PATH}` is outdated. Run `cargo run -- dev update` to update it. Then run `cargo run -- dev check` again");
+        }
+
+        bail!("The file `{RUST_PROJECT_JSON_PATH}` is outdated. Run `rustlings dev update` to update it. Then run `rustlings dev check` again");
+    }
+
+    Ok(false)
 }
 
-// Check the info of all exercises and return their paths in a set.
-fn check_info_file_exercises(info_file: &InfoFile) -> Result<HashSet<PathBuf>> {
-    let mut names = HashSet::with_capacity(info_file.exercises.len());
+// Check the info of all exercises that aren't excluded and return the paths of their files in a set.
+fn check_info_file_exercises(
+    info_file: &InfoFile,
+    selection: &[Selection],
+    args: &CheckArgs,
+    warnings: &mut Warnings,
+) -> Result<HashSet<PathBuf>> {
+    // The exercise names with their positions in `info.toml` starting at 1.
+    let mut names = HashMap::with_capacity(info_file.exercises.len());
     let mut paths = HashSet::with_capacity(info_file.exercises.len());
+    // The lowercase dirs with their spelling and the name of the first exercise in them.
+    let mut dirs = HashMap::<String, (&str, &str)>::new();
+    let mut problems = Problems::new(args.fail_fast);
 
     let mut file_buf = String::with_capacity(1 << 14);
-    for exercise_info in &info_file.exercises {
+    // Only read if an exercise has features.
+    let mut declared_features = None;
+    for ((exercise_info, selection), position) in info_file.exercises.iter().zip(selection).zip(1..)
+    {
         let name = exercise_info.name.as_str();
+        if *selection == Selection::Excluded {
+            // Needed to check the order of the prerequisites of the next exercises.
+            names.entry(name).or_insert(position);
+            continue;
+        }
+
         if name.is_empty() {
-            bail!("Found an empty exercise name in `info.toml`");
+            problems.add_for_exercise(
+                exercise_info,
+                String::from("Found an empty exercise name in `info.toml`"),
+            )?;
         }
         if name.len() > MAX_EXERCISE_NAME_LEN {
-            bail!("The length of the exercise name `{name}` is bigger than the maximum {MAX_EXERCISE_NAME_LEN}");
+            problems.add_for_exercise(exercise_info, format!("The length of the exercise name `{name}` is bigger than the maximum {MAX_EXERCISE_NAME_LEN}"))?;
         }
         if let Some(c) = forbidden_char(name) {
-            bail!("Char `{c}` in the exercise name `{name}` is not allowed");
+            problems.add_for_exercise(
+                exercise_info,
+                format!("Char `{c}` in the exercise name `{name}` is not allowed. {ALLOWED_NAME_CHARS_MSG}"),
+            )?;
         }
 
         if let Some(dir) = &exercise_info.dir {
             if dir.is_empty() {
-                bail!("The exercise `{name}` has an empty dir name in `info.toml`");
+                problems.add_for_exercise(
+                    exercise_info,
+                    format!("The exercise `{name}` has an empty dir name in `info.toml`"),
+                )?;
             }
             if let Some(c) = forbidden_char(dir) {
-                bail!("Char `{c}` in the exercise dir `{dir}` is not allowed");
+                problems.add_for_exercise(
+                    exercise_info,
+                    format!("Char `{c}` in the exercise dir `{dir}` is not allowed. {ALLOWED_NAME_CHARS_MSG}"),
+                )?;
+            }
+
+            // Exercises sharing a dir are intended. Dirs that only differ in case are the same
+            // directory on case-insensitive file systems which confuses the check of unexpected files.
+            let (first_dir, first_name) = *dirs.entry(dir.to_lowercase()).or_insert((dir, name));
+            if first_dir != dir {
+                warnings.add_for_exercise(&mut problems, exercise_info, format!("The dir `{dir}` of the exercise `{name}` only differs in case from the dir `{first_dir}` of the exercise `{first_name}`. Use the same spelling if both exercises belong to the same dir or rename one of them"))?;
             }
         }
 
-        if exercise_info.hint.trim_ascii().is_empty() {
-            bail!("The exercise `{name}` has an empty hint. Please provide a hint or at least tell the user why a hint isn't needed for this exercise");
+        for tag in &exercise_info.tags {
+            if tag.is_empty() {
+                problems.add_for_exercise(
+                    exercise_info,
+                    format!("The exercise `{name}` has an empty tag in `info.toml`"),
+                )?;
+            }
+            if let Some(c) = forbidden_tag_char(tag) {
+                problems.add_for_exercise(exercise_info, format!("Char `{c}` in the tag `{tag}` of the exercise `{name}` is not allowed. Tags can only contain lowercase ASCII letters, digits and `-`"))?;
+            }
         }
 
-        if !names.insert(name) {
-            bail!("The exercise name `{name}` is duplicated. Exercise names must all be unique");
+        if exercise_info.hints.is_empty() {
+            problems.add_for_exercise(exercise_info, format!("The exercise `{name}` has no hints. Please provide a hint or at least tell the user why a hint isn't needed for this exercise"))?;
+        }
+        if let Some((len, n_sentences)) = exercise_info
+            .hints
+            .first()
+            .filter(|_| args.strict_hints)
+            .and_then(|hint| short_hint(hint))
+        {
+            problems.add_for_exercise(exercise_info, format!("The first hint of the exercise `{name}` is too short ({len} characters, {n_sentences} sentences). `--strict-hints` requires at least {MIN_STRICT_HINT_SENTENCES} sentences or {MIN_STRICT_HINT_LEN} characters"))?;
+        }
+        for (level, hint) in (1..).zip(&exercise_info.hints) {
+            if hint.trim_ascii().is_empty() {
+                problems.add_for_exercise(exercise_info, format!("The hint of level {level} of the exercise `{name}` is empty. Please provide a hint or at least tell the user why a hint isn't needed for this exercise"))?;
+            }
+            if is_placeholder_hint(hint) {
+                problems.add_for_exercise(exercise_info, format!("The hint of level {level} of the exercise `{name}` is the placeholder `{}`. Please write the hint", hint.trim_ascii()))?;
+            }
+            if let Some(warning) = hint_warning(level, hint) {
+                warnings.add_for_exercise(&mut problems, exercise_info, warning)?;
+            }
+        }
+
+        match (exercise_info.test, &exercise_info.test_command) {
+            (TestMode::Custom, None) => {
+                problems.add_for_exercise(exercise_info, format!("The exercise `{name}` has `test = \"custom\"` but no `test_command` in `info.toml`"))?;
+            }
+            (TestMode::Custom, Some(test_command)) => {
+                if test_command.trim().is_empty() {
+                    problems.add_for_exercise(
+                        exercise_info,
+                        format!("The `test_command` of the exercise `{name}` is empty"),
+                    )?;
+                } else if let Some(c) = test_command.chars().find(|c| matches!(c, '|' | ';' | '&'))
+                {
+                    problems.add_for_exercise(exercise_info, format!("The `test_command` of the exercise `{name}` contains the shell metacharacter `{c}`. The command isn't run in a shell. It can only be a program with arguments separated by whitespace"))?;
+                }
+            }
+            (TestMode::Cargo | TestMode::Off, Some(_)) => {
+                problems.add_for_exercise(exercise_info, format!("The exercise `{name}` has a `test_command` but it is only run with `test = \"custom\"` in `info.toml`"))?;
+            }
+            (_, None) => (),
+        }
+
+        if let Some(group) = &exercise_info.group {
+            if !info_file
+                .groups
+                .iter()
+                .any(|group_info| group_info.name == *group)
+            {
+                problems.add_for_exercise(exercise_info, format!("The group `{group}` of the exercise `{name}` isn't defined in a `[[groups]]` table in `info.toml`"))?;
+            }
+        }
+
+        for feature in &exercise_info.features {
+            if feature.is_empty()
+                || !feature.bytes().all(|c| {
+                    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, b'_' | b'-')
+                })
+            {
+                problems.add_for_exercise(exercise_info, format!("The feature `{feature}` of the exercise `{name}` is invalid. Feature names can only contain lowercase ASCII letters, digits, `_` and `-`"))?;
+                continue;
+            }
+
+            let declared_features = declared_features.get_or_insert_with(|| {
+                fs::read_to_string(CARGO_TOML_PATH)
+                    .map(|cargo_toml| cargo_toml::declared_features(&cargo_toml))
+                    .unwrap_or_default()
+            });
+            if !declared_features.contains(feature) {
+                problems.add_for_exercise(exercise_info, format!("The feature `{feature}` of the exercise `{name}` isn't declared in the `[features]` table of `{CARGO_TOML_PATH}`"))?;
+            }
+        }
+
+        if let Some(edition) = &exercise_info.edition {
+            if !EDITIONS.contains(&edition.as_str()) {
+                problems.add_for_exercise(exercise_info, format!("The edition `{edition}` of the exercise `{name}` is invalid. It must be one of {}", EDITIONS.join(", ")))?;
+            }
+        }
+
+        if let Some(difficulty) = exercise_info.difficulty {
+            if !(MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&difficulty) {
+                problems.add_for_exercise(exercise_info, format!("The difficulty {difficulty} of the exercise `{name}` is out of the range {MIN_DIFFICULTY}-{MAX_DIFFICULTY}"))?;
+            }
+        }
+
+        if exercise_info
+            .author
+            .as_ref()
+            .is_some_and(|author| author.trim_ascii().is_empty())
+        {
+            problems.add_for_exercise(
+                exercise_info,
+                format!("The exercise `{name}` has an empty author in `info.toml`"),
+            )?;
+        }
+        if let Some(source_url) = &exercise_info.source_url {
+            if let Some(problem) = url_problem(source_url) {
+                problems.add_for_exercise(exercise_info, format!("The source URL `{source_url}` of the exercise `{name}` is invalid. {problem}"))?;
+            }
+        }
+
+        if exercise_info.skip_fmt_check && !Path::new(&exercise_info.sol_path()).exists() {
+            warnings.add_for_exercise(&mut problems, exercise_info, format!("The exercise `{name}` has `skip_fmt_check = true` but its solution doesn't exist"))?;
+        }
+
+        if let Some(estimated_minutes) = exercise_info.estimated_minutes {
+            if !(MIN_ESTIMATED_MINUTES..=MAX_ESTIMATED_MINUTES).contains(&estimated_minutes) {
+                problems.add_for_exercise(exercise_info, format!("The estimated minutes {estimated_minutes} of the exercise `{name}` are out of the range {MIN_ESTIMATED_MINUTES}-{MAX_ESTIMATED_MINUTES}"))?;
+            }
+        }
+
+        for prerequisite in &exercise_info.prerequisites {
+            if names.contains_key(prerequisite.as_str()) {
+                continue;
+            }
+
+            let problem = if prerequisite == name {
+                format!("The exercise `{name}` (#{position}) can't be its own prerequisite")
+            } else if let Some(prerequisite_position) = info_file
+                .exercises
+                .iter()
+                .position(|exercise_info| exercise_info.name == *prerequisite)
+            {
+                format!("The prerequisite `{prerequisite}` (#{}) of the exercise `{name}` (#{position}) must come before it in `info.toml`", prerequisite_position + 1)
+            } else {
+                format!("The prerequisite `{prerequisite}` of the exercise `{name}` (#{position}) doesn't exist")
+            };
+            problems.add_for_exercise(exercise_info, problem)?;
+        }
+
+        // Every cycle contains a prerequisite coming later. Report it only once for the first exercise in it.
+        if let Some(cycle) = prerequisite_cycle(&info_file.exercises, position - 1) {
+            let cycle = cycle
+                .into_iter()
+                .map(|ind| format!("`{}` (#{})", info_file.exercises[ind].name, ind + 1))
+                .collect::<Vec<_>>()
+                .join(" → ");
+            problems.add_for_exercise(
+                exercise_info,
+                format!("The prerequisites of the exercises {cycle} form a cycle"),
+            )?;
+        }
+
+        if let Some(first_position) = names.get(name) {
+            problems.add_for_exercise(
+                exercise_info,
+                format!(
+                    "The exercise name `{name}` of the exercise #{position} in `info.toml` is already used by the exercise #{first_position}. Exercise names must all be unique"
+                ),
+            )?;
+        } else {
+            names.insert(name, position);
+        }
+
+        // The binary of the solution of an exercise is named `{name}_sol` in `Cargo.toml`.
+        if let Some(exercise_name) = name.strip_suffix("_sol") {
+            if let Some(exercise_position) = info_file
+                .exercises
+                .iter()
+                .position(|exercise_info| exercise_info.name == exercise_name)
+            {
+                problems.add_for_exercise(exercise_info, format!("The name `{name}` of the exercise #{position} in `info.toml` collides with the name of the solution binary of the exercise `{exercise_name}` (#{}) in `Cargo.toml`. Rename one of them", exercise_position + 1))?;
+            }
         }
 
         let path = exercise_info.path();
 
-        OpenOptions::new()
+        file_buf.clear();
+        if let Err(e) = OpenOptions::new()
             .read(true)
             .open(&path)
-            .with_context(|| format!("Failed to open the file {path}"))?
-            .read_to_string(&mut file_buf)
-            .with_context(|| format!("Failed to read the file {path}"))?;
+            .with_context(|| format!("Failed to open the file {path}"))
+            .and_then(|mut file| {
+                file.read_to_string(&mut file_buf)
+                    .with_context(|| format!("Failed to read the file {path}"))
+            })
+        {
+            let problem = if e
+                .downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == ErrorKind::NotFound)
+            {
+                missing_exercise_file_problem(info_file, exercise_info, &path)
+            } else {
+                format!("{e:#}")
+            };
+
+            // The checks below depend on the file's content.
+            problems.add_for_exercise(exercise_info, problem)?;
+            continue;
+        }
+
+        for warning in style_warnings(&path, &file_buf) {
+            warnings.add_for_exercise(&mut problems, exercise_info, warning)?;
+        }
+
+        for pattern in &exercise_info.forbidden_patterns {
+            if pattern.trim().is_empty() {
+                problems.add_for_exercise(
+                    exercise_info,
+                    format!("The exercise `{name}` has an empty forbidden pattern in `info.toml`"),
+                )?;
+            } else if let Some((line_number, line)) = find_forbidden_pattern(&file_buf, pattern) {
+                problems.add_for_exercise(exercise_info, format!("The file `{path}` contains the forbidden pattern `{pattern}` of the exercise `{name}` in line {line_number}:\n{}", line.trim()))?;
+            }
+        }
+
+        // Identifiers in the hints that might have been renamed in the exercise.
+        // Removed while reading the extra files if they are found in them.
+        let mut missing_hint_identifiers = (1..)
+            .zip(&exercise_info.hints)
+            .flat_map(|(level, hint)| {
+                hint_identifiers(hint).map(move |identifier| (level, identifier))
+            })
+            .filter(|(_, identifier)| {
+                !file_buf.contains(identifier)
+                    && !info_file
+                        .exercises
+                        .iter()
+                        .any(|exercise_info| exercise_info.name == *identifier)
+            })
+            .collect::<Vec<_>>();
 
         if !file_buf.contains("fn main()") {
-            bail!("The `main` function is missing in the file `{path}`.\nCreate at least an empty `main` function to avoid language server errors");
+            problems.add_for_exercise(exercise_info, format!("The `main` function is missing in the file `{path}`.\nCreate at least an empty `main` function to avoid language server errors"))?;
+        }
+
+        if !contains_todo_comment(&file_buf) {
+            if file_buf.contains("// TODO") {
+                problems.add_for_exercise(exercise_info, format!("The `// TODO` markers in the file `{path}` are only inside string or char literals.\nThe marker must be in an actual code comment like `// TODO: Fix the function` to guide the user"))?;
+            } else {
+                problems.add_for_exercise(exercise_info, format!("Didn't find any `// TODO` comment in the file `{path}`.\nYou need to have at least one such comment to guide the user."))?;
+            }
+        } else if args.pedantic && !contains_guiding_todo_comment(&file_buf) {
+            problems.add_for_exercise(exercise_info, format!("The file `{path}` only contains bare `// TODO` placeholders. `--pedantic` requires at least one guiding comment like `// TODO: Fix the function` that tells the user what to change"))?;
+        }
+
+        let mut unused_allowed_lints = exercise_info
+            .allowed_lints
+            .iter()
+            .map(String::as_str)
+            .collect::<HashSet<_>>();
+        for lint in allowed_lints(&file_buf) {
+            if !unused_allowed_lints.remove(lint)
+                && !exercise_info
+                    .allowed_lints
+                    .iter()
+                    .any(|allowed| allowed == lint)
+            {
+                warnings.add_for_exercise(&mut problems, exercise_info, format!("The file `{path}` allows the lint `{lint}`. If this is intentional, document why with a comment and add the lint to `allowed_lints` of the exercise in `info.toml`"))?;
+            }
+        }
+        for lint in &exercise_info.allowed_lints {
+            if unused_allowed_lints.contains(lint.as_str()) {
+                warnings.add_for_exercise(&mut problems, exercise_info, format!("The lint `{lint}` in `allowed_lints` isn't allowed with `#[allow(…)]` in the file `{path}`. Remove it from `allowed_lints`"))?;
+            }
         }
 
-        if !file_buf.contains("// TODO") {
-            bail!("Didn't find any `// TODO` comment in the file `{path}`.\nYou need to have at least one such comment to guide the user.");
+        let marked_should_fail = file_buf
+            .lines()
+            .any(|line| line.trim() == SHOULD_FAIL_MARKER);
+        if marked_should_fail && !exercise_info.should_fail {
+            problems.add_for_exercise(exercise_info, format!("The file `{path}` contains the comment `{SHOULD_FAIL_MARKER}` but the exercise `{name}` doesn't have `should_fail = true` in the `info.toml` file"))?;
         }
 
-        let contains_tests = file_buf.contains("#[test]\n");
-        if exercise_info.test {
+        let contains_tests = file_buf.lines().any(|line| line.trim() == "#[test]");
+        if exercise_info.should_fail {
+            if exercise_info.test != TestMode::Off {
+                problems.add_for_exercise(exercise_info, format!("The exercise `{name}` has `should_fail = true` which only checks that it fails to compile, so its tests would never run. Set `test = false` for this exercise in the `info.toml` file"))?;
+            }
+        } else if exercise_info.test == TestMode::Cargo {
             if !contains_tests {
-                bail!("The file `{path}` doesn't contain any tests. If you don't want to add tests to this exercise, set `test = false` for this exercise in the `info.toml` file");
+                problems.add_for_exercise(exercise_info, format!("The file `{path}` doesn't contain any tests annotated with `#[test]`. The exercise `{name}` has `test = true` (the default) which runs its tests, so it would pass without any and be reported as already solved. Add test functions or set `test = false` for this exercise in the `info.toml` file"))?;
+            } else if !file_buf.contains("#[cfg(test)]") {
+                warnings.add_for_exercise(&mut problems, exercise_info, format!("The tests in the file `{path}` aren't inside a module annotated with `#[cfg(test)]`. They are compiled in the normal build too which slows it down"))?;
             }
-        } else if contains_tests {
-            bail!("The file `{path}` contains tests annotated with `#[test]` but the exercise `{name}` has `test = false` in the `info.toml` file");
+        } else if exercise_info.test == TestMode::Off && contains_tests {
+            problems.add_for_exercise(exercise_info, format!("The file `{path}` contains tests annotated with `#[test]` but the exercise `{name}` has `test = false` in the `info.toml` file"))?;
         }
 
-        file_buf.clear();
+        for extra_file in &exercise_info.extra_files {
+            if !extra_file.ends_with(".rs") || !is_file_name(extra_file) {
+                problems.add_for_exercise(exercise_info, format!("The extra file `{extra_file}` of the exercise `{name}` must be a Rust file in the same directory as the exercise file"))?;
+            }
+        }
+
+        for extra_path in exercise_info.extra_file_paths(exercises_dir()) {
+            if extra_path == path {
+                problems.add_for_exercise(
+                    exercise_info,
+                    format!("The exercise file `{path}` can't also be an extra file"),
+                )?;
+                continue;
+            }
+
+            file_buf.clear();
+            if let Err(e) = OpenOptions::new()
+                .read(true)
+                .open(&extra_path)
+                .with_context(|| format!("Failed to open the extra file {extra_path}"))
+                .and_then(|mut file| {
+                    file.read_to_string(&mut file_buf)
+                        .with_context(|| format!("Failed to read the extra file {extra_path}"))
+                })
+            {
+                problems.add_for_exercise(exercise_info, format!("{e:#}"))?;
+                continue;
+            }
+
+            if file_buf.contains("fn main()") {
+                problems.add_for_exercise(exercise_info, format!("The extra file `{extra_path}` contains a `main` function. Only the exercise file `{path}` can have one"))?;
+            }
+
+            missing_hint_identifiers.retain(|(_, identifier)| !file_buf.contains(identifier));
+
+            paths.insert(PathBuf::from(extra_path));
+        }
+
+        for (level, identifier) in missing_hint_identifiers {
+            warnings.add_for_exercise(&mut problems, exercise_info, format!("The hint of level {level} mentions `{identifier}` which doesn't appear in the exercise. Update the hint if it was renamed"))?;
+        }
+
+        for additional_file in &exercise_info.additional_files {
+            if additional_file.ends_with(".rs") {
+                problems.add_for_exercise(exercise_info, format!("The additional file `{additional_file}` of the exercise `{name}` is a Rust file. Add it to `extra_files` instead"))?;
+            }
+            if !is_file_name(additional_file) {
+                problems.add_for_exercise(exercise_info, format!("The additional file `{additional_file}` of the exercise `{name}` must be in the same directory as the exercise file"))?;
+            }
+        }
+
+        for additional_path in exercise_info.additional_file_paths() {
+            if !Path::new(&additional_path).is_file() {
+                problems.add_for_exercise(
+                    exercise_info,
+                    format!("The additional file `{additional_path}` doesn't exist"),
+                )?;
+                continue;
+            }
+
+            paths.insert(PathBuf::from(additional_path));
+        }
+
+        if let Some(stdin_fixture) = &exercise_info.stdin_fixture {
+            if !is_file_name(stdin_fixture) {
+                problems.add_for_exercise(exercise_info, format!("The stdin fixture `{stdin_fixture}` of the exercise `{name}` must be in the same directory as the exercise file"))?;
+            }
+        }
+
+        if let Some(stdin_fixture_path) = exercise_info.stdin_fixture_path() {
+            if !Path::new(&stdin_fixture_path).is_file() {
+                problems.add_for_exercise(
+                    exercise_info,
+                    format!("The stdin fixture `{stdin_fixture_path}` doesn't exist"),
+                )?;
+            } else {
+                paths.insert(PathBuf::from(stdin_fixture_path));
+            }
+        }
 
         paths.insert(PathBuf::from(path));
     }
 
+    problems.finish("Found problems in the exercises of the `info.toml` file")?;
+
     Ok(paths)
 }
 
+// Check that the names of the groups are unique and that no group is empty.
+fn check_groups(info_file: &InfoFile, fail_fast: bool) -> Result<()> {
+    let mut problems = Problems::new(fail_fast);
+
+    let mut names = HashSet::with_capacity(info_file.groups.len());
+    for group_info in &info_file.groups {
+        let name = group_info.name.as_str();
+        if name.is_empty() {
+            problems.add(String::from("Found an empty group name in `info.toml`"))?;
+        }
+        if group_info.description.trim_ascii().is_empty() {
+            problems.add(format!(
+                "The group `{name}` has an empty description in `info.toml`"
+            ))?;
+        }
+        if !names.insert(name) {
+            problems.add(format!("The group name `{name}` is used by multiple `[[groups]]` tables in `info.toml`. Group names must all be unique"))?;
+        }
+        if !info_file
+            .exercises
+            .iter()
+            .any(|exercise_info| exercise_info.group.as_deref() == Some(name))
+        {
+            problems.add(format!(
+                "The group `{name}` in `info.toml` has no exercises"
+            ))?;
+        }
+    }
+
+    problems.finish("Found problems in the groups")
+}
+
+// Check that the directories referenced by the exercises exist and aren't empty
+// and that every directory in the exercises directory is referenced by an exercise.
+// Runs before opening any exercise file to report structural problems first.
+fn check_exercise_dirs(info_file: &InfoFile, fail_fast: bool) -> Result<()> {
+    let mut problems = Problems::new(fail_fast);
+
+    // The referenced directories with the names of the exercises referencing them.
+    let mut dirs = BTreeMap::<&str, Vec<&str>>::new();
+    for exercise_info in &info_file.exercises {
+        if let Some(dir) = exercise_info.dir.as_deref() {
+            // Empty dir names are reported by `check_info_file_exercises`.
+            if !dir.is_empty() {
+                dirs.entry(dir).or_default().push(&exercise_info.name);
+            }
+        }
+    }
+
+    for (dir, exercise_names) in &dirs {
+        let dir_path = Path::new(exercises_dir()).join(dir);
+        let exercise_names = exercise_names.join(", ");
+
+        let Ok(mut entries) = read_dir(&dir_path) else {
+            problems.add(format!("The directory `{}` doesn't exist but is referenced by the exercises: {exercise_names}", dir_path.display()))?;
+            continue;
+        };
+        if entries.next().is_none() {
+            problems.add(format!(
+                "The directory `{}` is empty but is referenced by the exercises: {exercise_names}",
+                dir_path.display(),
+            ))?;
+        }
+    }
+
+    let exercises_dir = exercises_dir();
+    for entry in read_dir(exercises_dir)
+        .with_context(|| format!("Failed to open the `{exercises_dir}` directory"))?
+    {
+        let entry =
+            entry.with_context(|| format!("Failed to read the `{exercises_dir}` directory"))?;
+        if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            continue;
+        }
+
+        let path = entry.path();
+        let is_referenced = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|dir| dirs.contains_key(dir));
+        if !is_referenced {
+            problems.add(format!("The directory `{}` isn't referenced by any exercise in `info.toml`. Check the `dir` of the exercises for typos or remove the directory", path.display()))?;
+        }
+    }
+
+    problems.finish("Found problems in the exercise directories")
+}
+
+// Explain a missing exercise file and mention unused Rust files next to it
+// because the file might have been renamed without updating the exercise name.
+fn missing_exercise_file_problem(
+    info_file: &InfoFile,
+    exercise_info: &ExerciseInfo,
+    path: &str,
+) -> String {
+    let name = &exercise_info.name;
+    let mut problem = format!("The file `{path}` doesn't exist. The exercise file name must be the exercise name `{name}` with the `.rs` extension");
+
+    let Some(dir) = Path::new(path).parent() else {
+        return problem;
+    };
+    let Ok(entries) = read_dir(dir) else {
+        return problem;
+    };
+
+    let used_paths = info_file
+        .exercises
+        .iter()
+        .flat_map(|exercise_info| {
+            exercise_info
+                .extra_file_paths(exercises_dir())
+                .chain([exercise_info.path()])
+        })
+        .map(PathBuf::from)
+        .collect::<HashSet<_>>();
+
+    let mut unused_file_names = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "rs") && !used_paths.contains(path)
+        })
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|file_stem| file_stem.to_string_lossy().into_owned())
+        })
+        .collect::<Vec<_>>();
+
+    if !unused_file_names.is_empty() {
+        unused_file_names.sort_unstable();
+        let unused_file_names = unused_file_names.join(", ");
+        problem = format!("{problem}. Found files that aren't used by any exercise: {unused_file_names}.\nIf one of them was renamed, update the exercise name `{name}` in `info.toml` to match the new file name");
+    }
+
+    problem
+}
+
+// Return the problem of the `README.md` file of an exercise directory.
+fn readme_problem(dir_path: &Path, readme_path: Option<&Path>) -> Option<String> {
+    let Some(readme_path) = readme_path else {
+        return Some(format!(
+            "- `{}` has no `README.md` file",
+            dir_path.display()
+        ));
+    };
+
+    let content = match fs::read_to_string(readme_path) {
+        Ok(v) => v,
+        Err(e) => return Some(format!("- Failed to read `{}`: {e}", readme_path.display())),
+    };
+    if content.trim().is_empty() {
+        Some(format!("- `{}` is empty", readme_path.display()))
+    } else if !content.contains("](") && !content.contains("https://") {
+        Some(format!(
+            "- `{}` doesn't contain any link",
+            readme_path.display(),
+        ))
+    } else {
+        None
+    }
+}
+
 // Check `dir` for unexpected files.
-// Only Rust files in `allowed_rust_files` and `README.md` files are allowed.
+// Only files in `allowed_files` and `README.md` files are allowed.
 // Only one level of directory nesting is allowed.
-fn check_unexpected_files(dir: &str, allowed_rust_files: &HashSet<PathBuf>) -> Result<()> {
+fn check_unexpected_files(
+    dir: &str,
+    allowed_files: &HashSet<PathBuf>,
+    require_readmes: bool,
+    fail_fast: bool,
+) -> Result<()> {
+    let mut problems = Problems::new(fail_fast);
+    // The problems of the `README.md` files of the visited directories are reported together.
+    let mut readme_problems = Vec::new();
     let unexpected_file = |path: &Path| {
-        anyhow!("Found the file `{}`. Only `README.md` and Rust files related to an exercise in `info.toml` are allowed in the `{dir}` directory", path.display())
+        if dir == solutions_dir() {
+            // Most likely left behind after removing its exercise from `info.toml`.
+            format!("Found the orphaned solution file `{}`. It doesn't belong to any exercise in `info.toml`. Remove it if its exercise was removed", path.display())
+        } else {
+            format!("Found the file `{}`. Only `README.md` and files related to an exercise in `info.toml` are allowed in the `{dir}` directory", path.display())
+        }
     };
 
     for entry in read_dir(dir).with_context(|| format!("Failed to open the `{dir}` directory"))? {
@@ -138,14 +985,15 @@ fn check_unexpected_files(dir: &str, allowed_rust_files: &HashSet<PathBuf>) -> R
                 continue;
             }
 
-            if !allowed_rust_files.contains(&path) {
-                return Err(unexpected_file(&path));
+            if !allowed_files.contains(&path) {
+                problems.add(unexpected_file(&path))?;
             }
 
             continue;
         }
 
         let dir_path = entry.path();
+        let mut readme_path = None;
         for entry in read_dir(&dir_path)
             .with_context(|| format!("Failed to open the directory {}", dir_path.display()))?
         {
@@ -154,221 +1002,1420 @@ fn check_unexpected_files(dir: &str, allowed_rust_files: &HashSet<PathBuf>) -> R
             let path = entry.path();
 
             if !entry.file_type().unwrap().is_file() {
-                bail!("Found `{}` but expected only files. Only one level of exercise nesting is allowed", path.display());
+                problems.add(format!("Found `{}` but expected only files. Only one level of exercise nesting is allowed", path.display()))?;
+                continue;
             }
 
             let file_name = path.file_name().unwrap();
             if file_name == "README.md" {
+                readme_path = Some(path);
                 continue;
             }
 
-            if !allowed_rust_files.contains(&path) {
-                return Err(unexpected_file(&path));
+            if !allowed_files.contains(&path) {
+                problems.add(unexpected_file(&path))?;
+            }
+        }
+
+        if require_readmes {
+            if let Some(problem) = readme_problem(&dir_path, readme_path.as_deref()) {
+                readme_problems.push(problem);
             }
         }
     }
 
-    Ok(())
-}
+    if !readme_problems.is_empty() {
+        problems.add(format!("Found exercise directories without a proper `README.md` file. Every directory needs one with at least one link, like to the related chapter of the book:\n{}", readme_problems.join("\n")))?;
+    }
 
-fn check_exercises_unsolved(
-    info_file: &'static InfoFile,
-    cmd_runner: &'static CmdRunner,
-) -> Result<()> {
+    problems.finish("Found unexpected files")
+}
+
+// Run `check` on all `items` with at most `jobs` threads (`0` for one thread per item).
+// The results are passed to `handle_result` on the current thread in the order of completion
+// together with the duration of the check.
+// An error returned by `handle_result` stops checking the remaining items.
+pub(super) fn run_in_parallel<T, R>(
+    items: &[T],
+    jobs: usize,
+    check: impl Fn(&T) -> R + Sync,
+    mut handle_result: impl FnMut(&T, R, Duration) -> Result<()>,
+    panic_err: impl Fn(&T) -> Error,
+) -> Result<()>
+where
+    T: Sync,
+    R: Send,
+{
+    let n_threads = if jobs == 0 {
+        items.len()
+    } else {
+        jobs.min(items.len())
+    };
+    let next_ind = AtomicUsize::new(0);
+    let mut received = vec![false; items.len()];
+
+    thread::scope(|s| {
+        let (result_sender, result_receiver) = mpsc::channel();
+
+        let handles = (0..n_threads)
+            .map(|_| {
+                let result_sender = result_sender.clone();
+                let next_ind = &next_ind;
+                let check = &check;
+                thread::Builder::new().spawn_scoped(s, move || loop {
+                    let ind = next_ind.fetch_add(1, Relaxed);
+                    let Some(item) = items.get(ind) else {
+                        // No more items.
+                        break;
+                    };
+
+                    let start = Instant::now();
+                    let result = check(item);
+                    if result_sender.send((ind, result, start.elapsed())).is_err() {
+                        break;
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to spawn a thread to run the checks")?;
+
+        // Drop this sender to detect when the last thread is done.
+        drop(result_sender);
+
+        let res = 'recv: {
+            while let Ok((ind, result, duration)) = result_receiver.recv() {
+                received[ind] = true;
+                if let Err(e) = handle_result(&items[ind], result, duration) {
+                    break 'recv Err(e);
+                }
+            }
+
+            Ok(())
+        };
+
+        // Stop the remaining threads after their current item.
+        drop(result_receiver);
+
+        let mut panicked = false;
+        for handle in handles {
+            panicked |= handle.join().is_err();
+        }
+
+        res?;
+
+        if panicked {
+            // A panicking thread never sends the result of its item.
+            if let Some(ind) = received.iter().position(|received| !received) {
+                return Err(panic_err(&items[ind]));
+            }
+        }
+
+        Ok(())
+    })
+}
+
+// Warn about an unsolved exercise without tests that fails without any output.
+// The user wouldn't know what is wrong.
+fn silent_failure_warning(exercise_info: &ExerciseInfo, output: &[u8]) -> Option<String> {
+    if exercise_info.test() {
+        return None;
+    }
+
+    let bin_output = failed_bin_output(output)?;
+    bin_output.trim_ascii().is_empty().then(|| {
+        format!(
+            "The exercise `{}` fails without any output when it's unsolved. Add an assertion or a compilation error to make the failure observable for the user. The output of running it:\n{}",
+            exercise_info.name,
+            String::from_utf8_lossy(output),
+        )
+    })
+}
+
+// Returns the warnings about exercises that fail silently.
+// With `strict`, they are failures instead.
+fn check_exercises_unsolved<'a>(
+    info_file: &'a InfoFile,
+    cmd_runner: &CmdRunner,
+    jobs: usize,
+    selection: &[Selection],
+    strict: bool,
+    mut timings: Option<&mut Vec<(&'a str, Duration)>>,
+    mut report: Option<&mut CategoryReport>,
+) -> Result<Warnings> {
     let mut stdout = io::stdout().lock();
-    stdout.write_all(b"Running all exercises to check that they aren't already solved...\n")?;
+    let print_progress = report.is_none();
+    if print_progress {
+        stdout.write_all(b"Running all exercises to check that they aren't already solved...\n")?;
+    }
 
-    let handles = info_file
-        .exercises
-        .iter()
-        .filter_map(|exercise_info| {
-            if exercise_info.skip_check_unsolved {
-                return None;
+    let mut exercise_infos = Vec::with_capacity(info_file.exercises.len());
+    for (exercise_info, selection) in info_file.exercises.iter().zip(selection) {
+        match selection {
+            Selection::Excluded => (),
+            Selection::Cached => {
+                if let Some(report) = report.as_deref_mut() {
+                    report.add_exercise(exercise_info, exercise_info.path(), Status::Cached, None);
+                }
             }
+            Selection::Checked => exercise_infos.push(exercise_info),
+        }
+    }
 
-            Some(
-                thread::Builder::new()
-                    .spawn(|| exercise_info.run_exercise(None, cmd_runner))
-                    .map(|handle| (exercise_info.name.as_str(), handle)),
+    let n_exercises = exercise_infos.len();
+    if print_progress {
+        write!(stdout, "Progress: 0/{n_exercises}")?;
+        stdout.flush()?;
+    }
+    let mut n_done = 0;
+    let mut warnings = Warnings::new(strict);
+
+    run_in_parallel(
+        &exercise_infos,
+        jobs,
+        |exercise_info| {
+            let mut output = Vec::new();
+            if let Some(stdin_fixture_path) = exercise_info.stdin_fixture_path() {
+                let input = fs::read(&stdin_fixture_path).with_context(|| {
+                    format!("Failed to read the stdin fixture {stdin_fixture_path}")
+                })?;
+                output.reserve(OUTPUT_CAPACITY);
+                return exercise_info
+                    .run_with_input(&input, &mut output, cmd_runner)
+                    .map(|success| (success, output));
+            }
+
+            // Only needed to show why an exercise that should be solved failed
+            // or to check that an exercise without tests doesn't fail silently.
+            let output_arg = if exercise_info.skip_check_unsolved || !exercise_info.test() {
+                output.reserve(OUTPUT_CAPACITY);
+                Some(&mut output)
+            } else {
+                None
+            };
+
+            exercise_info
+                .run_exercise(output_arg, cmd_runner)
+                .map(|success| (success, output))
+        },
+        |exercise_info, result, duration| {
+            if let Some(timings) = timings.as_deref_mut() {
+                timings.push((&exercise_info.name, duration));
+            }
+
+            // `skip_check_unsolved` inverts which outcome is an error.
+            let e = match result {
+                Ok((true, _)) if !exercise_info.skip_check_unsolved => anyhow!(
+                    "The exercise {} is already solved.\n{SKIP_CHECK_UNSOLVED_HINT}",
+                    exercise_info.name,
+                ),
+                Ok((false, output)) if exercise_info.skip_check_unsolved => anyhow!(
+                    "Running the exercise {} failed.\n{SOLVED_EXERCISE_FAILED_HINT}\n\n{}",
+                    exercise_info.name,
+                    String::from_utf8_lossy(&output),
+                ),
+                Ok((success, output)) => match (!success)
+                    .then(|| silent_failure_warning(exercise_info, &output))
+                    .flatten()
+                {
+                    Some(warning) if strict => anyhow!(warning),
+                    warning => {
+                        if let Some(warning) = warning {
+                            warnings.push_for_exercise(exercise_info, warning);
+                        }
+
+                        if let Some(report) = report.as_deref_mut() {
+                            report.add_exercise(
+                                exercise_info,
+                                exercise_info.path(),
+                                Status::Passed,
+                                None,
+                            );
+                        }
+
+                        n_done += 1;
+                        if print_progress {
+                            write!(stdout, "\rProgress: {n_done}/{n_exercises}")?;
+                            stdout.flush()?;
+                        }
+
+                        return Ok(());
+                    }
+                },
+                Err(e) => e.context(format!("Failed to run the exercise {}", exercise_info.name)),
+            };
+
+            let Some(report) = report.as_deref_mut() else {
+                return Err(Failure::Unsolved.attach(e));
+            };
+
+            report.add_exercise(
+                exercise_info,
+                exercise_info.path(),
+                Status::Failed,
+                Some(format!("{e:#}")),
+            );
+
+            Ok(())
+        },
+        |exercise_info| {
+            anyhow!(
+                "Panic while trying to run the exercise {}",
+                exercise_info.name
             )
-        })
-        .collect::<Result<Vec<_>, _>>()
-        .context("Failed to spawn a thread to check if an exercise is already solved")?;
+        },
+    )?;
+
+    if print_progress {
+        stdout.write_all(b"\n")?;
+    }
+
+    Ok(warnings)
+}
 
-    let n_handles = handles.len();
-    write!(stdout, "Progress: 0/{n_handles}")?;
-    stdout.flush()?;
-    let mut handle_num = 1;
+fn check_exercises<'a>(
+    info_file: &'a InfoFile,
+    cmd_runner: &CmdRunner,
+    jobs: usize,
+    args: &CheckArgs,
+    selection: &[Selection],
+    timings: Option<&mut Vec<(&'a str, Duration)>>,
+    report: Option<&mut CheckReport>,
+) -> Result<Warnings> {
+    let (mut info_file_report, unexpected_files_report, unsolved_report) = match report {
+        Some(report) => (
+            Some(&mut report.info_file),
+            Some(&mut report.unexpected_files),
+            Some(&mut report.unsolved),
+        ),
+        None => (None, None, None),
+    };
+
+    let format_version_err = match info_file.format_version.cmp(&CURRENT_FORMAT_VERSION) {
+        Ordering::Less => Some(anyhow!("`format_version` < {CURRENT_FORMAT_VERSION} (supported version)\nPlease migrate to the latest format version")),
+        Ordering::Greater => Some(anyhow!("`format_version` > {CURRENT_FORMAT_VERSION} (supported version)\nTry updating the Rustlings program")),
+        Ordering::Equal => None,
+    };
+    if args.static_checks() {
+        if let Some(e) = format_version_err {
+            handle_err(info_file_report.as_deref_mut(), Failure::Metadata, e)?;
+        }
 
-    for (exercise_name, handle) in handles {
-        let Ok(result) = handle.join() else {
-            bail!("Panic while trying to run the exercise {exercise_name}");
+        if let Err(e) = check_exercise_dirs(info_file, args.fail_fast) {
+            handle_err(info_file_report.as_deref_mut(), Failure::Metadata, e)?;
+        }
+
+        if let Err(e) = check_groups(info_file, args.fail_fast) {
+            handle_err(info_file_report.as_deref_mut(), Failure::Metadata, e)?;
+        }
+    }
+
+    thread::scope(|s| {
+        let handle = if args.run_exercises() {
+            let handle = thread::Builder::new()
+                .spawn_scoped(s, move || {
+                    check_exercises_unsolved(
+                        info_file,
+                        cmd_runner,
+                        jobs,
+                        selection,
+                        args.strict,
+                        timings,
+                        unsolved_report,
+                    )
+                })
+                .context("Failed to spawn a thread to check if any exercise is already solved")?;
+            Some(handle)
+        } else {
+            if let Some(unsolved_report) = unsolved_report {
+                unsolved_report.skip();
+            }
+            None
         };
 
-        match result {
-            Ok(true) => {
-                bail!("The exercise {exercise_name} is already solved.\n{SKIP_CHECK_UNSOLVED_HINT}",)
+        let mut warnings = Warnings::new(args.strict);
+        if !args.static_checks() {
+            for category in [info_file_report, unexpected_files_report]
+                .into_iter()
+                .flatten()
+            {
+                category.skip();
+            }
+        } else {
+            match check_info_file_exercises(info_file, selection, args, &mut warnings) {
+                Ok(info_file_paths) => {
+                    if is_partial(selection) {
+                        // Only the files of the selected exercises are known.
+                        if let Some(unexpected_files_report) = unexpected_files_report {
+                            unexpected_files_report.skip();
+                        }
+                    } else {
+                        // The official exercises require them by default.
+                        let require_readmes = info_file
+                            .require_readmes
+                            .unwrap_or_else(|| !Path::new("info.toml").exists());
+                        let mut res = check_unexpected_files(
+                            exercises_dir(),
+                            &info_file_paths,
+                            require_readmes,
+                            args.fail_fast,
+                        );
+                        // The solutions directory is otherwise checked with the solutions.
+                        if res.is_ok() && !args.run_solutions() {
+                            res = check_unexpected_files(
+                                solutions_dir(),
+                                &allowed_sol_paths(info_file),
+                                false,
+                                args.fail_fast,
+                            );
+                        }
+                        if let Err(e) = res {
+                            handle_err(unexpected_files_report, Failure::Metadata, e)?;
+                        }
+                    }
+                }
+                Err(e) => {
+                    handle_err(info_file_report, Failure::Metadata, e)?;
+                    // The allowed files are unknown.
+                    if let Some(unexpected_files_report) = unexpected_files_report {
+                        unexpected_files_report.skip();
+                    }
+                }
             }
-            Ok(false) => (),
-            Err(e) => return Err(e),
         }
 
-        write!(stdout, "\rProgress: {handle_num}/{n_handles}")?;
-        stdout.flush()?;
-        handle_num += 1;
+        if let Some(handle) = handle {
+            warnings.extend(handle.join().unwrap()?);
+        }
+
+        Ok(warnings)
+    })
+}
+
+// Build the command to check or fix the formatting of the solution files.
+// Uses the edition of the exercise or the course's `Cargo.toml` because the formatting can depend on it.
+fn rustfmt_cmd(
+    cargo_toml_path: &str,
+    exercise_edition: Option<&str>,
+    check: bool,
+    color: bool,
+) -> Command {
+    let edition = exercise_edition.map(String::from).or_else(|| {
+        fs::read_to_string(cargo_toml_path)
+            .ok()
+            .and_then(|cargo_toml| package_edition(&cargo_toml))
+    });
+
+    let mut fmt_cmd = Command::new("rustfmt");
+    if check {
+        fmt_cmd.arg("--check");
     }
-    stdout.write_all(b"\n")?;
+    fmt_cmd
+        .arg("--edition")
+        .arg(edition.as_deref().unwrap_or(DEFAULT_EDITION))
+        .arg("--color")
+        .arg(if color { "always" } else { "never" })
+        .stdin(Stdio::null());
 
-    Ok(())
+    fmt_cmd
 }
 
-fn check_exercises(info_file: &'static InfoFile, cmd_runner: &'static CmdRunner) -> Result<()> {
-    match info_file.format_version.cmp(&CURRENT_FORMAT_VERSION) {
-        Ordering::Less => bail!("`format_version` < {CURRENT_FORMAT_VERSION} (supported version)\nPlease migrate to the latest format version"),
-        Ordering::Greater => bail!("`format_version` > {CURRENT_FORMAT_VERSION} (supported version)\nTry updating the Rustlings program"),
-        Ordering::Equal => (),
+// Check the formatting of the solution after running `rustfmt` on it in place with `--fix`.
+fn check_solution_fmt(
+    sol_path: String,
+    edition: Option<&str>,
+    fix: bool,
+    color: bool,
+) -> SolutionCheck {
+    let mut formatted = false;
+    if fix {
+        let content = match fs::read(&sol_path) {
+            Ok(v) => v,
+            Err(e) => {
+                return SolutionCheck::Err(
+                    Error::from(e).context(format!("Failed to read the file {sol_path}")),
+                )
+            }
+        };
+
+        match rustfmt_cmd(CARGO_TOML_PATH, edition, false, false)
+            .arg(&sol_path)
+            .output()
+        {
+            Ok(fmt_output) if fmt_output.status.success() => (),
+            Ok(fmt_output) => {
+                return SolutionCheck::Err(anyhow!(
+                    "Failed to format the solution {sol_path} with `rustfmt`\n{}",
+                    String::from_utf8_lossy(&fmt_output.stderr),
+                ))
+            }
+            Err(e) => {
+                return SolutionCheck::Err(
+                    Error::from(e).context(format!("Failed to run `rustfmt` on {sol_path}")),
+                )
+            }
+        }
+
+        formatted = fs::read(&sol_path).is_ok_and(|formatted_content| formatted_content != content);
     }
 
-    let handle = thread::Builder::new()
-        .spawn(move || check_exercises_unsolved(info_file, cmd_runner))
-        .context("Failed to spawn a thread to check if any exercise is already solved")?;
+    // With `--fix`, the formatted solution is checked too because `rustfmt` might leave some code unformatted.
+    match rustfmt_cmd(CARGO_TOML_PATH, edition, true, color)
+        .arg(&sol_path)
+        .output()
+    {
+        Ok(fmt_output) if fmt_output.status.success() => SolutionCheck::Success {
+            sol_path,
+            formatted,
+        },
+        Ok(fmt_output) => SolutionCheck::FmtFailure {
+            sol_path,
+            formatted,
+            diff: fmt_output.stdout,
+        },
+        Err(e) => SolutionCheck::Err(
+            Error::from(e).context(format!("Failed to run `rustfmt` on {sol_path}")),
+        ),
+    }
+}
 
-    let info_file_paths = check_info_file_exercises(info_file)?;
-    check_unexpected_files("exercises", &info_file_paths)?;
+// Markers of unfinished code that are expected in exercises but not in solutions.
+const UNFINISHED_MARKERS: [&str; 3] = ["// TODO", "todo!()", "unimplemented!()"];
 
-    handle.join().unwrap()
+// A marker must be the whole line apart from the indentation and a trailing `;`
+// to not match mentions in strings or longer comments.
+fn is_marker_line(line: &str, marker: &str) -> bool {
+    let line = line.trim();
+    line == marker || line.strip_suffix(';') == Some(marker)
+}
+
+// Return the line number (starting at 1) and the marker of the first unfinished marker.
+// A `// TODO` comment that the exercise has too is allowed because it explains the task.
+fn find_unfinished_marker(content: &str, exercise_content: &str) -> Option<(usize, &'static str)> {
+    (1..).zip(content.lines()).find_map(|(line_num, line)| {
+        UNFINISHED_MARKERS
+            .into_iter()
+            .find(|marker| is_marker_line(line, marker))
+            .filter(|marker| {
+                *marker != "// TODO"
+                    || !exercise_content
+                        .lines()
+                        .any(|exercise_line| is_marker_line(exercise_line, marker))
+            })
+            .map(|marker| (line_num, marker))
+    })
 }
 
 enum SolutionCheck {
-    Success { sol_path: String },
+    Success {
+        sol_path: String,
+        /// Whether the solution was formatted because of `--fix`.
+        formatted: bool,
+    },
+    /// The solution ran successfully but isn't formatted.
+    FmtFailure {
+        sol_path: String,
+        formatted: bool,
+        /// The diff printed by `rustfmt --check`.
+        diff: Vec<u8>,
+    },
     MissingOptional,
-    RunFailure { output: Vec<u8> },
+    RunFailure {
+        output: Vec<u8>,
+    },
     Err(Error),
 }
 
-fn check_solutions(
-    require_solutions: bool,
-    info_file: &'static InfoFile,
-    cmd_runner: &'static CmdRunner,
-) -> Result<()> {
-    let mut stdout = io::stdout().lock();
-    stdout.write_all(b"Running all solutions...\n")?;
-
-    let handles = info_file
+// The paths of all solution files referenced by the exercises.
+fn allowed_sol_paths(info_file: &InfoFile) -> HashSet<PathBuf> {
+    info_file
         .exercises
         .iter()
-        .map(|exercise_info| {
-            thread::Builder::new().spawn(move || {
-                let sol_path = exercise_info.sol_path();
-                if !Path::new(&sol_path).exists() {
-                    if require_solutions {
-                        return SolutionCheck::Err(anyhow!(
-                            "The solution of the exercise {} is missing",
-                            exercise_info.name,
-                        ));
+        .flat_map(|exercise_info| {
+            iter::once(exercise_info.sol_path())
+                .chain(exercise_info.extra_file_paths(solutions_dir()))
+                .map(PathBuf::from)
+        })
+        .collect()
+}
+
+// Returns the paths of the solutions that were formatted because of `--fix`.
+fn check_solutions<'a>(
+    info_file: &'a InfoFile,
+    cmd_runner: &CmdRunner,
+    jobs: usize,
+    args: &CheckArgs,
+    selection: &[Selection],
+    mut timings: Option<&mut Vec<(&'a str, Duration)>>,
+    report: Option<&mut CheckReport>,
+) -> Result<Vec<String>> {
+    let (mut solutions_report, unexpected_files_report) = match report {
+        Some(report) => (
+            Some(&mut report.solutions),
+            Some(&mut report.unexpected_files),
+        ),
+        None => (None, None),
+    };
+
+    let mut stdout = io::stdout().lock();
+    let print_progress = solutions_report.is_none();
+    if print_progress {
+        stdout.write_all(b"Running all solutions...\n")?;
+    }
+
+    // All solution files referenced by an exercise are allowed, independent of the outcome of running them.
+    let sol_paths = allowed_sol_paths(info_file);
+    let mut formatted_paths = Vec::new();
+    let mut unformatted = Vec::new();
+    // The exercises with solutions that ran successfully.
+    let mut solved = Vec::new();
+
+    let mut exercise_infos = Vec::with_capacity(info_file.exercises.len());
+    for (exercise_info, selection) in info_file.exercises.iter().zip(selection) {
+        match selection {
+            Selection::Excluded => continue,
+            Selection::Cached => (),
+            Selection::Checked => {
+                exercise_infos.push(exercise_info);
+                continue;
+            }
+        }
+
+        let sol_path = exercise_info.sol_path();
+        if let Some(report) = solutions_report.as_deref_mut() {
+            report.add_exercise(exercise_info, sol_path.clone(), Status::Cached, None);
+        }
+    }
+
+    let n_exercises = exercise_infos.len();
+    if print_progress {
+        write!(stdout, "Progress: 0/{n_exercises}")?;
+        stdout.flush()?;
+    }
+    let mut n_done = 0;
+
+    run_in_parallel(
+        &exercise_infos,
+        jobs,
+        |exercise_info| {
+            let sol_path = exercise_info.sol_path();
+            if !Path::new(&sol_path).exists() {
+                if args.require_solutions {
+                    return SolutionCheck::Err(anyhow!(
+                        "The solution of the exercise {} is missing",
+                        exercise_info.name,
+                    ));
+                }
+
+                return SolutionCheck::MissingOptional;
+            }
+
+            let sol_content = match fs::read_to_string(&sol_path) {
+                Ok(v) => v,
+                Err(e) => {
+                    return SolutionCheck::Err(
+                        Error::from(e).context(format!("Failed to read the file {sol_path}")),
+                    )
+                }
+            };
+            // A missing exercise file is reported by the checks of `info.toml`.
+            let exercise_content = fs::read_to_string(exercise_info.path()).unwrap_or_default();
+            if let Some((line_num, marker)) =
+                find_unfinished_marker(&sol_content, &exercise_content)
+            {
+                return SolutionCheck::Err(anyhow!(
+                    "The solution `{sol_path}` of the exercise {} contains `{marker}` in line {line_num}. Solutions must be finished",
+                    exercise_info.name,
+                ));
+            }
+
+            // The solution reads the same input from stdin as the exercise.
+            let input = match exercise_info.stdin_fixture_path() {
+                Some(stdin_fixture_path) => match fs::read(&stdin_fixture_path) {
+                    Ok(input) => Some(input),
+                    Err(e) => {
+                        return SolutionCheck::Err(Error::from(e).context(format!(
+                            "Failed to read the stdin fixture {stdin_fixture_path}"
+                        )))
                     }
+                },
+                None => None,
+            };
+
+            let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+            match exercise_info.run_solution(
+                input.as_deref(),
+                Some(&mut output),
+                cmd_runner,
+                !args.no_clippy,
+            ) {
+                Ok(true) => (),
+                Ok(false) => return SolutionCheck::RunFailure { output },
+                Err(e) => return SolutionCheck::Err(e),
+            }
 
-                    return SolutionCheck::MissingOptional;
+            // The solution of a `should_fail` exercise doesn't compile with any version.
+            if let Some(msrv) = args.msrv.as_ref().filter(|_| !exercise_info.should_fail) {
+                output.clear();
+                let bin_name = format!("{}_sol", exercise_info.name);
+                let build_success = cmd_runner.build_with_toolchain(
+                    msrv,
+                    &bin_name,
+                    exercise_info.features(),
+                    Some(&mut output),
+                );
+                cmd_runner.clean_sandbox(&bin_name);
+                match build_success {
+                    Ok(true) => (),
+                    Ok(false) => {
+                        return SolutionCheck::Err(anyhow!(
+                            "The solution {sol_path} doesn't compile with the minimum supported Rust version {msrv}\n{}",
+                            String::from_utf8_lossy(&output),
+                        ))
+                    }
+                    Err(e) => return SolutionCheck::Err(e),
                 }
+            }
 
-                let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
-                match exercise_info.run_solution(Some(&mut output), cmd_runner) {
-                    Ok(true) => SolutionCheck::Success { sol_path },
-                    Ok(false) => SolutionCheck::RunFailure { output },
-                    Err(e) => SolutionCheck::Err(e),
+            if exercise_info.skip_fmt_check {
+                SolutionCheck::Success {
+                    sol_path,
+                    formatted: false,
                 }
-            })
-        })
-        .collect::<Result<Vec<_>, _>>()
-        .context("Failed to spawn a thread to check a solution")?;
+            } else {
+                // Formatting in the same thread overlaps it with running other solutions.
+                check_solution_fmt(
+                    sol_path,
+                    exercise_info.edition.as_deref(),
+                    args.fix,
+                    print_progress,
+                )
+            }
+        },
+        |exercise_info, check_result, duration| {
+            if let Some(timings) = timings.as_deref_mut() {
+                timings.push((&exercise_info.name, duration));
+            }
 
-    let mut sol_paths = HashSet::with_capacity(info_file.exercises.len());
-    let mut fmt_cmd = Command::new("rustfmt");
-    fmt_cmd
-        .arg("--check")
-        .arg("--edition")
-        .arg("2021")
-        .arg("--color")
-        .arg("always")
-        .stdin(Stdio::null());
+            match check_result {
+                SolutionCheck::Success {
+                    sol_path,
+                    formatted,
+                } => {
+                    if let Some(report) = solutions_report.as_deref_mut() {
+                        report.add_exercise(exercise_info, sol_path.clone(), Status::Passed, None);
+                    }
+                    if formatted {
+                        formatted_paths.push(sol_path);
+                    }
+                    solved.push(*exercise_info);
+                }
+                SolutionCheck::FmtFailure {
+                    sol_path,
+                    formatted,
+                    diff,
+                } => {
+                    if let Some(report) = solutions_report.as_deref_mut() {
+                        report.add_exercise(exercise_info, sol_path.clone(), Status::Passed, None);
+                    }
+                    if formatted {
+                        formatted_paths.push(sol_path.clone());
+                    }
+                    unformatted.push((sol_path, diff));
+                    solved.push(*exercise_info);
+                }
+                SolutionCheck::MissingOptional => {
+                    if let Some(report) = solutions_report.as_deref_mut() {
+                        report.add_exercise(
+                            exercise_info,
+                            exercise_info.sol_path(),
+                            Status::Missing,
+                            None,
+                        );
+                    }
+                }
+                SolutionCheck::RunFailure { output } => {
+                    let Some(report) = solutions_report.as_deref_mut() else {
+                        stdout.write_all(b"\n\n")?;
+                        stdout.write_all(&output)?;
+                        return Err(Failure::Solution.attach(anyhow!(
+                            "Running the solution of the exercise {} failed with the error above",
+                            exercise_info.name,
+                        )));
+                    };
+
+                    report.add_exercise(
+                        exercise_info,
+                        exercise_info.sol_path(),
+                        Status::Failed,
+                        Some(String::from_utf8_lossy(&output).into_owned()),
+                    );
+                }
+                SolutionCheck::Err(e) => {
+                    let Some(report) = solutions_report.as_deref_mut() else {
+                        return Err(Failure::Solution.attach(e));
+                    };
+
+                    report.add_exercise(
+                        exercise_info,
+                        exercise_info.sol_path(),
+                        Status::Failed,
+                        Some(format!("{e:#}")),
+                    );
+                }
+            }
 
-    let n_handles = handles.len();
-    write!(stdout, "Progress: 0/{n_handles}")?;
-    stdout.flush()?;
-    let mut handle_num = 1;
+            n_done += 1;
+            if print_progress {
+                write!(stdout, "\rProgress: {n_done}/{n_exercises}")?;
+                stdout.flush()?;
+            }
 
-    for (exercise_info, handle) in info_file.exercises.iter().zip(handles) {
-        let Ok(check_result) = handle.join() else {
-            bail!(
+            Ok(())
+        },
+        |exercise_info| {
+            anyhow!(
                 "Panic while trying to run the solution of the exercise {}",
                 exercise_info.name,
-            );
-        };
+            )
+        },
+    )?;
 
-        match check_result {
-            SolutionCheck::Success { sol_path } => {
-                fmt_cmd.arg(&sol_path);
-                sol_paths.insert(PathBuf::from(sol_path));
-            }
-            SolutionCheck::MissingOptional => (),
-            SolutionCheck::RunFailure { output } => {
-                stdout.write_all(b"\n\n")?;
-                stdout.write_all(&output)?;
-                bail!(
-                    "Running the solution of the exercise {} failed with the error above",
-                    exercise_info.name,
-                );
-            }
-            SolutionCheck::Err(e) => return Err(e),
+    if print_progress {
+        stdout.write_all(b"\n")?;
+    }
+
+    // A copied exercise file shows the answer to students. Checked after running the solutions
+    // to not read the files twice in parallel. Intentionally solved exercises can be identical.
+    solved.sort_unstable_by_key(|exercise_info| exercise_info.sol_path());
+    for exercise_info in solved {
+        if exercise_info.skip_check_unsolved {
+            continue;
         }
 
-        write!(stdout, "\rProgress: {handle_num}/{n_handles}")?;
-        stdout.flush()?;
-        handle_num += 1;
+        let path = exercise_info.path();
+        if fs::read(&path).ok() == fs::read(exercise_info.sol_path()).ok() {
+            handle_err(
+                solutions_report.as_deref_mut(),
+                Failure::Solution,
+                anyhow!(
+                    "The solution of the exercise `{}` is identical to the exercise file {path}. The exercise appears already solved",
+                    exercise_info.name,
+                ),
+            )?;
+        }
     }
-    stdout.write_all(b"\n")?;
 
+    let fail_fast = args.fail_fast;
+    let partial = is_partial(selection);
     let handle = thread::Builder::new()
-        .spawn(move || check_unexpected_files("solutions", &sol_paths))
+        .spawn(move || {
+            if partial {
+                return Ok(());
+            }
+
+            check_unexpected_files(solutions_dir(), &sol_paths, false, fail_fast)
+        })
         .context(
             "Failed to spawn a thread to check for unexpected files in the solutions directory",
         )?;
 
-    if !fmt_cmd
-        .status()
-        .context("Failed to run `rustfmt` on all solution files")?
-        .success()
+    // The results are received in the order of completion.
+    formatted_paths.sort_unstable();
+    unformatted.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+    if !unformatted.is_empty() {
+        let unformatted_paths = unformatted
+            .iter()
+            .map(|(sol_path, _)| sol_path.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let fmt_err_msg = if args.fix {
+            format!("The solutions {unformatted_paths} are still not formatted after running `rustfmt` on them. Format them manually")
+        } else {
+            format!("The solutions {unformatted_paths} aren't formatted. Run `rustfmt` on them")
+        };
+        let diffs = unformatted
+            .into_iter()
+            .flat_map(|(_, diff)| diff)
+            .collect::<Vec<_>>();
+
+        if print_progress {
+            stdout.write_all(&diffs)?;
+            return Err(Failure::Fmt.attach(anyhow!("{fmt_err_msg}")));
+        }
+
+        handle_err(
+            solutions_report,
+            Failure::Fmt,
+            anyhow!("{fmt_err_msg}\n{}", String::from_utf8_lossy(&diffs)),
+        )?;
+    }
+
+    if let Err(e) = handle.join().unwrap() {
+        handle_err(unexpected_files_report, Failure::Metadata, e)?;
+    }
+
+    Ok(formatted_paths)
+}
+
+// Whether only some exercises are checked because of `--only`.
+// Checks of the whole course like the one of `Cargo.toml` are skipped in this case.
+fn is_partial(selection: &[Selection]) -> bool {
+    selection.contains(&Selection::Excluded)
+}
+
+impl CheckArgs {
+    // The checks of `Cargo.toml`, `info.toml` and unexpected files.
+    fn static_checks(&self) -> bool {
+        !self.solutions_only && !self.exercises_only
+    }
+
+    fn run_exercises(&self) -> bool {
+        !self.no_run && !self.solutions_only
+    }
+
+    fn run_solutions(&self) -> bool {
+        !self.no_run && !self.exercises_only
+    }
+
+    // Describe the checks that ran for the final message if some were skipped.
+    fn skipped_checks_note(&self) -> Option<&'static str> {
+        if self.no_run {
+            Some("Only the static checks of `Cargo.toml`, `info.toml` and unexpected files ran (`--no-run`)")
+        } else if self.solutions_only {
+            Some("Only the solutions were run and checked (`--solutions-only`)")
+        } else if self.exercises_only {
+            Some("Only the exercises were run to check that they aren't already solved (`--exercises-only`)")
+        } else {
+            None
+        }
+    }
+}
+
+// Check that the toolchain is installed by rustup to fail early instead of for every solution.
+fn check_toolchain_installed(toolchain: &str) -> Result<()> {
+    if toolchain.is_empty()
+        || toolchain.starts_with('-')
+        || !toolchain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
     {
-        bail!("Some solutions aren't formatted. Run `rustfmt` on them");
+        bail!("The minimum supported Rust version `{toolchain}` is invalid. Use a version like `1.75`");
     }
 
-    handle.join().unwrap()
+    let output = Command::new("rustup")
+        .arg("toolchain")
+        .arg("list")
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .context("Failed to run `rustup toolchain list`. rustup is required to check the solutions with the minimum supported Rust version")?;
+    if !output.status.success() {
+        bail!("The command `rustup toolchain list` failed. See the possible error message above");
+    }
+
+    // The lines have the form `1.75-x86_64-unknown-linux-gnu (default)`.
+    let installed = String::from_utf8_lossy(&output.stdout).lines().any(|line| {
+        let name = line.split_whitespace().next().unwrap_or_default();
+        name.strip_prefix(toolchain)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+    });
+    if !installed {
+        bail!("The toolchain `{toolchain}` of the minimum supported Rust version isn't installed. Install it by running the following command:
+rustup toolchain install {toolchain} --profile minimal");
+    }
+
+    Ok(())
+}
+
+// Print the counts of the checked exercises and solutions after a successful run.
+fn print_summary(
+    info_file: &InfoFile,
+    selection: &[Selection],
+    args: &CheckArgs,
+    n_warnings: usize,
+) -> io::Result<()> {
+    let n_checked = selection
+        .iter()
+        .filter(|selection| **selection == Selection::Checked)
+        .count();
+    let n_cached = selection
+        .iter()
+        .filter(|selection| **selection == Selection::Cached)
+        .count();
+
+    let mut rows = vec![(
+        "Exercises checked",
+        format!("{} ({n_cached} cached)", n_checked + n_cached),
+    )];
+    if args.run_solutions() {
+        // Every checked solution ran successfully or is missing without `--require-solutions`.
+        let n_missing = info_file
+            .exercises
+            .iter()
+            .zip(selection)
+            .filter(|(exercise_info, selection)| {
+                **selection == Selection::Checked && !Path::new(&exercise_info.sol_path()).exists()
+            })
+            .count();
+        rows.push(("Solutions run", (n_checked - n_missing).to_string()));
+        rows.push(("Solutions missing (optional)", n_missing.to_string()));
+    }
+    rows.push(("Warnings", n_warnings.to_string()));
+
+    let key_col_width = rows.iter().fold(0, |width, (key, _)| width.max(key.len()));
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(b"Summary:\n")?;
+    for (key, value) in rows {
+        writeln!(stdout, "  {key:key_col_width$}  {value}")?;
+    }
+    stdout.write_all(b"\n")
+}
+
+pub fn check(mut args: CheckArgs) -> Result<ExitCode> {
+    // Resolved now because the printed path should still be valid if the directory changes.
+    if let Some(report_path) = &mut args.emit_report {
+        *report_path = path::absolute(&report_path).with_context(|| {
+            format!(
+                "Failed to resolve the path of the report {}",
+                report_path.display()
+            )
+        })?;
+    }
+    let report_path = args.emit_report.clone();
+
+    let mut report = None;
+    let res = run_checks(args, &mut report);
+    if let Some(report_path) = report_path {
+        // A check that bailed before the report was created or an error that stopped checking
+        // isn't contained in the report.
+        let report = report.unwrap_or_default();
+        let bail_error = res.as_ref().err().filter(|_| report.failure().is_none());
+        report.emit(&report_path, bail_error)?;
+    }
+
+    match res {
+        Ok(()) => Ok(ExitCode::SUCCESS),
+        Err(e) => match e.downcast::<CheckError>() {
+            // Printed like the errors returned from `main`.
+            Ok(CheckError { failure, error }) => {
+                eprintln!("Error: {error:?}");
+                Ok(ExitCode::from(failure as u8))
+            }
+            Err(e) => Err(e),
+        },
+    }
 }
 
-pub fn check(require_solutions: bool) -> Result<()> {
-    let info_file = InfoFile::parse()?;
+// With `--json` or `--emit-report`, the results are collected in `report` instead of stopping
+// at the first error.
+fn run_checks(mut args: CheckArgs, report: &mut Option<CheckReport>) -> Result<()> {
+    let start = Instant::now();
+    let info_file = InfoFile::parse().map_err(|e| Failure::Metadata.attach(e))?;
+
+    if args.msrv.is_none() {
+        args.msrv.clone_from(&info_file.msrv);
+    }
+    if let Some(msrv) = &args.msrv {
+        if args.run_solutions() {
+            check_toolchain_installed(msrv)?;
+        }
+    }
 
     if info_file.exercises.len() > MAX_N_EXERCISES {
-        bail!("The maximum number of exercises is {MAX_N_EXERCISES}");
+        return Err(Failure::Metadata.attach(anyhow!(
+            "The maximum number of exercises is {MAX_N_EXERCISES}"
+        )));
     }
 
-    if cfg!(debug_assertions) {
-        // A hack to make `cargo run -- dev check` work when developing Rustlings.
-        check_cargo_toml(&info_file.exercises, "dev/Cargo.toml", b"../")?;
+    let matching = matching_exercises(&info_file, &args.only)?;
+    let n_matching = matching.iter().filter(|matching| **matching).count();
+    let partial = n_matching < info_file.exercises.len();
+
+    *report = (args.json || args.emit_report.is_some()).then(|| CheckReport {
+        partial,
+        ..Default::default()
+    });
+
+    if partial && report.is_none() {
+        println!(
+            "Partial check of {n_matching}/{} exercises matching `--only`. Skipping the checks of `Cargo.toml` and unexpected files\n",
+            info_file.exercises.len(),
+        );
+    }
+
+    // Files modified by `--fix`.
+    let mut fixed_paths = Vec::new();
+
+    if partial || !args.static_checks() {
+        if let Some(report) = report.as_mut() {
+            report.cargo_toml.skip();
+            report.editor_files.skip();
+        }
     } else {
-        check_cargo_toml(&info_file.exercises, "Cargo.toml", b"")?;
+        let exercise_path_prefix: &[u8] = if cfg!(debug_assertions) { b"../" } else { b"" };
+        match check_cargo_toml(
+            &info_file.exercises,
+            CARGO_TOML_PATH,
+            exercise_path_prefix,
+            args.fix,
+        ) {
+            Ok(true) => {
+                if report.is_none() {
+                    println!("Fixed: Updated the outdated file `{CARGO_TOML_PATH}`\n");
+                }
+                fixed_paths.push(CARGO_TOML_PATH.to_string());
+            }
+            Ok(false) => (),
+            Err(e) => handle_err(
+                report.as_mut().map(|report| &mut report.cargo_toml),
+                Failure::Metadata,
+                e,
+            )?,
+        }
+
+        match check_rust_project_json(&info_file.exercises, args.fix) {
+            Ok(true) => {
+                if report.is_none() {
+                    println!("Fixed: Updated the outdated file `{RUST_PROJECT_JSON_PATH}`\n");
+                }
+                fixed_paths.push(RUST_PROJECT_JSON_PATH.to_string());
+            }
+            Ok(false) => (),
+            Err(e) => handle_err(
+                report.as_mut().map(|report| &mut report.editor_files),
+                Failure::Metadata,
+                e,
+            )?,
+        }
     }
 
-    // Leaking is fine since they are used until the end of the program.
-    let cmd_runner = Box::leak(Box::new(CmdRunner::build()?));
-    let info_file = Box::leak(Box::new(info_file));
+    let cmd_runner = CmdRunner::build(SandboxArgs::default())?;
 
-    check_exercises(info_file, cmd_runner)?;
-    check_solutions(require_solutions, info_file, cmd_runner)?;
+    let jobs = args.jobs.unwrap_or_else(|| {
+        thread::available_parallelism().map_or(DEFAULT_JOBS, |count| count.get())
+    });
 
-    println!("Everything looks fine!");
+    let global_hash = CheckCache::global_hash(
+        &info_file,
+        CARGO_TOML_PATH,
+        &[args.require_solutions, args.no_clippy],
+        args.msrv.as_deref(),
+    );
+    let exercise_hashes = info_file
+        .exercises
+        .iter()
+        .map(CheckCache::exercise_hash)
+        .collect::<Vec<_>>();
+    let prev_cache = CheckCache::read(global_hash);
+    let unchanged = match &prev_cache {
+        Some(cache) if !args.force => cache.unchanged(&info_file, &exercise_hashes),
+        _ => vec![false; info_file.exercises.len()],
+    };
+    let selection = matching
+        .iter()
+        .zip(&unchanged)
+        .map(|(matching, unchanged)| match (matching, unchanged) {
+            (false, _) => Selection::Excluded,
+            (true, true) => Selection::Cached,
+            (true, false) => Selection::Checked,
+        })
+        .collect::<Vec<_>>();
+
+    let mut timings = args.timings.then(Timings::default);
+    let warnings = check_exercises(
+        &info_file,
+        &cmd_runner,
+        jobs,
+        &args,
+        &selection,
+        timings.as_mut().map(|timings| &mut timings.exercises),
+        report.as_mut(),
+    )?;
+    let formatted_paths = if args.run_solutions() {
+        check_solutions(
+            &info_file,
+            &cmd_runner,
+            jobs,
+            &args,
+            &selection,
+            timings.as_mut().map(|timings| &mut timings.solutions),
+            report.as_mut(),
+        )?
+    } else {
+        if let Some(report) = report.as_mut() {
+            report.solutions.skip();
+        }
+        Vec::new()
+    };
+    if report.is_none() && !formatted_paths.is_empty() {
+        println!(
+            "Fixed: Formatted the solutions {}\n",
+            formatted_paths.join(", ")
+        );
+    }
+    fixed_paths.extend(formatted_paths);
+
+    let cache = CheckCache::new(
+        global_hash,
+        &info_file,
+        &exercise_hashes,
+        &selection,
+        prev_cache,
+    );
+
+    // The cache is only valid if all checks ran.
+    let skipped_checks_note = args.skipped_checks_note();
+
+    let Some(report) = report.as_mut() else {
+        if skipped_checks_note.is_none() {
+            cache.write()?;
+        }
+        if let Some(timings) = &mut timings {
+            timings.print(start.elapsed())?;
+        }
+        if !warnings.is_empty() {
+            warnings.print()?;
+        }
+        print_summary(&info_file, &selection, &args, warnings.len())?;
+        match skipped_checks_note {
+            Some(note) => println!("Everything looks fine! {note}"),
+            None => println!("Everything looks fine!"),
+        }
+        return Ok(());
+    };
+
+    if !args.json && !warnings.is_empty() {
+        warnings.print()?;
+    }
+    report.warnings = warnings.into_vec();
+    report.fixed_files = fixed_paths;
+    report.finish(&info_file.exercises, timings.as_ref());
+    if args.json {
+        report.print()?;
+    }
+    if let Some(failure) = report.failure() {
+        let e = match &args.emit_report {
+            Some(report_path) if !args.json => {
+                anyhow!(
+                    "Some checks failed. See the report {}",
+                    report_path.display()
+                )
+            }
+            _ => anyhow!("Some checks failed. See the JSON report above"),
+        };
+        return Err(failure.attach(e));
+    }
+
+    if skipped_checks_note.is_none() {
+        cache.write()?;
+    }
+
+    if let (Some(report_path), false) = (&args.emit_report, args.json) {
+        match skipped_checks_note {
+            Some(note) => println!(
+                "Everything looks fine! {note}\nSee the report {}",
+                report_path.display(),
+            ),
+            None => println!(
+                "Everything looks fine! See the report {}",
+                report_path.display(),
+            ),
+        }
+    }
 
     Ok(())
 }
 
-const SKIP_CHECK_UNSOLVED_HINT: &str = "If this is an introduction exercise that is intended to be already solved, add `skip_check_unsolved = true` to the exercise's metadata in the `info.toml` file";
+// The comment in exercise files that must fail to compile.
+const SHOULD_FAIL_MARKER: &str = "// #[should_fail]";
+const SKIP_CHECK_UNSOLVED_HINT: &str = "If this is an introduction exercise that is intended to be already solved, add `skip_check_unsolved = true` to the exercise's metadata in the `info.toml` file. Such exercises are still run to check that they succeed";
+const SOLVED_EXERCISE_FAILED_HINT: &str = "The exercise has `skip_check_unsolved = true` in the `info.toml` file. It is intended to be already solved and must run successfully";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_chars() {
+        assert_eq!(forbidden_char("structs1"), None);
+        assert_eq!(forbidden_char("error_handling_1"), None);
+        assert_eq!(forbidden_char("error-handling-1"), None);
+        assert_eq!(forbidden_char("error handling"), Some(' '));
+        assert_eq!(forbidden_char("errors/1"), Some('/'));
+        assert_eq!(forbidden_char("errors1.rs"), Some('.'));
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(url_problem("https://github.com/rust-lang/rustlings"), None);
+        assert_eq!(url_problem("http://example.com?q=1"), None);
+        assert!(url_problem("github.com/rust-lang/rustlings").is_some());
+        assert!(url_problem("https:///path").is_some());
+        assert!(url_problem("https://example.com/a b").is_some());
+    }
+
+    #[test]
+    fn file_names() {
+        assert!(is_file_name("guess.txt"));
+        assert!(is_file_name("..txt"));
+        assert!(!is_file_name(""));
+        assert!(!is_file_name(".."));
+        assert!(!is_file_name("../guess.txt"));
+        assert!(!is_file_name("fixtures/guess.txt"));
+        assert!(!is_file_name("fixtures\\guess.txt"));
+    }
+
+    #[test]
+    fn unfinished_markers() {
+        assert_eq!(
+            find_unfinished_marker("fn f() {\n    todo!()\n}\n", ""),
+            Some((2, "todo!()"))
+        );
+        assert_eq!(
+            find_unfinished_marker("    unimplemented!();\n", ""),
+            Some((1, "unimplemented!()"))
+        );
+        assert_eq!(
+            find_unfinished_marker("// TODO\n", ""),
+            Some((1, "// TODO"))
+        );
+        // Copied from the exercise.
+        assert_eq!(find_unfinished_marker("    // TODO\n", "// TODO\n"), None);
+        assert_eq!(
+            find_unfinished_marker("// TODO: Explain the task.\n", ""),
+            None
+        );
+        assert_eq!(find_unfinished_marker("let s = \"todo!()\";\n", ""), None);
+    }
+
+    #[test]
+    fn todo_comments() {
+        assert!(contains_todo_comment("fn main() {\n    // TODO: Fix\n}\n"));
+        assert!(contains_todo_comment(
+            "/* Fix this.\n// TODO */ fn main() {}"
+        ));
+        assert!(contains_todo_comment(
+            "fn f<'a>(s: &'a str) -> char { '\"' } // TODO"
+        ));
+        assert!(contains_todo_comment("let s = \"\\\"\"; // TODO"));
+        assert!(!contains_todo_comment("let s = \"// TODO\";"));
+        assert!(!contains_todo_comment("let s = \"a\\\" // TODO\";"));
+        assert!(!contains_todo_comment(
+            "let c = '/'; let s = \"/\" ; // todo"
+        ));
+        assert!(!contains_todo_comment("let s = \"unterminated // TODO"));
+        assert!(!contains_todo_comment(
+            "let c = '\\''; let s = \"// TODO\";"
+        ));
+        assert!(!contains_todo_comment("let c = 'ä'; let s = \"// TODO\";"));
+
+        assert!(contains_guiding_todo_comment(
+            "x(); // TODO: Fix the call\n"
+        ));
+        assert!(contains_guiding_todo_comment(
+            "/* TODO */\n/* // TODO: Fix */"
+        ));
+        assert!(!contains_guiding_todo_comment("// TODO\nx(); // TODO:\n"));
+        assert!(!contains_guiding_todo_comment(
+            "/* // TODO: */\nlet s = \"// TODO: Fix\";"
+        ));
+    }
+
+    #[test]
+    fn short_hints() {
+        assert_eq!(short_hint("  Look at the docs.\n"), Some((17, 1)));
+        // Three sentences are enough even if short.
+        assert_eq!(short_hint("Wait... What? No!"), None);
+        assert_eq!(short_hint("Use `1.5`. Or `x.y()`."), Some((22, 2)));
+        assert_eq!(short_hint(&"a".repeat(MIN_STRICT_HINT_LEN)), None);
+    }
+
+    #[test]
+    fn placeholder_hints() {
+        for hint in [
+            "todo",
+            " TODO\n",
+            "TBD.",
+            "fixme!",
+            "TODO: write a hint",
+            "todo:",
+        ] {
+            assert!(is_placeholder_hint(hint), "{hint}");
+        }
+        for hint in ["", "Use `todo!()`", "Todos are tracked in a `Vec`", "tbd1"] {
+            assert!(!is_placeholder_hint(hint), "{hint}");
+        }
+    }
+
+    #[test]
+    fn prerequisite_cycles() {
+        let info_file = toml_edit::de::from_str::<InfoFile>(
+            r#"format_version = 1
+exercises = [
+  { name = "a", hint = "", requires = ["c"] },
+  { name = "b", hint = "", prerequisites = ["a", "b"] },
+  { name = "c", hint = "", prerequisites = ["b", "d"] },
+  { name = "d", hint = "", prerequisites = ["a"] },
+]"#,
+        )
+        .unwrap();
+        let exercise_infos = &info_file.exercises;
+
+        assert_eq!(
+            prerequisite_cycle(exercise_infos, 0),
+            Some(vec![0, 2, 1, 0])
+        );
+        // Only found from the first exercise of the cycle.
+        assert_eq!(prerequisite_cycle(exercise_infos, 1), None);
+        assert_eq!(prerequisite_cycle(exercise_infos, 2), None);
+        assert_eq!(prerequisite_cycle(exercise_infos, 3), None);
+    }
+
+    #[test]
+    fn forbidden_patterns() {
+        let content = "fn main() {\n    let x = y.unwrap_or(1);\n    let z = x.clone();\n}\n";
+
+        assert_eq!(find_forbidden_pattern(content, "unwrap"), None);
+        assert_eq!(find_forbidden_pattern(content, "unwrap()"), None);
+        assert_eq!(
+            find_forbidden_pattern(content, "unwrap_or"),
+            Some((2, "    let x = y.unwrap_or(1);")),
+        );
+        assert_eq!(
+            find_forbidden_pattern(content, ".clone()"),
+            Some((3, "    let z = x.clone();")),
+        );
+        assert_eq!(find_forbidden_pattern(content, "lone"), None);
+        assert_eq!(
+            find_forbidden_pattern(content, "let"),
+            Some((2, "    let x = y.unwrap_or(1);"))
+        );
+        assert_eq!(find_forbidden_pattern("deletes", "let"), None);
+    }
+
+    #[test]
+    fn rustfmt_edition() {
+        let course_dir = tempfile::TempDir::new().unwrap();
+        let cargo_toml_path = course_dir.path().join("Cargo.toml");
+        let cargo_toml_path = cargo_toml_path.to_str().unwrap();
+
+        let edition_arg = |cargo_toml_path, exercise_edition| {
+            let fmt_cmd = rustfmt_cmd(cargo_toml_path, exercise_edition, true, false);
+            let args = fmt_cmd.get_args().collect::<Vec<_>>();
+            let ind = args.iter().position(|arg| *arg == "--edition").unwrap();
+            args[ind + 1].to_owned()
+        };
+
+        // Missing `Cargo.toml`.
+        assert_eq!(edition_arg(cargo_toml_path, None), DEFAULT_EDITION);
+
+        fs::write(
+            cargo_toml_path,
+            "bin = []\n\n[package]\nname = \"exercises\"\nedition = \"2024\"\n",
+        )
+        .unwrap();
+        assert_eq!(edition_arg(cargo_toml_path, None), "2024");
+        assert_eq!(edition_arg(cargo_toml_path, Some("2018")), "2018");
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    cache,
+    exercise::RunnableExercise,
+    hash::{toolchain_versions, StableHasher},
+    info_file::{exercises_dir, solutions_dir, ExerciseInfo, InfoFile},
+};
+
+use super::selection::Selection;
+
+// The name of the entry in `cache::CACHE_DIR`.
+const CACHE_ENTRY_NAME: &str = "dev-check.json";
+
+// Hash a file's content or the fact that the file couldn't be read.
+fn hash_file(hasher: &mut StableHasher, path: &str) {
+    fs::read(path).ok().hash(hasher);
+}
+
+/// The hashes of the exercises from the last successful run of `dev check`.
+#[derive(Serialize, Deserialize)]
+pub struct CheckCache {
+    // Hash of everything that affects all exercises. It is the key of the cache entry.
+    // The whole cache is invalid if it changes.
+    #[serde(skip)]
+    global_hash: u64,
+    exercise_hashes: HashMap<String, u64>,
+}
+
+impl CheckCache {
+    /// Hash everything that affects all exercises including the versions of `rustc` and Clippy.
+    /// `check_flags` are the options of `dev check` that change which checks run.
+    pub fn global_hash(
+        info_file: &InfoFile,
+        cargo_toml_path: &str,
+        check_flags: &[bool],
+        msrv: Option<&str>,
+    ) -> u64 {
+        let mut hasher = StableHasher::default();
+        // The checks might change between versions.
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        toolchain_versions().hash(&mut hasher);
+        check_flags.hash(&mut hasher);
+        msrv.hash(&mut hasher);
+        hash_file(&mut hasher, cargo_toml_path);
+        info_file.format_version.hash(&mut hasher);
+        info_file.welcome_message.hash(&mut hasher);
+        info_file.final_message.hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    /// Hash the exercise's metadata and all files of the exercise and its solution.
+    pub fn exercise_hash(exercise_info: &ExerciseInfo) -> u64 {
+        let mut hasher = StableHasher::default();
+        exercise_info.hash(&mut hasher);
+        hash_file(&mut hasher, &exercise_info.path());
+        hash_file(&mut hasher, &exercise_info.sol_path());
+        for path in exercise_info
+            .extra_file_paths(exercises_dir())
+            .chain(exercise_info.extra_file_paths(solutions_dir()))
+            .chain(exercise_info.additional_file_paths())
+            .chain(exercise_info.stdin_fixture_path())
+        {
+            hash_file(&mut hasher, &path);
+        }
+
+        hasher.finish()
+    }
+
+    /// Create a cache from the exercise hashes in the order of `info.toml`.
+    /// Excluded exercises weren't checked. Their hashes are kept from the previous cache.
+    pub fn new(
+        global_hash: u64,
+        info_file: &InfoFile,
+        exercise_hashes: &[u64],
+        selection: &[Selection],
+        prev_cache: Option<Self>,
+    ) -> Self {
+        let mut prev_exercise_hashes = prev_cache
+            .map(|prev_cache| prev_cache.exercise_hashes)
+            .unwrap_or_default();
+
+        let exercise_hashes = info_file
+            .exercises
+            .iter()
+            .zip(exercise_hashes)
+            .zip(selection)
+            .filter_map(|((exercise_info, hash), selection)| {
+                let hash = if *selection == Selection::Excluded {
+                    prev_exercise_hashes.remove(&exercise_info.name)?
+                } else {
+                    *hash
+                };
+
+                Some((exercise_info.name.clone(), hash))
+            })
+            .collect();
+
+        Self {
+            global_hash,
+            exercise_hashes,
+        }
+    }
+
+    /// Read the cache entry. Return `None` if it is missing, invalid or from a different setup.
+    pub fn read(global_hash: u64) -> Option<Self> {
+        let content = cache::read(CACHE_ENTRY_NAME, global_hash)?;
+        let mut cache = serde_json::from_slice::<Self>(&content).ok()?;
+        cache.global_hash = global_hash;
+
+        Some(cache)
+    }
+
+    /// Return for every exercise if it is unchanged since the last successful run.
+    pub fn unchanged(&self, info_file: &InfoFile, exercise_hashes: &[u64]) -> Vec<bool> {
+        info_file
+            .exercises
+            .iter()
+            .zip(exercise_hashes)
+            .map(|(exercise_info, hash)| {
+                self.exercise_hashes.get(&exercise_info.name) == Some(hash)
+            })
+            .collect()
+    }
+
+    /// Write the cache entry.
+    pub fn write(&self) -> Result<()> {
+        let content = serde_json::to_vec(self).context("Failed to serialize the check cache")?;
+        cache::write(CACHE_ENTRY_NAME, self.global_hash, &content)
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::{bail, Result};
+use std::fmt::{self, Display, Formatter};
+
+use crate::info_file::ExerciseInfo;
+
+/// Collects the problems found by a check instead of stopping at the first one.
+pub struct Problems {
+    fail_fast: bool,
+    problems: Vec<String>,
+}
+
+impl Problems {
+    pub fn new(fail_fast: bool) -> Self {
+        Self {
+            fail_fast,
+            problems: Vec::new(),
+        }
+    }
+
+    /// Fail fast: Return the problem as an error.
+    /// Otherwise: Collect the problem and continue.
+    pub fn add(&mut self, problem: String) -> Result<()> {
+        if self.fail_fast {
+            bail!(problem);
+        }
+
+        self.problems.push(problem);
+        Ok(())
+    }
+
+    /// Like `add`, but prefix the problem with the exercise's name and path.
+    pub fn add_for_exercise(
+        &mut self,
+        exercise_info: &ExerciseInfo,
+        problem: String,
+    ) -> Result<()> {
+        self.add(format!(
+            "`{}` ({}): {problem}",
+            exercise_info.name,
+            exercise_info.path(),
+        ))
+    }
+
+    /// Return all collected problems as one error if there are any.
+    pub fn finish(self, summary: &'static str) -> Result<()> {
+        if self.problems.is_empty() {
+            return Ok(());
+        }
+
+        Err(ProblemList {
+            summary,
+            problems: self.problems,
+        }
+        .into())
+    }
+}
+
+/// The error returned by `Problems::finish`.
+#[derive(Debug)]
+pub struct ProblemList {
+    summary: &'static str,
+    pub problems: Vec<String>,
+}
+
+impl Display for ProblemList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}):", self.summary, self.problems.len())?;
+
+        for problem in &self.problems {
+            write!(f, "\n\n- {problem}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ProblemList {}
@@ -0,0 +1,356 @@
+use anyhow::{Context, Error, Result};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    error,
+    fmt::{self, Display, Formatter},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::info_file::ExerciseInfo;
+
+use super::{problems::ProblemList, timings::Timings};
+
+/// The category of a failed check. Its value is the exit code of `dev check`.
+/// Ordered by priority if checks of multiple categories fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Failure {
+    /// `info.toml`, `Cargo.toml`, editor files or unexpected files.
+    Metadata = 2,
+    /// An exercise is already solved or an intentionally solved one fails.
+    Unsolved = 3,
+    Solution = 4,
+    Fmt = 5,
+}
+
+impl Failure {
+    /// Attach the category to the error to determine the exit code.
+    /// An already attached category is kept.
+    pub fn attach(self, error: Error) -> Error {
+        if error.is::<CheckError>() {
+            return error;
+        }
+
+        Error::new(CheckError {
+            failure: self,
+            error,
+        })
+    }
+}
+
+/// An error of a failed check with its category.
+#[derive(Debug)]
+pub struct CheckError {
+    pub failure: Failure,
+    pub error: Error,
+}
+
+// Only the outermost message. The rest of the chain is returned by `source`.
+impl Display for CheckError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl error::Error for CheckError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Passed,
+    Failed,
+    /// Not checked because the check depends on a failed check or only some exercises are checked.
+    Skipped,
+    /// The solution of an exercise is missing, but solutions aren't required.
+    Missing,
+    /// Not checked because the exercise didn't change since the last successful run.
+    Cached,
+}
+
+/// The result of checking one exercise or its solution.
+#[derive(Serialize)]
+pub struct ExerciseReport {
+    pub name: String,
+    /// Path of the exercise or solution file.
+    pub path: String,
+    pub status: Status,
+    /// The captured output or the error message if the check failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// The duration of running the exercise or solution with `--timings`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+}
+
+/// The results of one check category.
+#[derive(Serialize)]
+pub struct CategoryReport {
+    pub status: Status,
+    /// Errors that aren't related to a single exercise.
+    pub errors: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exercises: Vec<ExerciseReport>,
+    /// The highest priority category of the errors.
+    #[serde(skip)]
+    error_failure: Option<Failure>,
+}
+
+impl Default for CategoryReport {
+    fn default() -> Self {
+        Self {
+            status: Status::Passed,
+            errors: Vec::new(),
+            exercises: Vec::new(),
+            error_failure: None,
+        }
+    }
+}
+
+impl CategoryReport {
+    pub fn add_error(&mut self, failure: Failure, e: Error) {
+        self.status = Status::Failed;
+        self.error_failure = Some(self.error_failure.map_or(failure, |prev| prev.min(failure)));
+
+        // Split collected problems into separate errors.
+        match e.downcast::<ProblemList>() {
+            Ok(problem_list) => self.errors.extend(problem_list.problems),
+            Err(e) => self.errors.push(format!("{e:#}")),
+        }
+    }
+
+    pub fn add_exercise(
+        &mut self,
+        exercise_info: &ExerciseInfo,
+        path: String,
+        status: Status,
+        output: Option<String>,
+    ) {
+        if status == Status::Failed {
+            self.status = Status::Failed;
+        }
+
+        self.exercises.push(ExerciseReport {
+            name: exercise_info.name.clone(),
+            path,
+            status,
+            output,
+            duration_secs: None,
+        });
+    }
+
+    #[inline]
+    pub fn skip(&mut self) {
+        self.status = Status::Skipped;
+    }
+
+    // Failed exercises have the category `exercise_failure`.
+    fn failure(&self, exercise_failure: Failure) -> Option<Failure> {
+        if self.status != Status::Failed {
+            return None;
+        }
+
+        let exercise_failed = self
+            .exercises
+            .iter()
+            .any(|exercise| exercise.status == Status::Failed);
+        match (exercise_failed, self.error_failure) {
+            (true, Some(error_failure)) => Some(exercise_failure.min(error_failure)),
+            (false, Some(error_failure)) => Some(error_failure),
+            (_, None) => Some(exercise_failure),
+        }
+    }
+}
+
+/// The version of the format of the file written by `dev check --emit-report`.
+const EMITTED_REPORT_FORMAT_VERSION: u8 = 1;
+
+#[derive(Serialize)]
+struct EmittedExercise<'a> {
+    name: &'a str,
+    status: Status,
+    /// The captured output or the error message if the check failed.
+    error_message: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct EmittedSolution<'a> {
+    name: &'a str,
+    status: Status,
+}
+
+/// The file written by `dev check --emit-report` for CI systems.
+#[derive(Serialize)]
+struct EmittedReport<'a> {
+    /// Unix timestamp in seconds.
+    timestamp: u64,
+    rustlings_version: &'static str,
+    format_version: u8,
+    /// The check that the exercises aren't already solved.
+    exercises: Vec<EmittedExercise<'a>>,
+    solutions: Vec<EmittedSolution<'a>>,
+    /// Errors that aren't related to a single exercise, e.g. problems in `info.toml`.
+    errors: Vec<String>,
+}
+
+/// The JSON report of `dev check --json`.
+#[derive(Serialize, Default)]
+pub struct CheckReport {
+    /// Only some exercises were checked because of `--only`.
+    /// The checks of `cargo_toml`, `editor_files` and `unexpected_files` are skipped in this case.
+    pub partial: bool,
+    pub cargo_toml: CategoryReport,
+    /// The check of `rust-project.json` if it exists.
+    pub editor_files: CategoryReport,
+    pub info_file: CategoryReport,
+    pub unexpected_files: CategoryReport,
+    pub unsolved: CategoryReport,
+    pub solutions: CategoryReport,
+    /// Warnings that don't fail the check without `--strict`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Files modified by `--fix`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixed_files: Vec<String>,
+}
+
+impl CheckReport {
+    /// The highest priority category of the failed checks.
+    pub fn failure(&self) -> Option<Failure> {
+        [
+            (&self.cargo_toml, Failure::Metadata),
+            (&self.editor_files, Failure::Metadata),
+            (&self.info_file, Failure::Metadata),
+            (&self.unexpected_files, Failure::Metadata),
+            (&self.unsolved, Failure::Unsolved),
+            (&self.solutions, Failure::Solution),
+        ]
+        .into_iter()
+        .filter_map(|(category, exercise_failure)| category.failure(exercise_failure))
+        .min()
+    }
+
+    /// Add the durations and sort the exercises of every category in the order of `info.toml`.
+    pub fn finish(&mut self, exercise_infos: &[ExerciseInfo], timings: Option<&Timings>) {
+        if let Some(timings) = timings {
+            for (category, timings) in [
+                (&mut self.unsolved, &timings.exercises),
+                (&mut self.solutions, &timings.solutions),
+            ] {
+                let durations = timings.iter().copied().collect::<HashMap<_, _>>();
+                for exercise in &mut category.exercises {
+                    exercise.duration_secs = durations
+                        .get(exercise.name.as_str())
+                        .map(Duration::as_secs_f64);
+                }
+            }
+        }
+
+        let exercise_inds = exercise_infos
+            .iter()
+            .enumerate()
+            .map(|(ind, exercise_info)| (exercise_info.name.as_str(), ind))
+            .collect::<HashMap<_, _>>();
+
+        for category in [
+            &mut self.cargo_toml,
+            &mut self.editor_files,
+            &mut self.info_file,
+            &mut self.unexpected_files,
+            &mut self.unsolved,
+            &mut self.solutions,
+        ] {
+            // The exercises are added in the order of completion.
+            category
+                .exercises
+                .sort_by_key(|exercise| exercise_inds.get(exercise.name.as_str()).copied());
+        }
+    }
+
+    pub fn print(&self) -> Result<()> {
+        let mut stdout = io::stdout().lock();
+        serde_json::to_writer_pretty(&mut stdout, self)?;
+        stdout.write_all(b"\n")?;
+
+        Ok(())
+    }
+
+    /// Write the report of `dev check --emit-report` to the file.
+    /// `bail_error` is an error that stopped checking and isn't contained in the report.
+    pub fn emit(&self, path: &Path, bail_error: Option<&Error>) -> Result<()> {
+        let exercises = self
+            .unsolved
+            .exercises
+            .iter()
+            .map(|exercise| EmittedExercise {
+                name: &exercise.name,
+                status: exercise.status,
+                error_message: exercise
+                    .output
+                    .as_deref()
+                    .filter(|_| exercise.status == Status::Failed),
+            })
+            .collect();
+        let solutions = self
+            .solutions
+            .exercises
+            .iter()
+            .map(|exercise| EmittedSolution {
+                name: &exercise.name,
+                status: exercise.status,
+            })
+            .collect();
+        let errors = [
+            &self.cargo_toml,
+            &self.editor_files,
+            &self.info_file,
+            &self.unexpected_files,
+            &self.unsolved,
+            &self.solutions,
+        ]
+        .into_iter()
+        .flat_map(|category| category.errors.iter().cloned())
+        .chain(bail_error.map(|e| format!("{e:#}")))
+        .collect();
+
+        let report = EmittedReport {
+            // A clock before the Unix epoch can't be represented anyway.
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+            rustlings_version: env!("CARGO_PKG_VERSION"),
+            format_version: EMITTED_REPORT_FORMAT_VERSION,
+            exercises,
+            solutions,
+            errors,
+        };
+
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create the report {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, &report)
+            .with_context(|| format!("Failed to write the report {}", path.display()))?;
+        writer
+            .write_all(b"\n")
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("Failed to write the report {}", path.display()))
+    }
+}
+
+/// Human-readable output: Return the error with the attached failure category to stop checking.
+/// JSON report: Record the error in the category and continue.
+pub fn handle_err(category: Option<&mut CategoryReport>, failure: Failure, e: Error) -> Result<()> {
+    match category {
+        Some(category) => {
+            category.add_error(failure, e);
+            Ok(())
+        }
+        None => Err(failure.attach(e)),
+    }
+}
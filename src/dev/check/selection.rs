@@ -0,0 +1,112 @@
+use anyhow::{bail, Result};
+
+use crate::info_file::InfoFile;
+
+/// Whether an exercise is checked in the current run of `dev check`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// Not matched by any pattern of `--only`.
+    Excluded,
+    /// Unchanged since the last successful run.
+    Cached,
+    Checked,
+}
+
+// Match `name` against the glob `pattern`.
+// `*` matches any number of chars and `?` matches exactly one char.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    let mut pattern_ind = 0;
+    let mut name_ind = 0;
+    // The index of the last `*` in the pattern and the index in the name where it stopped matching.
+    let mut last_star = None;
+
+    while name_ind < name.len() {
+        match pattern.get(pattern_ind) {
+            Some('*') => {
+                last_star = Some((pattern_ind, name_ind));
+                pattern_ind += 1;
+            }
+            Some(c) if *c == '?' || *c == name[name_ind] => {
+                pattern_ind += 1;
+                name_ind += 1;
+            }
+            _ => {
+                // Let the last `*` match one more char.
+                let Some((star_pattern_ind, star_name_ind)) = last_star else {
+                    return false;
+                };
+                last_star = Some((star_pattern_ind, star_name_ind + 1));
+                pattern_ind = star_pattern_ind + 1;
+                name_ind = star_name_ind + 1;
+            }
+        }
+    }
+
+    pattern[pattern_ind..].iter().all(|c| *c == '*')
+}
+
+/// Return for every exercise if it matches any of the names or glob patterns of `--only`.
+/// All exercises match if there are no patterns.
+pub fn matching_exercises(info_file: &InfoFile, patterns: &[String]) -> Result<Vec<bool>> {
+    if patterns.is_empty() {
+        return Ok(vec![true; info_file.exercises.len()]);
+    }
+
+    let unknown_patterns = patterns
+        .iter()
+        .filter(|pattern| {
+            !info_file
+                .exercises
+                .iter()
+                .any(|exercise_info| glob_match(pattern, &exercise_info.name))
+        })
+        .map(|pattern| format!("`{pattern}`"))
+        .collect::<Vec<_>>();
+
+    if !unknown_patterns.is_empty() {
+        let unknown_patterns = unknown_patterns.join(", ");
+        let names = info_file
+            .exercises
+            .iter()
+            .map(|exercise_info| exercise_info.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        bail!(
+            "No exercise matches {unknown_patterns} of `--only`. The exercise names are: {names}"
+        );
+    }
+
+    Ok(info_file
+        .exercises
+        .iter()
+        .map(|exercise_info| {
+            patterns
+                .iter()
+                .any(|pattern| glob_match(pattern, &exercise_info.name))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        assert!(glob_match("structs1", "structs1"));
+        assert!(!glob_match("structs1", "structs2"));
+        assert!(!glob_match("structs", "structs1"));
+        assert!(glob_match("structs*", "structs"));
+        assert!(glob_match("structs*", "structs3"));
+        assert!(glob_match("*s1", "structs1"));
+        assert!(glob_match("s*s*", "structs1"));
+        assert!(glob_match("structs?", "structs2"));
+        assert!(!glob_match("structs?", "structs"));
+        assert!(glob_match("*", "intro1"));
+        assert!(glob_match("*_*_*", "a_b_c"));
+        assert!(!glob_match("*_*_*", "a_bc"));
+    }
+}
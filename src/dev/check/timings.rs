@@ -0,0 +1,47 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+// The number of the slowest exercises and solutions to print.
+const N_SLOWEST: usize = 10;
+
+/// How long running each exercise and solution took for `dev check --timings`.
+#[derive(Default)]
+pub struct Timings<'a> {
+    pub exercises: Vec<(&'a str, Duration)>,
+    pub solutions: Vec<(&'a str, Duration)>,
+}
+
+impl Timings<'_> {
+    /// Print the slowest exercises and solutions and the total durations.
+    pub fn print(&mut self, total: Duration) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+
+        for (kind, timings) in [
+            ("exercises", &mut self.exercises),
+            ("solutions", &mut self.solutions),
+        ] {
+            timings.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+
+            writeln!(stdout, "\nSlowest {kind}:")?;
+            for (name, duration) in timings.iter().take(N_SLOWEST) {
+                writeln!(stdout, "{:>8.2}s  {name}", duration.as_secs_f64())?;
+            }
+
+            // Larger than the wall-clock duration if running in parallel.
+            let sum = timings
+                .iter()
+                .map(|(_, duration)| *duration)
+                .sum::<Duration>();
+            writeln!(
+                stdout,
+                "Sum of {} {kind}: {:.2}s",
+                timings.len(),
+                sum.as_secs_f64(),
+            )?;
+        }
+
+        writeln!(stdout, "\nTotal: {:.2}s", total.as_secs_f64())
+    }
+}
@@ -0,0 +1,273 @@
+use anyhow::Result;
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+};
+
+use crate::info_file::ExerciseInfo;
+
+use super::problems::Problems;
+
+const MAX_HINT_LINES: usize = 20;
+const MAX_FILE_LINES: usize = 200;
+const MAX_LINE_LEN: usize = 100;
+
+/// Collects findings that are only problems with `--strict`.
+pub struct Warnings {
+    strict: bool,
+    warnings: Vec<String>,
+    exercise_names: HashSet<String>,
+}
+
+impl Warnings {
+    pub fn new(strict: bool) -> Self {
+        Self {
+            strict,
+            warnings: Vec::new(),
+            exercise_names: HashSet::new(),
+        }
+    }
+
+    /// Strict: Add the warning as a problem.
+    /// Otherwise: Collect the warning prefixed with the exercise's name and path.
+    pub fn add_for_exercise(
+        &mut self,
+        problems: &mut Problems,
+        exercise_info: &ExerciseInfo,
+        warning: String,
+    ) -> Result<()> {
+        if self.strict {
+            return problems.add_for_exercise(exercise_info, warning);
+        }
+
+        self.push_for_exercise(exercise_info, warning);
+        Ok(())
+    }
+
+    /// Collect the warning independent of `--strict`.
+    /// For checks that don't use `Problems` and handle `--strict` themselves.
+    pub fn push_for_exercise(&mut self, exercise_info: &ExerciseInfo, warning: String) {
+        self.warnings.push(format!(
+            "`{}` ({}): {warning}",
+            exercise_info.name,
+            exercise_info.path(),
+        ));
+        self.exercise_names.insert(exercise_info.name.clone());
+    }
+
+    pub fn extend(&mut self, other: Self) {
+        self.warnings.extend(other.warnings);
+        self.exercise_names.extend(other.exercise_names);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    // "N warnings in M exercises"
+    fn summary(&self) -> String {
+        let n_warnings = self.warnings.len();
+        let n_exercises = self.exercise_names.len();
+        format!(
+            "{n_warnings} warning{} in {n_exercises} exercise{}",
+            if n_warnings == 1 { "" } else { "s" },
+            if n_exercises == 1 { "" } else { "s" },
+        )
+    }
+
+    pub fn print(&self) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        for warning in &self.warnings {
+            writeln!(stdout, "Warning: {warning}\n")?;
+        }
+        writeln!(
+            stdout,
+            "{}. Run with `--strict` to treat them as errors\n",
+            self.summary(),
+        )
+    }
+
+    pub fn into_vec(self) -> Vec<String> {
+        self.warnings
+    }
+}
+
+/// Return a warning if the hint is too long to be helpful at once.
+pub fn hint_warning(level: usize, hint: &str) -> Option<String> {
+    let n_lines = hint.lines().count();
+    (n_lines > MAX_HINT_LINES).then(|| {
+        format!("The hint of level {level} has {n_lines} lines which is more than {MAX_HINT_LINES}. Consider splitting it into multiple hint levels")
+    })
+}
+
+// Keywords and primitive types which hints mention without them appearing in the exercise.
+const LANGUAGE_WORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "bool", "char", "str", "f32", "f64", "i8", "i16", "i32",
+    "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Return the identifiers that are quoted in backticks in the hint, e.g. `parse_args`.
+/// Code like `x + 1` and keywords like `match` are skipped.
+pub fn hint_identifiers(hint: &str) -> impl Iterator<Item = &str> {
+    // Ignore the text after an unclosed backtick.
+    let hint = if hint.matches('`').count() % 2 == 1 {
+        hint.rsplit_once('`').map_or(hint, |(closed, _)| closed)
+    } else {
+        hint
+    };
+
+    // Every odd part is between backticks.
+    hint.split('`').skip(1).step_by(2).filter(|code| {
+        code.chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && code.chars().all(|c| c.is_alphanumeric() || c == '_')
+            && !LANGUAGE_WORDS.contains(code)
+    })
+}
+
+// Lints that exercises commonly allow because they are unfinished.
+const COMMON_ALLOWED_LINTS: [&str; 2] = ["dead_code", "unused"];
+
+/// Return the lints allowed by `#[allow(…)]` and `#![allow(…)]` attributes in the content.
+/// `dead_code` and `unused` are skipped because unfinished exercises often need them.
+pub fn allowed_lints(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .match_indices("allow(")
+        .filter(|(ind, _)| {
+            let before = content[..*ind].trim_end();
+            before.ends_with("#[") || before.ends_with("#![")
+        })
+        .filter_map(|(ind, pattern)| content[ind + pattern.len()..].split_once(')'))
+        .flat_map(|(lints, _)| lints.split(','))
+        .map(str::trim)
+        .filter(|lint| !lint.is_empty() && !COMMON_ALLOWED_LINTS.contains(lint))
+}
+
+/// Return warnings about the style of the file's content.
+/// Only the first line that violates a rule is mentioned.
+pub fn style_warnings(path: &str, content: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    let n_lines = content.lines().count();
+    if n_lines > MAX_FILE_LINES {
+        warnings.push(format!(
+            "The file `{path}` has {n_lines} lines which is more than {MAX_FILE_LINES}"
+        ));
+    }
+
+    if content.contains("\r\n") {
+        warnings.push(format!(
+            "The file `{path}` has CRLF line endings. Use LF line endings instead"
+        ));
+    }
+
+    let mut long_line = None;
+    let mut tab_line = None;
+    let mut trailing_whitespace_line = None;
+    // `lines` strips the `\r` of CRLF line endings.
+    for (line_num, line) in (1..).zip(content.lines()) {
+        if long_line.is_none() && line.chars().count() > MAX_LINE_LEN {
+            long_line = Some(line_num);
+        }
+        if tab_line.is_none() && line.trim_start_matches(' ').starts_with('\t') {
+            tab_line = Some(line_num);
+        }
+        if trailing_whitespace_line.is_none() && line.ends_with([' ', '\t']) {
+            trailing_whitespace_line = Some(line_num);
+        }
+    }
+
+    if let Some(line_num) = long_line {
+        warnings.push(format!(
+            "Line {line_num} of the file `{path}` is longer than {MAX_LINE_LEN} characters"
+        ));
+    }
+    if let Some(line_num) = tab_line {
+        warnings.push(format!(
+            "Line {line_num} of the file `{path}` is indented with tabs. Use spaces instead"
+        ));
+    }
+    if let Some(line_num) = trailing_whitespace_line {
+        warnings.push(format!(
+            "Line {line_num} of the file `{path}` has trailing whitespace"
+        ));
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn style() {
+        assert!(style_warnings("a.rs", "fn main() {\n    let x = 1;\n}\n").is_empty());
+
+        let warnings = style_warnings("a.rs", "fn main() {\r\n    let x = 1;\r\n}\r\n");
+        assert_eq!(
+            warnings,
+            ["The file `a.rs` has CRLF line endings. Use LF line endings instead"],
+        );
+
+        let long_line = format!("// {}\n", "x".repeat(MAX_LINE_LEN - 3));
+        assert!(style_warnings("a.rs", &long_line).is_empty());
+        let content = format!("fn main() {{}}\n{}", long_line.replace("//", "///"));
+        assert_eq!(
+            style_warnings("a.rs", &content),
+            ["Line 2 of the file `a.rs` is longer than 100 characters"],
+        );
+        // Chars, not bytes.
+        assert!(style_warnings("a.rs", &long_line.replace('x', "ä")).is_empty());
+
+        assert_eq!(
+            style_warnings("a.rs", "fn main() {\n\tlet x = 1; \n}\n"),
+            [
+                "Line 2 of the file `a.rs` is indented with tabs. Use spaces instead",
+                "Line 2 of the file `a.rs` has trailing whitespace",
+            ],
+        );
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(
+            hint_identifiers(
+                "Call `parse_args` with `x + 1` in a `match`. Then `Vec::new()`, `_x`"
+            )
+            .collect::<Vec<_>>(),
+            ["parse_args", "_x"],
+        );
+        // An unclosed backtick.
+        assert_eq!(hint_identifiers("`a` and `b").collect::<Vec<_>>(), ["a"]);
+    }
+
+    #[test]
+    fn lints() {
+        assert_eq!(
+            allowed_lints(
+                "#![allow(clippy::ptr_arg)]\n#[allow(unused, dead_code)]\n#[allow(unused_variables, unused_assignments,)]\nfn main() {}"
+            )
+            .collect::<Vec<_>>(),
+            ["clippy::ptr_arg", "unused_variables", "unused_assignments"],
+        );
+        assert_eq!(
+            allowed_lints("// Don't allow(x).\n#[cfg_attr(test, allow(y))]").collect::<Vec<_>>(),
+            Vec::<&str>::new(),
+        );
+    }
+
+    #[test]
+    fn long_hint() {
+        assert!(hint_warning(1, &"line\n".repeat(MAX_HINT_LINES)).is_none());
+        assert!(hint_warning(2, &"line\n".repeat(MAX_HINT_LINES + 1)).is_some());
+    }
+}
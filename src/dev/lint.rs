@@ -0,0 +1,186 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::{
+    io::{self, Write},
+    path::Path,
+    thread,
+};
+
+use crate::{
+    cli::SandboxArgs,
+    cmd::CmdRunner,
+    exercise::{RunnableExercise, OUTPUT_CAPACITY},
+    info_file::{ExerciseInfo, InfoFile},
+};
+
+use super::check::{problems::Problems, run_in_parallel, DEFAULT_JOBS};
+
+// An exercise or its solution to run Clippy on.
+struct LintTarget<'a> {
+    exercise_info: &'a ExerciseInfo,
+    solution: bool,
+}
+
+impl LintTarget<'_> {
+    fn bin_name(&self) -> String {
+        if self.solution {
+            format!("{}_sol", self.exercise_info.name)
+        } else {
+            self.exercise_info.name.clone()
+        }
+    }
+
+    fn path(&self) -> String {
+        if self.solution {
+            self.exercise_info.sol_path()
+        } else {
+            self.exercise_info.path()
+        }
+    }
+}
+
+// Parses the parts of a line of `cargo clippy --message-format json` that are needed.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CompilerMessage>,
+}
+
+#[derive(Deserialize)]
+struct CompilerMessage {
+    code: Option<DiagnosticCode>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticCode {
+    code: String,
+}
+
+// Run Clippy and return the rendered lints if there are any.
+// Exercises are expected to have compiler errors and warnings. Only Clippy lints are reported for them.
+// Everything is reported for solutions.
+fn lint_target(target: &LintTarget, cmd_runner: &CmdRunner) -> Result<Option<String>> {
+    let bin_name = target.bin_name();
+    let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+    let mut clippy_cmd = cmd_runner.cargo(
+        "clippy",
+        &bin_name,
+        target.exercise_info.features(),
+        Some(&mut output),
+        None,
+    );
+    // `--profile test` is required to also check code with `#[cfg(test)]`.
+    clippy_cmd.args([
+        "--profile",
+        "test",
+        "--message-format",
+        "json",
+        "--",
+        "-D",
+        "warnings",
+    ]);
+
+    let success = clippy_cmd.run("cargo clippy …");
+    cmd_runner.clean_sandbox(&bin_name);
+    if success? {
+        return Ok(None);
+    }
+
+    let mut lints = String::new();
+    // Lines that aren't JSON are from Cargo's human-readable output on stderr.
+    for message in output
+        .split(|c| *c == b'\n')
+        .filter_map(|line| serde_json::from_slice::<CargoMessage>(line).ok())
+        .filter(|message| message.reason == "compiler-message")
+        .filter_map(|message| message.message)
+    {
+        let is_clippy_lint = message
+            .code
+            .as_ref()
+            .is_some_and(|code| code.code.starts_with("clippy::"));
+        if !target.solution && !is_clippy_lint {
+            continue;
+        }
+
+        if let Some(rendered) = message.rendered {
+            lints.push_str(&rendered);
+        }
+    }
+
+    if lints.is_empty() && target.solution {
+        // Failed without any diagnostic, e.g. because of a Cargo error.
+        lints = String::from_utf8_lossy(&output).into_owned();
+    }
+
+    Ok((!lints.is_empty()).then_some(lints))
+}
+
+pub fn lint() -> Result<()> {
+    let info_file = InfoFile::parse()?;
+    let cmd_runner = CmdRunner::build(SandboxArgs::default())?;
+
+    let mut targets = Vec::with_capacity(2 * info_file.exercises.len());
+    for exercise_info in &info_file.exercises {
+        if !exercise_info.skip_check_lint {
+            targets.push(LintTarget {
+                exercise_info,
+                solution: false,
+            });
+        }
+
+        // The solution of a `should_fail` exercise doesn't compile.
+        if !exercise_info.should_fail && Path::new(&exercise_info.sol_path()).exists() {
+            targets.push(LintTarget {
+                exercise_info,
+                solution: true,
+            });
+        }
+    }
+
+    let jobs = thread::available_parallelism().map_or(DEFAULT_JOBS, |count| count.get());
+    let mut problems = Problems::new(false);
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(b"Running Clippy on all exercises and solutions...\n")?;
+    let n_targets = targets.len();
+    write!(stdout, "Progress: 0/{n_targets}")?;
+    stdout.flush()?;
+    let mut n_done = 0;
+
+    run_in_parallel(
+        &targets,
+        jobs,
+        |target| lint_target(target, &cmd_runner),
+        |target, result, _| {
+            match result {
+                Ok(None) => (),
+                Ok(Some(lints)) => problems.add(format!(
+                    "`{}` ({}):\n{}",
+                    target.exercise_info.name,
+                    target.path(),
+                    lints.trim_end(),
+                ))?,
+                Err(e) => problems.add(format!(
+                    "`{}` ({}): {e:#}",
+                    target.exercise_info.name,
+                    target.path(),
+                ))?,
+            }
+
+            n_done += 1;
+            write!(stdout, "\rProgress: {n_done}/{n_targets}")?;
+            stdout.flush()?;
+
+            Ok(())
+        },
+        |target| anyhow!("Panic while trying to run Clippy on {}", target.path()),
+    )?;
+    stdout.write_all(b"\n")?;
+
+    problems.finish("Found Clippy warnings. Fix them or add `skip_check_lint = true` to exercises that intentionally trigger them")?;
+
+    stdout.write_all(b"No Clippy warnings found!\n")?;
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path};
+use toml_edit::{value, DocumentMut};
+
+use crate::{info_file::InfoFile, CURRENT_FORMAT_VERSION};
+
+/// Migrate the document of `info.toml` from one format version to the next.
+/// The document is edited instead of the deserialized `InfoFile` to keep the comments of authors.
+type Migration = fn(&mut DocumentMut) -> Result<()>;
+
+/// The migration at index `i` migrates from the format version `i + 1` to `i + 2`.
+/// Add a migration with a doc comment explaining the changes for every bump of `CURRENT_FORMAT_VERSION`.
+const MIGRATIONS: &[Migration] = &[];
+
+const _: () = assert!(
+    MIGRATIONS.len() + 1 == CURRENT_FORMAT_VERSION as usize,
+    "Every format version needs a migration",
+);
+
+/// Apply the migrations from the `format_version` of the content to the last one.
+/// Returns the migrated content with the original format version or `None` if it is already current.
+fn migrate_content(content: &str, migrations: &[Migration]) -> Result<Option<(String, usize)>> {
+    let mut document = content
+        .parse::<DocumentMut>()
+        .context("Failed to parse the file `info.toml`")?;
+    let current_version = migrations.len() + 1;
+
+    let Some(format_version) = document
+        .get("format_version")
+        .and_then(|format_version| format_version.as_integer())
+    else {
+        bail!("The file `info.toml` has no integer `format_version`");
+    };
+    let format_version = match usize::try_from(format_version) {
+        Ok(0) | Err(_) => bail!("The format version {format_version} of `info.toml` is invalid"),
+        Ok(format_version) if format_version > current_version => bail!(
+            "The format version {format_version} of `info.toml` is higher than the last one supported ({current_version}).
+Try to install the latest Rustlings version first"
+        ),
+        Ok(format_version) if format_version == current_version => return Ok(None),
+        Ok(format_version) => format_version,
+    };
+
+    for (migration, version) in migrations[format_version - 1..]
+        .iter()
+        .zip(format_version..)
+    {
+        migration(&mut document).with_context(|| {
+            format!(
+                "Failed to migrate `info.toml` from the format version {version} to {}",
+                version + 1,
+            )
+        })?;
+    }
+    document["format_version"] = value(current_version as i64);
+
+    Ok(Some((document.to_string(), format_version)))
+}
+
+pub fn migrate(dry_run: bool) -> Result<()> {
+    if !Path::new("info.toml").exists() {
+        bail!("No `info.toml` file found. Run this command in a project created with `rustlings dev new`");
+    }
+
+    let content = fs::read_to_string("info.toml").context("Failed to read the file `info.toml`")?;
+    let Some((migrated_content, format_version)) = migrate_content(&content, MIGRATIONS)? else {
+        println!(
+            "The file `info.toml` already has the current format version {CURRENT_FORMAT_VERSION}"
+        );
+        return Ok(());
+    };
+
+    // Verify the migration to never break `info.toml`.
+    toml_edit::de::from_str::<InfoFile>(&migrated_content)
+        .context("The migrated `info.toml` file is invalid. Migrate it manually")?;
+
+    if dry_run {
+        print!("{migrated_content}");
+        return Ok(());
+    }
+
+    fs::write("info.toml", migrated_content).context("Failed to write the file `info.toml`")?;
+    println!(
+        "Migrated `info.toml` from the format version {format_version} to {CURRENT_FORMAT_VERSION}"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Example: Rename `hint` to `hints`.
+    fn rename_hint(document: &mut DocumentMut) -> Result<()> {
+        let Some(exercises) = document
+            .get_mut("exercises")
+            .and_then(|exercises| exercises.as_array_of_tables_mut())
+        else {
+            bail!("No exercises");
+        };
+
+        for exercise in exercises.iter_mut() {
+            if let Some(hint) = exercise.remove("hint") {
+                exercise.insert("hints", hint);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn migrations() {
+        let content = "# Comment\nformat_version = 1\n\n[[exercises]]\nname = \"intro1\"\nhint = \"\"\"\nHint\"\"\"\n";
+
+        assert_eq!(migrate_content(content, &[]).unwrap(), None);
+        assert_eq!(
+            migrate_content(content, &[rename_hint]).unwrap(),
+            Some((
+                String::from("# Comment\nformat_version = 2\n\n[[exercises]]\nname = \"intro1\"\nhints = \"\"\"\nHint\"\"\"\n"),
+                1,
+            )),
+        );
+        assert_eq!(
+            migrate_content(
+                &content.replace("format_version = 1", "format_version = 2"),
+                &[]
+            )
+            .unwrap_err()
+            .to_string(),
+            "The format version 2 of `info.toml` is higher than the last one supported (1).
+Try to install the latest Rustlings version first",
+        );
+        assert!(migrate_content("[[exercises]]\n", &[]).is_err());
+    }
+}
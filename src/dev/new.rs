@@ -6,7 +6,12 @@ use std::{
     process::Command,
 };
 
-use crate::{init::RUST_ANALYZER_TOML, CURRENT_FORMAT_VERSION};
+use crate::{
+    cargo_toml::updated_cargo_toml, info_file::InfoFile, init::RUST_ANALYZER_TOML,
+    CURRENT_FORMAT_VERSION,
+};
+
+use super::scaffold::check_name;
 
 // Create a directory relative to the current directory and print its path.
 fn create_rel_dir(dir_name: &str, current_dir: &str) -> Result<()> {
@@ -37,15 +42,7 @@ pub fn new(path: &Path, no_git: bool) -> Result<()> {
     set_current_dir(path)
         .with_context(|| format!("Failed to set {dir_path_str} as the current directory"))?;
 
-    if !no_git
-        && !Command::new("git")
-            .arg("init")
-            .status()
-            .context("Failed to run `git init`")?
-            .success()
-    {
-        bail!("`git init` didn't run successfully. See the possible error message above");
-    }
+    git_init(no_git)?;
 
     write_rel_file(".gitignore", &dir_path_str, GITIGNORE)?;
 
@@ -76,7 +73,64 @@ pub fn new(path: &Path, no_git: bool) -> Result<()> {
     Ok(())
 }
 
+// Run `git init` in the current directory unless `no_git` is true.
+fn git_init(no_git: bool) -> Result<()> {
+    if !no_git
+        && !Command::new("git")
+            .arg("init")
+            .status()
+            .context("Failed to run `git init`")?
+            .success()
+    {
+        bail!("`git init` didn't run successfully. See the possible error message above");
+    }
+
+    Ok(())
+}
+
+/// Create a standalone exercise pack in the directory `name` with an example exercise
+/// that passes `rustlings dev check`.
+pub fn new_pack(name: &str, no_git: bool) -> Result<()> {
+    check_name(name)?;
+
+    create_dir(name).with_context(|| format!("Failed to create the directory {name}"))?;
+    println!("Created the directory {name}");
+
+    set_current_dir(name)
+        .with_context(|| format!("Failed to set {name} as the current directory"))?;
+
+    git_init(no_git)?;
+
+    write_rel_file(".gitignore", name, GITIGNORE)?;
+
+    create_rel_dir("exercises", name)?;
+    create_rel_dir("solutions", name)?;
+    write_rel_file("exercises/intro1.rs", name, PACK_EXERCISE)?;
+    write_rel_file("solutions/intro1.rs", name, PACK_SOLUTION)?;
+
+    let info_file = format!("{PACK_INFO_FILE_BEFORE_FORMAT_VERSION}{CURRENT_FORMAT_VERSION}{PACK_INFO_FILE_AFTER_FORMAT_VERSION}");
+    let exercise_infos = toml_edit::de::from_str::<InfoFile>(&info_file)
+        .context("Failed to parse the generated `info.toml` file")?
+        .exercises;
+    write_rel_file("info.toml", name, info_file)?;
+
+    // The solutions must exist to have their binaries in the `bin` list.
+    let cargo_toml = updated_cargo_toml(&exercise_infos, CARGO_TOML, b"")?;
+    write_rel_file("Cargo.toml", name, cargo_toml)?;
+
+    let readme = README.replacen("Rustlings", name, 1).replace(
+        "these third-party Rustlings exercises",
+        &format!("the Rustlings exercise pack `{name}`"),
+    );
+    write_rel_file("README.md", name, readme)?;
+
+    println!("\nCreated the exercise pack `{name}` ✓\nRun `rustlings dev check` in it after adding exercises");
+
+    Ok(())
+}
+
 pub const GITIGNORE: &[u8] = b".rustlings-state.txt
+.rustlings-cache/
 Cargo.lock
 target/
 .vscode/
@@ -100,6 +154,29 @@ welcome_message = """Welcome to these third-party Rustlings exercises."""
 # Optional multi-line message to be shown to users after finishing all exercises.
 final_message = """We hope that you found the exercises helpful :D"""
 
+# Optional minimum supported Rust version of the exercises.
+# `rustlings dev check` also builds all solutions with it if it is installed by rustup.
+# msrv = "1.75"
+
+# Optional requirement of a `README.md` file with at least one link in every exercise directory.
+# For example, a link to the chapter of the book that the exercises of the directory are about.
+# require_readmes = true
+
+# Optional packs of exercises from other sources.
+# `rustlings pack fetch` downloads their `info.toml` fragments with only `[[exercises]]` entries
+# into the `packs` directory and appends their exercises. The `dir` of a pack exercise is
+# prefixed with the pack name (`NAME_DIR` or just `NAME` without a `dir`).
+# [[packs]]
+# name = "???"
+# url = "https://???"
+# sha256 = "???"
+
+# Optional groups to show the exercises in sections in `rustlings list`.
+# Groups in which all exercises are done are collapsed to their count.
+# [[groups]]
+# name = "???"
+# description = "???"
+
 # Repeat this section for every exercise.
 [[exercises]]
 # Exercise name which is the exercise file name without the `.rs` extension.
@@ -115,17 +192,149 @@ name = "???"
 # In that case, the exercise will be considered done when it just successfully compiles.
 # test = true
 
+# Set `test` to `"custom"` to run `test_command` instead of `cargo test`.
+# The command isn't run in a shell. Cargo subcommands get the usual arguments like `--bin`.
+# Other programs get the path of the built exercise binary in the environment variable `RUSTLINGS_BIN`.
+# test_command = "cargo test -- --test-threads 1"
+
 # Rustlings will always run Clippy on exercises.
 # You can optionally set `strict_clippy` to `true` (the default is `false`) to only consider
 # the exercise as done when there are no warnings left.
 # strict_clippy = false
 
+# Optional list of Cargo features to activate when running the exercise,
+# e.g. for an exercise that teaches `#[cfg(feature = "…")]`.
+# They must be declared in the `[features]` table of `Cargo.toml`.
+# features = ["???"]
+
+# Set `should_fail` to `true` (the default is `false`) for exercises that are done when they
+# fail to compile, e.g. to show a rule of the borrow checker. Add the comment `// #[should_fail]`
+# to the exercise file. Such exercises need `test = false`. Their solutions must fail to compile.
+# should_fail = false
+
+# `rustlings dev lint` reports Clippy warnings in exercises and solutions.
+# Set `skip_check_lint` to `true` (the default is `false`) for exercises that intentionally
+# trigger Clippy warnings to teach about them.
+# skip_check_lint = false
+
+# `rustlings dev check` warns about lints allowed with `#[allow(…)]` attributes in the exercise
+# except for `dead_code` and `unused`. List the lints that are intentionally allowed,
+# e.g. because the exercise teaches to fix them. Explain why in a comment next to the attribute.
+# allowed_lints = ["???"]
+
+# `rustlings dev check` fails if the exercise file contains one of these patterns,
+# e.g. `.unwrap()` in an exercise about handling errors or `.clone()` in one about borrowing.
+# Patterns starting or ending with a letter, digit or `_` only match whole words.
+# forbidden_patterns = ["???"]
+
+# `rustlings dev check` checks that all solutions are formatted with `rustfmt`.
+# Set `skip_fmt_check` to `true` (the default is `false`) if the solution intentionally
+# uses a different formatting, e.g. to highlight alignment.
+# skip_fmt_check = false
+
+# Optional difficulty from 1 (easiest) to 5 (hardest).
+# It is shown in `rustlings list` which can also sort the exercises by difficulty.
+# difficulty = 1
+
+# Optional estimated time in minutes from 1 to 120 to solve the exercise.
+# `rustlings list --show-time` shows it and the total time of all exercises.
+# estimated_minutes = 10
+
+# Optional list of concepts covered by the exercise.
+# Tags can only contain lowercase ASCII letters, digits and `-`.
+# Users can run `rustlings list --tag TAG` to find all exercises about a concept.
+# tags = ["???"]
+
+# Optional name of the group in `[[groups]]` that the exercise is listed under.
+# Users can run `rustlings list --group GROUP` to only list the exercises in it.
+# group = "???"
+
+# Optional list of additional Rust files for exercises that span multiple files.
+# The files are in the same directory as the exercise file which includes them as modules
+# (e.g. `mod helpers;` for `helpers.rs`). Only the exercise file can have a `main` function.
+# extra_files = ["???.rs"]
+
+# Optional list of non-Rust files that the exercise needs, e.g. `data.csv` for an exercise that
+# reads it. The files are in the same directory as the exercise file.
+# additional_files = ["???"]
+
+# Optional list of names of earlier exercises that this exercise builds on.
+# `rustlings run NAME` refuses to run the exercise before they are done unless `--force` is used.
+# `rustlings hint` and `rustlings watch` mention the ones that aren't done yet. `requires` is an alias.
+# prerequisites = ["???"]
+
+# Optional attribution of community-contributed exercises shown by `rustlings info NAME`.
+# `source_url` must be an `http://` or `https://` URL.
+# author = "???"
+# source_url = "https://???"
+
+# `rustlings dev check` kills the binary or the tests of the exercise if they take longer than
+# `timeout_secs` seconds (the default is 30). Compiling isn't limited. Increase it for slow tests.
+# timeout_secs = 30
+
+# Optional Rust edition of the exercise and its solution if it differs from the one in `Cargo.toml`.
+# One of "2015", "2018", "2021" and "2024".
+# edition = "2018"
+
 # A multi-line hint to be shown to users on request.
+# Use a list of hints with the key `hints` instead to give progressive hints from a nudge
+# to a near-solution. Users can show them with `rustlings hint --level N`.
+# hints = ["???", """???"""]
 hint = """???"""
 "#;
 
-const CARGO_TOML: &[u8] =
-    br#"# Don't edit the `bin` list manually! It is updated by `rustlings dev update`
+const PACK_INFO_FILE_BEFORE_FORMAT_VERSION: &str =
+    "# See the `info.toml` file created by `rustlings dev new` for all fields of exercises.
+format_version = ";
+
+const PACK_INFO_FILE_AFTER_FORMAT_VERSION: &str = r#"
+
+[[exercises]]
+name = "intro1"
+hint = """
+Return the greeting that the test expects."""
+"#;
+
+const PACK_EXERCISE: &str = r#"// TODO: Fix the function `greeting` to make the test pass.
+fn greeting() -> &'static str {
+    "Hello"
+}
+
+fn main() {
+    println!("{}", greeting());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greets_the_world() {
+        assert_eq!(greeting(), "Hello, world!");
+    }
+}
+"#;
+
+const PACK_SOLUTION: &str = r#"fn greeting() -> &'static str {
+    "Hello, world!"
+}
+
+fn main() {
+    println!("{}", greeting());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greets_the_world() {
+        assert_eq!(greeting(), "Hello, world!");
+    }
+}
+"#;
+
+const CARGO_TOML: &str = r#"# Don't edit the `bin` list manually! It is updated by `rustlings dev update`
 bin = []
 
 [package]
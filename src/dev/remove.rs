@@ -0,0 +1,335 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{self, read_dir, remove_dir, remove_file},
+    path::Path,
+};
+
+use crate::{
+    cli::{Editor, RemoveArgs},
+    exercise::RunnableExercise,
+    info_file::{exercises_dir, solutions_dir, ExerciseInfo, InfoFile},
+};
+
+use super::update::{
+    cargo_toml_path_and_prefix,
+    editor::{update_editor_file, with_existing_project},
+    update_cargo_toml,
+};
+
+// Whether the line is the header of a table or an array of tables.
+fn is_header(line: &str) -> bool {
+    line.trim_start().starts_with('[')
+}
+
+// Whether the line only contains a comment.
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+// Whether the line is `name = "…"` (or with a literal string) with the given name.
+pub(super) fn is_name_line(line: &str, name: &str) -> bool {
+    line.trim()
+        .strip_prefix("name")
+        .and_then(|rest| rest.trim_start().strip_prefix('='))
+        .is_some_and(|value| {
+            let value = value.trim();
+            value == serde_json::to_string(name).unwrap() || value == format!("'{name}'")
+        })
+}
+
+/// Find the `[[exercises]]` entry of the exercise in the lines of `info.toml`.
+/// Returns the index of its header and the index of the next header (or the number of lines).
+pub(super) fn find_entry(lines: &[&str], name: &str) -> Option<(usize, usize)> {
+    // The indices of the header lines outside of multi-line strings.
+    let mut header_inds = Vec::new();
+    let mut in_multi_line_str = false;
+    for (ind, line) in lines.iter().enumerate() {
+        if !in_multi_line_str && is_header(line) {
+            header_inds.push(ind);
+        }
+        // An odd number of delimiters opens or closes a multi-line string.
+        if (line.matches("\"\"\"").count() + line.matches("'''").count()) % 2 == 1 {
+            in_multi_line_str = !in_multi_line_str;
+        }
+    }
+
+    header_inds
+        .iter()
+        .enumerate()
+        .find_map(|(header_ind_ind, &header_ind)| {
+            if lines[header_ind].trim() != "[[exercises]]" {
+                return None;
+            }
+
+            let next_header_ind = header_inds
+                .get(header_ind_ind + 1)
+                .copied()
+                .unwrap_or(lines.len());
+            lines[header_ind + 1..next_header_ind]
+                .iter()
+                .any(|line| is_name_line(line, name))
+                .then_some((header_ind, next_header_ind))
+        })
+}
+
+/// Remove the `[[exercises]]` entry of the exercise from the content of `info.toml`.
+/// The formatting and comments of all other entries are kept.
+/// Comments directly above the entry's header are removed with it.
+/// Returns `None` if the entry wasn't found.
+fn remove_entry(info_file: &str, name: &str) -> Option<String> {
+    let lines = info_file.split_inclusive('\n').collect::<Vec<_>>();
+    let (header_ind, next_header_ind) = find_entry(&lines, name)?;
+
+    // Keep the comments and blank lines above the next header. They belong to the next entry.
+    let mut end = next_header_ind;
+    while end > header_ind + 1 && (is_comment(lines[end - 1]) || lines[end - 1].trim().is_empty()) {
+        end -= 1;
+    }
+    if end == next_header_ind || next_header_ind == lines.len() {
+        // Without comments for the next entry, remove the blank lines before it too.
+        end = next_header_ind;
+    }
+
+    let mut start = header_ind;
+    while start > 0 && is_comment(lines[start - 1]) {
+        start -= 1;
+    }
+    // Remove the blank line that separated the entry from the previous one.
+    if start > 0 && lines[start - 1].trim().is_empty() {
+        start -= 1;
+    }
+
+    Some(lines[..start].concat() + &lines[end..].concat())
+}
+
+// Delete the file if it exists and print it.
+fn delete_file(path: &str, dry_run: bool) -> Result<()> {
+    if !Path::new(path).exists() {
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("Would delete the file {path}");
+    } else {
+        remove_file(path).with_context(|| format!("Failed to delete the file {path}"))?;
+        println!("Deleted the file {path}");
+    }
+
+    Ok(())
+}
+
+// Delete the directory if it is empty (or would be empty without the deleted files).
+fn delete_empty_dir(dir: &str, deleted_paths: &[String], dry_run: bool) -> Result<()> {
+    let Ok(entries) = read_dir(dir) else {
+        return Ok(());
+    };
+
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read the directory {dir}"))?;
+        let path = entry.path();
+        if !deleted_paths
+            .iter()
+            .any(|deleted| Path::new(deleted) == path)
+        {
+            return Ok(());
+        }
+    }
+
+    if dry_run {
+        println!("Would delete the empty directory {dir}");
+    } else {
+        remove_dir(dir).with_context(|| format!("Failed to delete the directory {dir}"))?;
+        println!("Deleted the empty directory {dir}");
+    }
+
+    Ok(())
+}
+
+fn deleted_paths(exercise_info: &ExerciseInfo) -> Vec<String> {
+    let mut paths = vec![exercise_info.path(), exercise_info.sol_path()];
+    paths.extend(exercise_info.extra_file_paths(exercises_dir()));
+    paths.extend(exercise_info.extra_file_paths(solutions_dir()));
+    paths.extend(exercise_info.additional_file_paths());
+    paths
+}
+
+pub fn remove(args: RemoveArgs) -> Result<()> {
+    if !Path::new("info.toml").exists() {
+        bail!("No `info.toml` file found. Run this command in a project created with `rustlings dev new`");
+    }
+
+    let name = args.name.as_str();
+    let dry_run = args.dry_run;
+    let mut info_file = InfoFile::parse()?;
+
+    let Some(exercise_ind) = info_file
+        .exercises
+        .iter()
+        .position(|exercise_info| exercise_info.name == name)
+    else {
+        bail!("There is no exercise with the name `{name}` in `info.toml`");
+    };
+    // The remaining exercises are used to update `Cargo.toml`.
+    let exercise_info = info_file.exercises.remove(exercise_ind);
+
+    let dependents = info_file
+        .exercises
+        .iter()
+        .filter(|exercise_info| exercise_info.prerequisites.iter().any(|p| p == name))
+        .map(|exercise_info| exercise_info.name.as_str())
+        .collect::<Vec<_>>();
+    if !dependents.is_empty() {
+        bail!(
+            "The exercise `{name}` is a prerequisite of the exercises: {}\nRemove it from their `prerequisites` first",
+            dependents.join(", "),
+        );
+    }
+
+    let content = fs::read_to_string("info.toml").context("Failed to read the file `info.toml`")?;
+    let Some(updated_content) = remove_entry(&content, name) else {
+        bail!("The exercise `{name}` isn't listed in `info.toml` itself. The exercises of packs can only be removed from their pack");
+    };
+
+    // Verify the removal to never break `info.toml`.
+    let updated_exercise_names = toml_edit::de::from_str::<InfoFile>(&updated_content)
+        .ok()
+        .map(|updated_info_file| {
+            updated_info_file
+                .exercises
+                .into_iter()
+                .map(|exercise_info| exercise_info.name)
+                .collect::<Vec<_>>()
+        });
+    let expected_exercise_names = InfoFile::parse_without_packs()?
+        .exercises
+        .into_iter()
+        .map(|exercise_info| exercise_info.name)
+        .filter(|exercise_name| exercise_name != name)
+        .collect::<Vec<_>>();
+    if updated_exercise_names.as_ref() != Some(&expected_exercise_names) {
+        bail!("Failed to remove the exercise `{name}` from `info.toml` automatically. Remove its entry manually");
+    }
+
+    if dry_run {
+        println!("Would remove the exercise `{name}` from `info.toml`");
+    } else {
+        fs::write("info.toml", updated_content).context("Failed to write the file `info.toml`")?;
+        println!("Removed the exercise `{name}` from `info.toml`");
+    }
+
+    let deleted_paths = deleted_paths(&exercise_info);
+    for path in &deleted_paths {
+        delete_file(path, dry_run)?;
+    }
+
+    if let Some(dir) = &exercise_info.dir {
+        // Keep the directories if other exercises have the same one.
+        for base_dir in [exercises_dir(), solutions_dir()] {
+            delete_empty_dir(&format!("{base_dir}/{dir}"), &deleted_paths, dry_run)?;
+        }
+    }
+
+    let (cargo_toml_path, exercise_path_prefix) = cargo_toml_path_and_prefix();
+    update_cargo_toml(
+        &info_file.exercises,
+        cargo_toml_path,
+        exercise_path_prefix,
+        dry_run,
+    )
+    .with_context(|| format!("Failed to update the file `{cargo_toml_path}`"))?;
+    if !dry_run {
+        println!("Updated `{cargo_toml_path}`");
+    }
+
+    let editor = with_existing_project(Editor::None);
+    if let Some(editor_file_path) = editor.path() {
+        update_editor_file(editor, &info_file.exercises, dry_run)
+            .with_context(|| format!("Failed to update the file `{editor_file_path}`"))?;
+        if !dry_run {
+            println!("Updated `{editor_file_path}`");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_removal() {
+        let info_file = r#"format_version = 1
+
+# Intro
+
+[[exercises]]
+name = "intro1"
+hint = """
+[not a header]"""
+
+# The second exercise.
+[[exercises]]
+name = "intro2"
+hint = "…"
+
+# Variables
+[[exercises]]
+name = 'variables1'
+hint = "…"
+"#;
+
+        assert_eq!(
+            remove_entry(info_file, "intro1").unwrap(),
+            r#"format_version = 1
+
+# Intro
+
+# The second exercise.
+[[exercises]]
+name = "intro2"
+hint = "…"
+
+# Variables
+[[exercises]]
+name = 'variables1'
+hint = "…"
+"#,
+        );
+        assert_eq!(
+            remove_entry(info_file, "intro2").unwrap(),
+            r#"format_version = 1
+
+# Intro
+
+[[exercises]]
+name = "intro1"
+hint = """
+[not a header]"""
+
+# Variables
+[[exercises]]
+name = 'variables1'
+hint = "…"
+"#,
+        );
+        assert_eq!(
+            remove_entry(info_file, "variables1").unwrap(),
+            r#"format_version = 1
+
+# Intro
+
+[[exercises]]
+name = "intro1"
+hint = """
+[not a header]"""
+
+# The second exercise.
+[[exercises]]
+name = "intro2"
+hint = "…"
+"#,
+        );
+        assert_eq!(remove_entry(info_file, "intro").as_deref(), None);
+    }
+}
@@ -0,0 +1,272 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    borrow::Cow,
+    fmt::Write as _,
+    fs::{self, rename as rename_file},
+    path::Path,
+};
+
+use crate::{
+    app_state::STATE_FILE_NAME,
+    cli::{Editor, RenameArgs},
+    exercise::RunnableExercise,
+    info_file::{ExerciseInfo, InfoFile},
+};
+
+use super::{
+    remove::{find_entry, is_name_line},
+    scaffold::check_name,
+    update::{
+        cargo_toml_path_and_prefix,
+        editor::{update_editor_file, with_existing_project},
+        update_cargo_toml,
+    },
+};
+
+// Whether the line starts the `prerequisites` array.
+fn is_prerequisites_line(line: &str) -> bool {
+    line.trim_start()
+        .strip_prefix("prerequisites")
+        .is_some_and(|rest| rest.trim_start().starts_with('='))
+}
+
+/// Rename the exercise in the content of `info.toml`.
+/// The `name` of its entry and its mentions in the `prerequisites` of all entries are rewritten.
+/// Returns `None` if the entry wasn't found.
+fn rename_entry(info_file: &str, old: &str, new: &str) -> Option<String> {
+    let lines = info_file.split_inclusive('\n').collect::<Vec<_>>();
+    let (header_ind, next_header_ind) = find_entry(&lines, old)?;
+
+    let mut updated = String::with_capacity(info_file.len() + new.len());
+    let mut renamed = false;
+    let mut in_prerequisites = false;
+    for (ind, line) in lines.into_iter().enumerate() {
+        if !renamed && (header_ind..next_header_ind).contains(&ind) && is_name_line(line, old) {
+            renamed = true;
+            let indentation = &line[..line.len() - line.trim_start().len()];
+            let line_ending = &line[line.trim_end().len()..];
+            write!(updated, "{indentation}name = \"{new}\"{line_ending}").unwrap();
+            continue;
+        }
+
+        in_prerequisites = in_prerequisites || is_prerequisites_line(line);
+        if in_prerequisites {
+            // Names can't contain quotes or escape sequences.
+            updated.push_str(
+                &line
+                    .replace(&format!("\"{old}\""), &format!("\"{new}\""))
+                    .replace(&format!("'{old}'"), &format!("'{new}'")),
+            );
+            in_prerequisites = !line.contains(']');
+        } else {
+            updated.push_str(line);
+        }
+    }
+
+    Some(updated)
+}
+
+/// Rename the exercise in the content of the state file.
+/// See `AppState::write` for the file's format.
+fn rename_in_state_file(state_file: &str, old: &str, new: &str) -> String {
+    state_file
+        .split('\n')
+        .enumerate()
+        .map(|(ind, line)| {
+            // Skip the comment in the first line.
+            if ind == 0 {
+                return Cow::Borrowed(line);
+            }
+
+            if line == old {
+                return Cow::Borrowed(new);
+            }
+
+            // The name is the first field of a progress line.
+            match line
+                .strip_prefix(old)
+                .and_then(|rest| rest.strip_prefix(' '))
+            {
+                Some(fields) => Cow::Owned(format!("{new} {fields}")),
+                None => Cow::Borrowed(line),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// The names and prerequisites of the exercises to verify the renaming.
+fn names_and_prerequisites(exercise_infos: Vec<ExerciseInfo>) -> Vec<(String, Vec<String>)> {
+    exercise_infos
+        .into_iter()
+        .map(|exercise_info| (exercise_info.name, exercise_info.prerequisites))
+        .collect()
+}
+
+pub fn rename(args: RenameArgs) -> Result<()> {
+    if !Path::new("info.toml").exists() {
+        bail!("No `info.toml` file found. Run this command in a project created with `rustlings dev new`");
+    }
+
+    let old = args.old.as_str();
+    let new = args.new.as_str();
+    check_name(new)?;
+    let mut info_file = InfoFile::parse()?;
+
+    if info_file
+        .exercises
+        .iter()
+        .any(|exercise_info| exercise_info.name == new)
+    {
+        bail!("An exercise with the name `{new}` already exists in `info.toml`");
+    }
+    let Some(exercise_info) = info_file
+        .exercises
+        .iter_mut()
+        .find(|exercise_info| exercise_info.name == old)
+    else {
+        bail!("There is no exercise with the name `{old}` in `info.toml`");
+    };
+
+    let old_paths = [exercise_info.path(), exercise_info.sol_path()];
+    exercise_info.name = new.to_string();
+    let new_paths = [exercise_info.path(), exercise_info.sol_path()];
+    for path in &new_paths {
+        if Path::new(path).exists() {
+            bail!("The file {path} already exists");
+        }
+    }
+
+    let content = fs::read_to_string("info.toml").context("Failed to read the file `info.toml`")?;
+    let Some(updated_content) = rename_entry(&content, old, new) else {
+        bail!("The exercise `{old}` isn't listed in `info.toml` itself. The exercises of packs can only be renamed in their pack");
+    };
+
+    // Verify the renaming to never break `info.toml`.
+    let rename = |name: String| if name == old { new.to_string() } else { name };
+    let updated_exercises = toml_edit::de::from_str::<InfoFile>(&updated_content)
+        .ok()
+        .map(|updated_info_file| names_and_prerequisites(updated_info_file.exercises));
+    let expected_exercises = names_and_prerequisites(InfoFile::parse_without_packs()?.exercises)
+        .into_iter()
+        .map(|(name, prerequisites)| {
+            (
+                rename(name),
+                prerequisites.into_iter().map(rename).collect(),
+            )
+        })
+        .collect::<Vec<_>>();
+    if updated_exercises.as_ref() != Some(&expected_exercises) {
+        bail!("Failed to rename the exercise `{old}` in `info.toml` automatically. Rename it manually");
+    }
+
+    fs::write("info.toml", updated_content).context("Failed to write the file `info.toml`")?;
+    println!("Updated `info.toml`");
+
+    for (old_path, new_path) in old_paths.iter().zip(&new_paths) {
+        // The solution might not exist yet.
+        if !Path::new(old_path).exists() {
+            continue;
+        }
+
+        rename_file(old_path, new_path)
+            .with_context(|| format!("Failed to rename the file {old_path} to {new_path}"))?;
+        println!("Renamed the file {old_path} to {new_path}");
+    }
+
+    let (cargo_toml_path, exercise_path_prefix) = cargo_toml_path_and_prefix();
+    update_cargo_toml(
+        &info_file.exercises,
+        cargo_toml_path,
+        exercise_path_prefix,
+        false,
+    )
+    .with_context(|| format!("Failed to update the file `{cargo_toml_path}`"))?;
+    println!("Updated `{cargo_toml_path}`");
+
+    let editor = with_existing_project(Editor::None);
+    if let Some(editor_file_path) = editor.path() {
+        update_editor_file(editor, &info_file.exercises, false)
+            .with_context(|| format!("Failed to update the file `{editor_file_path}`"))?;
+        println!("Updated `{editor_file_path}`");
+    }
+
+    if args.keep_progress {
+        if let Ok(state_file) = fs::read_to_string(STATE_FILE_NAME) {
+            let updated_state_file = rename_in_state_file(&state_file, old, new);
+            if updated_state_file != state_file {
+                fs::write(STATE_FILE_NAME, updated_state_file)
+                    .with_context(|| format!("Failed to write the state file {STATE_FILE_NAME}"))?;
+                println!("Updated the state file {STATE_FILE_NAME}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_renaming() {
+        let info_file = r#"format_version = 1
+
+[[exercises]]
+name = "intro1"
+hint = """
+name = "intro1\""""
+
+[[exercises]]
+name = 'intro2'
+prerequisites = ["intro1"]
+hint = "…"
+
+[[exercises]]
+name = "intro3"
+prerequisites = [
+  'intro2',
+  "intro1",
+]
+hint = "…"
+"#;
+
+        assert_eq!(
+            rename_entry(info_file, "intro1", "intro0").unwrap(),
+            r#"format_version = 1
+
+[[exercises]]
+name = "intro0"
+hint = """
+name = "intro1\""""
+
+[[exercises]]
+name = 'intro2'
+prerequisites = ["intro0"]
+hint = "…"
+
+[[exercises]]
+name = "intro3"
+prerequisites = [
+  'intro2',
+  "intro0",
+]
+hint = "…"
+"#,
+        );
+        assert_eq!(rename_entry(info_file, "intro", "intro0").as_deref(), None);
+    }
+
+    #[test]
+    fn state_file_renaming() {
+        assert_eq!(
+            rename_in_state_file(
+                "# intro1\n\nintro1\n\nintro2\nintro1\n\n1700000000\n\nintro1 42 1700000000\nintro10 7",
+                "intro1",
+                "intro0"
+            ),
+            "# intro1\n\nintro0\n\nintro2\nintro0\n\n1700000000\n\nintro0 42 1700000000\nintro10 7",
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{create_dir_all, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use crate::{
+    cli::{Editor, ScaffoldArgs},
+    info_file::{exercises_dir, solutions_dir, InfoFile},
+};
+
+use super::{
+    check::{
+        forbidden_char, ALLOWED_NAME_CHARS_MSG, MAX_DIFFICULTY, MAX_EXERCISE_NAME_LEN,
+        MIN_DIFFICULTY,
+    },
+    update,
+};
+
+// Format a string as a TOML basic string with quotes and escaping.
+// The escape sequences of JSON strings are a subset of those of TOML basic strings.
+fn toml_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+// Create the file and its parent directories. Fail if the file already exists.
+fn create_file(path: &str, content: &str) -> Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        create_dir_all(parent)
+            .with_context(|| format!("Failed to create the directory {}", parent.display()))?;
+    }
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .with_context(|| format!("Failed to create the file {path}"))
+}
+
+/// Fail if the name isn't allowed as an exercise name.
+pub(super) fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_EXERCISE_NAME_LEN {
+        bail!("The exercise name must have between 1 and {MAX_EXERCISE_NAME_LEN} characters");
+    }
+    if let Some(c) = forbidden_char(name) {
+        bail!("Char `{c}` in the exercise name `{name}` is not allowed. {ALLOWED_NAME_CHARS_MSG}");
+    }
+
+    Ok(())
+}
+
+pub fn scaffold(args: ScaffoldArgs) -> Result<()> {
+    if !Path::new("info.toml").exists() {
+        bail!("No `info.toml` file found. Run this command in a project created with `rustlings dev new`");
+    }
+
+    let info_file = InfoFile::parse()?;
+    let name = args.name.as_str();
+
+    check_name(name)?;
+    if let Some(dir) = &args.dir {
+        if dir.is_empty() {
+            bail!("The exercise directory name can't be empty");
+        }
+        if let Some(c) = forbidden_char(dir) {
+            bail!(
+                "Char `{c}` in the exercise dir `{dir}` is not allowed. {ALLOWED_NAME_CHARS_MSG}"
+            );
+        }
+    }
+    if let Some(difficulty) = args.difficulty {
+        if !(MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&difficulty) {
+            bail!("The difficulty must be in the range {MIN_DIFFICULTY}-{MAX_DIFFICULTY}");
+        }
+    }
+    if info_file
+        .exercises
+        .iter()
+        .any(|exercise_info| exercise_info.name == name)
+    {
+        bail!("An exercise with the name `{name}` already exists in `info.toml`");
+    }
+
+    let rel_path = match &args.dir {
+        Some(dir) => format!("{dir}/{name}.rs"),
+        None => format!("{name}.rs"),
+    };
+    let exercise_path = format!("{}/{rel_path}", exercises_dir());
+    let sol_path = format!("{}/{rel_path}", solutions_dir());
+
+    for path in [&exercise_path, &sol_path] {
+        if Path::new(path).exists() {
+            bail!("The file {path} already exists");
+        }
+    }
+
+    let mut entry = format!("\n[[exercises]]\nname = {}\n", toml_str(name));
+    if let Some(dir) = &args.dir {
+        entry.push_str(&format!("dir = {}\n", toml_str(dir)));
+    }
+    if !args.test {
+        entry.push_str("test = false\n");
+    }
+    if let Some(difficulty) = args.difficulty {
+        entry.push_str(&format!("difficulty = {difficulty}\n"));
+    }
+    entry.push_str(&format!("hint = {}\n", toml_str(&args.hint)));
+
+    let (exercise_template, solution_template) = if args.test {
+        (TEST_EXERCISE_TEMPLATE, TEST_SOLUTION_TEMPLATE)
+    } else {
+        (EXERCISE_TEMPLATE, SOLUTION_TEMPLATE)
+    };
+    create_file(&exercise_path, exercise_template)?;
+    println!("Created the file {exercise_path}");
+    create_file(&sol_path, solution_template)?;
+    println!("Created the file {sol_path}");
+
+    // Appending keeps the formatting and comments of the existing file.
+    OpenOptions::new()
+        .append(true)
+        .open("info.toml")
+        .and_then(|mut file| file.write_all(entry.as_bytes()))
+        .context("Failed to append the exercise to `info.toml`")?;
+    println!("Added the exercise `{name}` to `info.toml`");
+
+    // `update` parses the updated `info.toml` file.
+    update::update(false, false, Editor::None)?;
+
+    println!(
+        "
+Fill in the task{} in the exercise file, the solution in the solution file
+and the hint in `info.toml`. Then run `rustlings dev check`",
+        if args.test { " and tests" } else { "" },
+    );
+
+    Ok(())
+}
+
+// The exercise panics with an error message until it is solved.
+const EXERCISE_TEMPLATE: &str = r#"// TODO: Describe the task of the exercise.
+fn answer() -> u32 {
+    0
+}
+
+fn main() {
+    let answer = answer();
+    assert_eq!(answer, 42, "The answer is wrong");
+    println!("The answer is {answer}");
+}
+"#;
+
+const SOLUTION_TEMPLATE: &str = r#"fn answer() -> u32 {
+    42
+}
+
+fn main() {
+    let answer = answer();
+    assert_eq!(answer, 42, "The answer is wrong");
+    println!("The answer is {answer}");
+}
+"#;
+
+const TEST_EXERCISE_TEMPLATE: &str = r#"// TODO: Describe the task of the exercise.
+fn answer() -> u32 {
+    0
+}
+
+fn main() {
+    println!("The answer is {}", answer());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_is_correct() {
+        assert_eq!(answer(), 42);
+    }
+}
+"#;
+
+const TEST_SOLUTION_TEMPLATE: &str = r#"fn answer() -> u32 {
+    42
+}
+
+fn main() {
+    println!("The answer is {}", answer());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_is_correct() {
+        assert_eq!(answer(), 42);
+    }
+}
+"#;
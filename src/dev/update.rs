@@ -1,44 +1,198 @@
-use anyhow::{Context, Result};
-use std::fs;
+use anyhow::{bail, Context, Result};
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Stdio},
+};
+use tempfile::NamedTempFile;
 
 use crate::{
     cargo_toml::updated_cargo_toml,
+    cli::Editor,
+    diff::unified_diff,
+    exercise::RunnableExercise,
     info_file::{ExerciseInfo, InfoFile},
 };
 
-// Update the `Cargo.toml` file.
-fn update_cargo_toml(
+use self::editor::{update_editor_file, with_existing_project};
+
+pub mod editor;
+
+/// The path of the `Cargo.toml` file and the prefix of the exercise paths in it.
+/// A hack to make `cargo run -- dev …` work when developing Rustlings.
+pub fn cargo_toml_path_and_prefix() -> (&'static str, &'static [u8]) {
+    if cfg!(debug_assertions) {
+        ("dev/Cargo.toml", b"../")
+    } else {
+        ("Cargo.toml", b"")
+    }
+}
+
+/// Update the `Cargo.toml` file and return `true` if it was outdated.
+/// With `dry_run`, only print the diff to the updated file without writing it.
+pub(super) fn update_cargo_toml(
     exercise_infos: &[ExerciseInfo],
     cargo_toml_path: &str,
     exercise_path_prefix: &[u8],
-) -> Result<()> {
+    dry_run: bool,
+) -> Result<bool> {
     let current_cargo_toml = fs::read_to_string(cargo_toml_path)
         .with_context(|| format!("Failed to read the file `{cargo_toml_path}`"))?;
 
     let updated_cargo_toml =
         updated_cargo_toml(exercise_infos, &current_cargo_toml, exercise_path_prefix)?;
 
+    let outdated = updated_cargo_toml != current_cargo_toml.as_bytes();
+    if dry_run {
+        if outdated {
+            let diff = unified_diff(
+                &current_cargo_toml,
+                &String::from_utf8_lossy(&updated_cargo_toml),
+                cargo_toml_path,
+                &format!("{cargo_toml_path} (updated)"),
+            );
+            print!("{}", diff.unwrap_or_default());
+        }
+
+        return Ok(outdated);
+    }
+
     fs::write(cargo_toml_path, updated_cargo_toml)
         .context("Failed to write the `Cargo.toml` file")?;
 
+    Ok(outdated)
+}
+
+// Build the solutions in the workspace of the `Cargo.toml` file.
+// The exercises aren't built because most of them don't compile before being solved.
+// Cargo still parses all targets in the manifest.
+// Without any solution, only the manifest is validated with `cargo metadata`
+// because `cargo build` without `--bin` would build the unsolved exercises.
+fn build_workspace(exercise_infos: &[ExerciseInfo], cargo_toml_path: &str) -> Result<()> {
+    let sol_bins = exercise_infos
+        .iter()
+        .filter(|exercise_info| Path::new(&exercise_info.sol_path()).exists())
+        .map(|exercise_info| format!("{}_sol", exercise_info.name))
+        .collect::<Vec<_>>();
+
+    let mut cmd = Command::new("cargo");
+    let description = if sol_bins.is_empty() {
+        cmd.arg("metadata").arg("-q").arg("--no-deps");
+        "`cargo metadata --no-deps`"
+    } else {
+        cmd.arg("build").arg("-q").arg("--workspace");
+        for sol_bin in &sol_bins {
+            cmd.arg("--bin").arg(sol_bin);
+        }
+        "`cargo build --workspace`"
+    };
+    cmd.arg("--manifest-path").arg(cargo_toml_path);
+
+    let output = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run {description}"))?;
+    if !output.status.success() {
+        bail!(
+            "{description} failed:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end(),
+        );
+    }
+
     Ok(())
 }
 
-pub fn update() -> Result<()> {
+// Update the `Cargo.toml` file and build the workspace.
+// The original file is restored from a backup in a temporary file if the update or the build fails.
+fn update_cargo_toml_validated(
+    exercise_infos: &[ExerciseInfo],
+    cargo_toml_path: &str,
+    exercise_path_prefix: &[u8],
+) -> Result<bool> {
+    let backup = NamedTempFile::new().context("Failed to create a temporary backup file")?;
+    fs::copy(cargo_toml_path, backup.path())
+        .with_context(|| format!("Failed to back up the file `{cargo_toml_path}`"))?;
+
+    let res = update_cargo_toml(exercise_infos, cargo_toml_path, exercise_path_prefix, false)
+        .and_then(|outdated| {
+            build_workspace(exercise_infos, cargo_toml_path)?;
+            Ok(outdated)
+        });
+
+    if res.is_err() {
+        // Copying instead of renaming because the temporary directory can be on another file system.
+        fs::copy(backup.path(), cargo_toml_path).with_context(|| {
+            format!(
+                "Failed to restore the file `{cargo_toml_path}` from the backup `{}`",
+                backup.path().display(),
+            )
+        })?;
+        return res.context("The original file was restored");
+    }
+
+    res
+}
+
+/// Fails with `dry_run` if the `Cargo.toml` file or the editor support file is outdated.
+/// With `validate`, the workspace is built after updating `Cargo.toml`
+/// and the original file is restored if the build fails.
+pub fn update(dry_run: bool, validate: bool, editor: Editor) -> Result<()> {
     let info_file = InfoFile::parse()?;
 
-    if cfg!(debug_assertions) {
-        // A hack to make `cargo run -- dev update` work when developing Rustlings.
-        update_cargo_toml(&info_file.exercises, "dev/Cargo.toml", b"../")
-            .context("Failed to update the file `dev/Cargo.toml`")?;
+    let (cargo_toml_path, exercise_path_prefix) = cargo_toml_path_and_prefix();
 
-        println!("Updated `dev/Cargo.toml`");
+    let mut outdated_paths = Vec::new();
+    let outdated = if validate && !dry_run {
+        update_cargo_toml_validated(&info_file.exercises, cargo_toml_path, exercise_path_prefix)
     } else {
-        update_cargo_toml(&info_file.exercises, "Cargo.toml", &[])
-            .context("Failed to update the file `Cargo.toml`")?;
+        update_cargo_toml(
+            &info_file.exercises,
+            cargo_toml_path,
+            exercise_path_prefix,
+            dry_run,
+        )
+    };
+    if outdated.with_context(|| format!("Failed to update the file `{cargo_toml_path}`"))? {
+        outdated_paths.push(cargo_toml_path);
+    }
+
+    let editor = with_existing_project(editor);
+    if let Some(editor_file_path) = editor.path() {
+        if update_editor_file(editor, &info_file.exercises, dry_run)
+            .with_context(|| format!("Failed to update the file `{editor_file_path}`"))?
+        {
+            outdated_paths.push(editor_file_path);
+        }
+    }
 
-        println!("Updated `Cargo.toml`");
+    let paths = [Some(cargo_toml_path), editor.path()];
+    let paths = paths.iter().flatten();
+    if !dry_run {
+        for path in paths {
+            println!("Updated `{path}`");
+        }
+    } else if !outdated_paths.is_empty() {
+        bail!(
+            "{} outdated. Run `rustlings dev update` to apply the diff above",
+            files_list(&outdated_paths),
+        );
+    } else {
+        println!(
+            "{} up to date",
+            files_list(&paths.copied().collect::<Vec<_>>())
+        );
     }
 
     Ok(())
 }
+
+// "`a` is" or "`a` and `b` are"
+fn files_list(paths: &[&str]) -> String {
+    let files = paths
+        .iter()
+        .map(|path| format!("`{path}`"))
+        .collect::<Vec<_>>()
+        .join(" and ");
+    format!("{files} {}", if paths.len() == 1 { "is" } else { "are" })
+}
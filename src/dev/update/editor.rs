@@ -0,0 +1,202 @@
+use anyhow::{Context, Error, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{
+    fs::{self, create_dir_all},
+    io::ErrorKind,
+    path::Path,
+};
+
+use crate::{
+    cargo_toml::{package_edition, DEFAULT_EDITION},
+    cli::Editor,
+    diff::unified_diff,
+    exercise::RunnableExercise,
+    info_file::ExerciseInfo,
+};
+
+use super::cargo_toml_path_and_prefix;
+
+pub(in crate::dev) const RUST_PROJECT_JSON_PATH: &str = "rust-project.json";
+const VS_CODE_SETTINGS_PATH: &str = ".vscode/settings.json";
+const LINKED_PROJECTS_KEY: &str = "rust-analyzer.linkedProjects";
+
+impl Editor {
+    pub(in crate::dev) fn path(self) -> Option<&'static str> {
+        match self {
+            Self::Vscode => Some(VS_CODE_SETTINGS_PATH),
+            Self::RaProject => Some(RUST_PROJECT_JSON_PATH),
+            Self::None => None,
+        }
+    }
+}
+
+/// Use `RaProject` instead of `None` if `rust-project.json` exists
+/// because `dev check` verifies that it is up to date.
+pub(in crate::dev) fn with_existing_project(editor: Editor) -> Editor {
+    if editor == Editor::None && Path::new(RUST_PROJECT_JSON_PATH).exists() {
+        Editor::RaProject
+    } else {
+        editor
+    }
+}
+
+// A crate in `rust-project.json`.
+// See https://rust-analyzer.github.io/book/non_cargo_based_projects.html
+#[derive(Serialize)]
+struct Crate<'a> {
+    display_name: String,
+    root_module: String,
+    edition: &'a str,
+    deps: &'static [&'static str],
+    cfg: &'static [&'static str],
+    is_workspace_member: bool,
+}
+
+#[derive(Serialize)]
+struct RustProject<'a> {
+    crates: Vec<Crate<'a>>,
+}
+
+/// Generate the content of `rust-project.json`.
+/// Like the `bin` list in `Cargo.toml`, it has a crate for every exercise and existing solution.
+/// The paths are relative to the file.
+pub(in crate::dev) fn rust_project_json(exercise_infos: &[ExerciseInfo]) -> String {
+    let (cargo_toml_path, _) = cargo_toml_path_and_prefix();
+    let edition = fs::read_to_string(cargo_toml_path)
+        .ok()
+        .and_then(|cargo_toml| package_edition(&cargo_toml));
+    let edition = edition.as_deref().unwrap_or(DEFAULT_EDITION);
+
+    let mut crates = Vec::with_capacity(2 * exercise_infos.len());
+    for exercise_info in exercise_infos {
+        // Analyze the tests of exercises that are checked by tests.
+        let cfg: &[&str] = if exercise_info.test() { &["test"] } else { &[] };
+
+        crates.push(Crate {
+            display_name: exercise_info.name.clone(),
+            root_module: exercise_info.path(),
+            edition: exercise_info.edition.as_deref().unwrap_or(edition),
+            deps: &[],
+            cfg,
+            is_workspace_member: true,
+        });
+
+        let sol_path = exercise_info.sol_path();
+        if Path::new(&sol_path).exists() {
+            crates.push(Crate {
+                display_name: format!("{}_sol", exercise_info.name),
+                root_module: sol_path,
+                edition: exercise_info.edition.as_deref().unwrap_or(edition),
+                deps: &[],
+                cfg,
+                is_workspace_member: true,
+            });
+        }
+    }
+
+    let mut json = serde_json::to_string_pretty(&RustProject { crates }).unwrap();
+    json.push('\n');
+    json
+}
+
+// Return the content of `.vscode/settings.json` with rust-analyzer linked to `Cargo.toml`.
+// Other settings are kept. The current content is returned unchanged if it is already linked.
+fn vs_code_settings(current: Option<&str>) -> Result<String> {
+    let (cargo_toml_path, _) = cargo_toml_path_and_prefix();
+
+    let mut settings = match current {
+        Some(current) => serde_json::from_str::<Map<String, Value>>(current).with_context(|| {
+            format!("Failed to parse the file `{VS_CODE_SETTINGS_PATH}`. Add `\"{LINKED_PROJECTS_KEY}\": [\"{cargo_toml_path}\"]` to it manually")
+        })?,
+        None => Map::new(),
+    };
+
+    let linked_projects = Value::from(vec![cargo_toml_path]);
+    if let Some(current) = current {
+        if settings.get(LINKED_PROJECTS_KEY) == Some(&linked_projects) {
+            return Ok(current.to_string());
+        }
+    }
+
+    settings.insert(LINKED_PROJECTS_KEY.to_string(), linked_projects);
+    let mut json = serde_json::to_string_pretty(&settings).unwrap();
+    json.push('\n');
+    Ok(json)
+}
+
+// Read the file. `None` if it doesn't exist.
+fn read_if_exists(path: &str) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::from(e).context(format!("Failed to read the file `{path}`"))),
+    }
+}
+
+/// Generate the editor support file and return `true` if it was outdated.
+/// With `dry_run`, only print the diff to the generated file without writing it.
+pub(in crate::dev) fn update_editor_file(
+    editor: Editor,
+    exercise_infos: &[ExerciseInfo],
+    dry_run: bool,
+) -> Result<bool> {
+    let Some(path) = editor.path() else {
+        return Ok(false);
+    };
+
+    let current = read_if_exists(path)?;
+    let updated = if editor == Editor::Vscode {
+        vs_code_settings(current.as_deref())?
+    } else {
+        rust_project_json(exercise_infos)
+    };
+
+    let outdated = current.as_deref() != Some(updated.as_str());
+    if dry_run {
+        if outdated {
+            let diff = unified_diff(
+                current.as_deref().unwrap_or_default(),
+                &updated,
+                path,
+                &format!("{path} (updated)"),
+            );
+            print!("{}", diff.unwrap_or_default());
+        }
+
+        return Ok(outdated);
+    }
+
+    if outdated {
+        if let Some(parent) = Path::new(path).parent() {
+            create_dir_all(parent)
+                .with_context(|| format!("Failed to create the directory {}", parent.display()))?;
+        }
+        fs::write(path, updated).with_context(|| format!("Failed to write the file `{path}`"))?;
+    }
+
+    Ok(outdated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings() {
+        let (cargo_toml_path, _) = cargo_toml_path_and_prefix();
+        let linked =
+            format!("{{\n  \"{LINKED_PROJECTS_KEY}\": [\n    \"{cargo_toml_path}\"\n  ]\n}}\n");
+
+        assert_eq!(vs_code_settings(None).unwrap(), linked);
+        // Already linked with a different formatting.
+        let current = format!("{{\"{LINKED_PROJECTS_KEY}\": [\"{cargo_toml_path}\"]}}");
+        assert_eq!(vs_code_settings(Some(&current)).unwrap(), current);
+        assert_eq!(
+            vs_code_settings(Some("{\"editor.tabSize\": 4}")).unwrap(),
+            format!("{{\n  \"editor.tabSize\": 4,\n  {}", &linked[4..]),
+        );
+        // Comments aren't supported.
+        assert!(vs_code_settings(Some("// Comment\n{}")).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt::Write;
+
+// The number of unchanged lines around changes.
+const CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+impl Op<'_> {
+    fn is_change(&self) -> bool {
+        !matches!(self, Self::Equal(_))
+    }
+}
+
+// Compute the line operations to turn `old` into `new` using the longest common subsequence.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    // Skip the common prefix and suffix to keep the table small.
+    let prefix_len = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix_len = old[prefix_len..]
+        .iter()
+        .rev()
+        .zip(new[prefix_len..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix_len..old.len() - suffix_len];
+    let new_mid = &new[prefix_len..new.len() - suffix_len];
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    // `lcs[i * (m + 1) + j]` is the length of the LCS of `old_mid[i..]` and `new_mid[j..]`.
+    let mut lcs = vec![0_u32; (n + 1) * (m + 1)];
+    let ind = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[ind(i, j)] = if old_mid[i] == new_mid[j] {
+                lcs[ind(i + 1, j + 1)] + 1
+            } else {
+                lcs[ind(i + 1, j)].max(lcs[ind(i, j + 1)])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    ops.extend(old[..prefix_len].iter().map(|line| Op::Equal(line)));
+
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_mid[i] == new_mid[j] {
+            ops.push(Op::Equal(old_mid[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[ind(i + 1, j)] >= lcs[ind(i, j + 1)] {
+            ops.push(Op::Delete(old_mid[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(new_mid[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old_mid[i..].iter().map(|line| Op::Delete(line)));
+    ops.extend(new_mid[j..].iter().map(|line| Op::Insert(line)));
+
+    ops.extend(
+        old[old.len() - suffix_len..]
+            .iter()
+            .map(|line| Op::Equal(line)),
+    );
+    ops
+}
+
+/// Return the unified diff between `old` and `new` or `None` if they are equal.
+/// A missing newline at the end of a file is a difference.
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> Option<String> {
+    // The lines keep their newline to detect a change of the newline at the end.
+    let old_lines = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new.split_inclusive('\n').collect::<Vec<_>>();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut change_inds = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| op.is_change())
+        .map(|(ind, _)| ind)
+        .peekable();
+    change_inds.peek()?;
+
+    // The line numbers in the old and new file before every operation, starting at 0.
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_pos, mut new_pos) = (0, 0);
+    for op in &ops {
+        positions.push((old_pos, new_pos));
+        match op {
+            Op::Equal(_) => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            Op::Delete(_) => old_pos += 1,
+            Op::Insert(_) => new_pos += 1,
+        }
+    }
+    positions.push((old_pos, new_pos));
+
+    let mut diff = format!("--- {old_name}\n+++ {new_name}\n");
+    while let Some(first_change_ind) = change_inds.next() {
+        let start = first_change_ind.saturating_sub(CONTEXT);
+        let mut last_change_ind = first_change_ind;
+        // Merge changes with overlapping context into one hunk.
+        while let Some(&next_change_ind) = change_inds.peek() {
+            if next_change_ind - last_change_ind > 2 * CONTEXT + 1 {
+                break;
+            }
+            last_change_ind = next_change_ind;
+            change_inds.next();
+        }
+        let end = (last_change_ind + 1 + CONTEXT).min(ops.len());
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        let (old_len, new_len) = (old_end - old_start, new_end - new_start);
+        // An empty range starts at the line before it.
+        let old_start = if old_len == 0 {
+            old_start
+        } else {
+            old_start + 1
+        };
+        let new_start = if new_len == 0 {
+            new_start
+        } else {
+            new_start + 1
+        };
+        writeln!(diff, "@@ -{old_start},{old_len} +{new_start},{new_len} @@").unwrap();
+
+        for op in &ops[start..end] {
+            let (prefix, line) = match op {
+                Op::Equal(line) => (' ', line),
+                Op::Delete(line) => ('-', line),
+                Op::Insert(line) => ('+', line),
+            };
+            diff.push(prefix);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    Some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new"), None);
+
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n";
+        assert_eq!(
+            unified_diff(old, new, "old", "new").unwrap(),
+            "--- old
++++ new
+@@ -2,7 +2,7 @@
+ 2
+ 3
+ 4
+-5
++five
+ 6
+ 7
+ 8
+@@ -13,3 +13,4 @@
+ 13
+ 14
+ 15
++16
+",
+        );
+
+        // Close changes are merged into one hunk.
+        assert_eq!(
+            unified_diff("a\nb\nc\n", "b\nc\nd\n", "old", "new").unwrap(),
+            "--- old
++++ new
+@@ -1,3 +1,3 @@
+-a
+ b
+ c
++d
+",
+        );
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(unified_diff("", "", "old", "new"), None);
+        assert_eq!(
+            unified_diff("", "a\n", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -0,0 +1,1 @@\n+a\n",
+        );
+        assert_eq!(
+            unified_diff("a\n", "", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -1,1 +0,0 @@\n-a\n",
+        );
+
+        // Only the newline at the end differs.
+        assert_eq!(
+            unified_diff("a\nb", "a\nb\n", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+b\n",
+        );
+        assert_eq!(
+            unified_diff("a\n", "b", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -1,1 +1,1 @@\n-a\n+b\n\\ No newline at end of file\n",
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use anyhow::Result;
+use crossterm::{
+    style::{Color, ResetColor, SetForegroundColor},
+    QueueableCommand,
+};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, StdoutLock, Write},
+    path::Path,
+    process::{self, Command, ExitCode, Stdio},
+};
+
+use crate::info_file::{exercises_dir, InfoFile};
+
+// Compiling the exercises needs space in the `target/` directory.
+const MIN_FREE_DISK_SPACE_MB: u64 = 500;
+const RUSTUP_ADVICE: &str = "Install Rust using rustup: https://www.rust-lang.org/tools/install
+If it is already installed, make sure that `~/.cargo/bin` is in your `PATH` environment variable";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+struct Report<'a, 'lock> {
+    stdout: &'a mut StdoutLock<'lock>,
+    n_failed: usize,
+}
+
+impl Report<'_, '_> {
+    // Print the result of a check. The advice is printed below it unless the check passed.
+    fn add(&mut self, status: Status, msg: &str, advice: &str) -> io::Result<()> {
+        let (color, label) = match status {
+            Status::Ok => (Color::Green, "[OK]  "),
+            Status::Warn => (Color::Yellow, "[WARN]"),
+            Status::Fail => {
+                self.n_failed += 1;
+                (Color::Red, "[FAIL]")
+            }
+        };
+
+        self.stdout.queue(SetForegroundColor(color))?;
+        self.stdout.write_all(label.as_bytes())?;
+        self.stdout.queue(ResetColor)?;
+        writeln!(self.stdout, " {msg}")?;
+
+        if status != Status::Ok {
+            for line in advice.lines() {
+                writeln!(self.stdout, "       {line}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Parse a version like `1.80`, `1.82.0` or `1.84.0-nightly` into its numeric components.
+fn parse_version(version: &str) -> Option<[u32; 3]> {
+    let version = version.split(['-', '+']).next()?;
+    let mut components = version.split('.').map(str::parse::<u32>);
+
+    let major = components.next()?.ok()?;
+    let minor = components.next()?.ok()?;
+    let patch = match components.next() {
+        Some(patch) => patch.ok()?,
+        None => 0,
+    };
+    if components.next().is_some() {
+        return None;
+    }
+
+    Some([major, minor, patch])
+}
+
+// Run the program with `--version` and return the first line of its output.
+// `None` if it isn't installed or fails.
+fn version_line(program: &str) -> Option<String> {
+    let output = Command::new(program)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()?
+        .lines()
+        .next()
+        .map(String::from)
+}
+
+// Uses `df` which isn't available on Windows.
+fn free_disk_space_mb() -> Option<u64> {
+    let output = Command::new("df")
+        .arg("-Pk")
+        .arg(".")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // The POSIX format: `Filesystem 1024-blocks Used Available Capacity Mounted on`
+    let available_kb = String::from_utf8(output.stdout)
+        .ok()?
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse::<u64>()
+        .ok()?;
+
+    Some(available_kb / 1024)
+}
+
+fn check_rustc(report: &mut Report, msrv: &str) -> io::Result<()> {
+    let Some(line) = version_line("rustc") else {
+        return report.add(Status::Fail, "`rustc` wasn't found", RUSTUP_ADVICE);
+    };
+
+    // The line has the form `rustc 1.82.0 (f6e511eec 2024-10-15)`.
+    let version = line.split_whitespace().nth(1).unwrap_or_default();
+    match (parse_version(version), parse_version(msrv)) {
+        (Some(version), Some(min_version)) if version < min_version => report.add(
+            Status::Fail,
+            &format!("{line} is older than the minimum supported Rust version {msrv}"),
+            "Update Rust by running `rustup update`",
+        ),
+        (Some(_), _) => report.add(Status::Ok, &line, ""),
+        (None, _) => report.add(
+            Status::Warn,
+            &format!("Failed to parse the version of `{line}`"),
+            &format!("Make sure that the Rust version is at least {msrv}"),
+        ),
+    }
+}
+
+fn check_tool(report: &mut Report, program: &str, advice: &str) -> io::Result<()> {
+    match version_line(program) {
+        Some(line) => report.add(Status::Ok, &line, ""),
+        None => report.add(Status::Fail, &format!("`{program}` wasn't found"), advice),
+    }
+}
+
+fn check_writable(report: &mut Report) -> io::Result<()> {
+    let path = format!(".rustlings-doctor-{}", process::id());
+    let res = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(b"rustlings"));
+    let _ = fs::remove_file(&path);
+
+    match res {
+        Ok(()) => report.add(Status::Ok, "The current directory is writable", ""),
+        Err(e) => report.add(
+            Status::Fail,
+            &format!("Failed to write a file in the current directory: {e}"),
+            "Run Rustlings in a directory that you have write permission for",
+        ),
+    }
+}
+
+fn check_disk_space(report: &mut Report) -> io::Result<()> {
+    match free_disk_space_mb() {
+        Some(free_mb) if free_mb < MIN_FREE_DISK_SPACE_MB => report.add(
+            Status::Warn,
+            &format!("Only {free_mb} MB of free disk space"),
+            &format!("Compiling the exercises can need up to {MIN_FREE_DISK_SPACE_MB} MB. Free some disk space"),
+        ),
+        Some(free_mb) => report.add(Status::Ok, &format!("{free_mb} MB of free disk space"), ""),
+        None if cfg!(windows) => Ok(()),
+        None => report.add(
+            Status::Warn,
+            "Failed to determine the free disk space using `df`",
+            &format!("Make sure that at least {MIN_FREE_DISK_SPACE_MB} MB are free"),
+        ),
+    }
+}
+
+// Only a warning without the exercises directory because `doctor` is also useful before `init`.
+fn check_info_file(report: &mut Report, info_file: &Result<InfoFile>) -> io::Result<()> {
+    if !Path::new(exercises_dir()).is_dir() {
+        return report.add(
+            Status::Warn,
+            &format!("No `{}` directory found", exercises_dir()),
+            "Run `rustlings init` to initialize the official exercises\nor change to the directory of the exercises",
+        );
+    }
+
+    match info_file {
+        Ok(info_file) => report.add(
+            Status::Ok,
+            &format!(
+                "{} exercises in {}",
+                info_file.exercises.len(),
+                if Path::new("info.toml").exists() {
+                    "`info.toml`"
+                } else {
+                    "the embedded `info.toml`"
+                },
+            ),
+            "",
+        ),
+        Err(e) => report.add(
+            Status::Fail,
+            &format!("{e:#}"),
+            "Fix the `info.toml` file or run `rustlings init` in another directory",
+        ),
+    }
+}
+
+/// Check the Rust toolchain and the environment that Rustlings needs and print a line per check.
+/// Exits with failure if any check fails. Warnings don't fail.
+pub fn doctor() -> Result<ExitCode> {
+    let info_file = InfoFile::parse();
+    // Falls back to the minimum supported Rust version of Rustlings itself.
+    let msrv = info_file
+        .as_ref()
+        .ok()
+        .and_then(|info_file| info_file.msrv.as_deref())
+        .unwrap_or(env!("CARGO_PKG_RUST_VERSION"));
+
+    let mut stdout = io::stdout().lock();
+    let mut report = Report {
+        stdout: &mut stdout,
+        n_failed: 0,
+    };
+
+    check_rustc(&mut report, msrv)?;
+    check_tool(&mut report, "cargo", RUSTUP_ADVICE)?;
+    check_tool(
+        &mut report,
+        "rustfmt",
+        "Install it by running `rustup component add rustfmt`",
+    )?;
+    check_tool(
+        &mut report,
+        "cargo-clippy",
+        "Install it by running `rustup component add clippy`",
+    )?;
+    check_writable(&mut report)?;
+    check_disk_space(&mut report)?;
+    check_info_file(&mut report, &info_file)?;
+
+    let n_failed = report.n_failed;
+    if n_failed == 0 {
+        stdout.write_all("\nEverything is ready for Rustlings 🦀\n".as_bytes())?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    writeln!(
+        stdout,
+        "\n{n_failed} check{} failed. Follow the advice above to fix {}",
+        if n_failed == 1 { "" } else { "s" },
+        if n_failed == 1 { "it" } else { "them" },
+    )?;
+    Ok(ExitCode::FAILURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions() {
+        assert_eq!(parse_version("1.80"), Some([1, 80, 0]));
+        assert_eq!(parse_version("1.82.1"), Some([1, 82, 1]));
+        assert_eq!(parse_version("1.84.0-nightly"), Some([1, 84, 0]));
+        assert_eq!(parse_version("1"), None);
+        assert_eq!(parse_version("1.x"), None);
+        assert_eq!(parse_version("1.2.3.4"), None);
+        assert!(parse_version("1.79.0") < parse_version("1.80"));
+        assert!(parse_version("1.100.0") > parse_version("1.80"));
+    }
+}
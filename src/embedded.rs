@@ -2,24 +2,77 @@ use anyhow::{Context, Error, Result};
 use std::{
     fs::{self, create_dir},
     io,
+    path::Path,
 };
 
-use crate::info_file::ExerciseInfo;
+use crate::info_file::{exercises_dir, solutions_dir, ExerciseInfo};
 
 /// Contains all embedded files.
 pub static EMBEDDED_FILES: EmbeddedFiles = rustlings_macros::include_files!();
 
+// A file next to the exercise file (`extra_files` or `additional_files` in `info.toml`).
+struct AdditionalFile {
+    // The file name in the exercise's directory.
+    name: &'static str,
+    content: &'static [u8],
+}
+
 // Files related to one exercise.
 struct ExerciseFiles {
     // The content of the exercise file.
     exercise: &'static [u8],
     // The content of the solution file.
     solution: &'static [u8],
+    // Other files of the exercise in its directory.
+    additional_files: &'static [AdditionalFile],
+    // The extra files of the solution in its directory.
+    solution_extra_files: &'static [AdditionalFile],
     // Index of the related `ExerciseDir` in `EmbeddedFiles::exercise_dirs`.
     dir_ind: usize,
 }
 
-fn create_dir_if_not_exists(path: &str) -> Result<()> {
+impl ExerciseFiles {
+    fn write_additional_files(&self, dir: &ExerciseDir, keep_existing: bool) -> Result<()> {
+        for additional_file in self.additional_files {
+            let path = format!("{}/{}/{}", exercises_dir(), dir.name, additional_file.name);
+            write_file(&path, additional_file.content, keep_existing)?;
+        }
+
+        Ok(())
+    }
+
+    // Write the solution file and its extra files to the directory and return the solution's path.
+    fn write_solution(&self, dir_path: &str, exercise_name: &str) -> Result<String> {
+        for extra_file in self.solution_extra_files {
+            let path = format!("{dir_path}/{}", extra_file.name);
+            write_file(&path, extra_file.content, false)?;
+        }
+
+        // 4 = 1 + 3
+        // / + .rs
+        let mut solution_path = String::with_capacity(4 + dir_path.len() + exercise_name.len());
+        solution_path.push_str(dir_path);
+        solution_path.push('/');
+        solution_path.push_str(exercise_name);
+        solution_path.push_str(".rs");
+
+        fs::write(&solution_path, self.solution)
+            .with_context(|| format!("Failed to write the solution file {solution_path}"))?;
+
+        Ok(solution_path)
+    }
+}
+
+/// Write the file. An existing file is only overwritten if `keep_existing` is `false`.
+pub fn write_file(path: &str, content: &[u8], keep_existing: bool) -> Result<()> {
+    if keep_existing && Path::new(path).exists() {
+        return Ok(());
+    }
+
+    fs::write(path, content).with_context(|| format!("Failed to write the file {path}"))
+}
+
+pub fn create_dir_if_not_exists(path: &str) -> Result<()> {
     if let Err(e) = create_dir(path) {
         if e.kind() != io::ErrorKind::AlreadyExists {
             return Err(Error::from(e).context(format!("Failed to create the directory {path}")));
@@ -29,26 +82,33 @@ fn create_dir_if_not_exists(path: &str) -> Result<()> {
     Ok(())
 }
 
-// A directory in the `exercises/` directory.
+// Like `create_dir_if_not_exists`, but also creates missing parents.
+// The exercises and solutions directories can be nested paths (see `exercises_dir`).
+fn create_dir_all_if_not_exists(path: &str) -> Result<()> {
+    fs::create_dir_all(path).with_context(|| format!("Failed to create the directory {path}"))
+}
+
+// A directory in the exercises directory (see `exercises_dir`).
 pub struct ExerciseDir {
     pub name: &'static str,
     readme: &'static [u8],
 }
 
 impl ExerciseDir {
-    fn init_on_disk(&self) -> Result<()> {
-        // 20 = 10 + 10
-        // exercises/ + /README.md
-        let mut dir_path = String::with_capacity(20 + self.name.len());
-        dir_path.push_str("exercises/");
+    fn init_on_disk(&self, keep_existing: bool) -> Result<()> {
+        let exercises_dir = exercises_dir();
+        // 11 = 1 + 10
+        // / + /README.md
+        let mut dir_path = String::with_capacity(11 + exercises_dir.len() + self.name.len());
+        dir_path.push_str(exercises_dir);
+        dir_path.push('/');
         dir_path.push_str(self.name);
         create_dir_if_not_exists(&dir_path)?;
 
         let mut readme_path = dir_path;
         readme_path.push_str("/README.md");
 
-        fs::write(&readme_path, self.readme)
-            .with_context(|| format!("Failed to write the file {readme_path}"))
+        write_file(&readme_path, self.readme, keep_existing)
     }
 }
 
@@ -61,35 +121,36 @@ pub struct EmbeddedFiles {
 }
 
 impl EmbeddedFiles {
-    /// Dump all the embedded files of the `exercises/` directory.
-    pub fn init_exercises_dir(&self, exercise_infos: &[ExerciseInfo]) -> Result<()> {
-        create_dir("exercises").context("Failed to create the directory `exercises`")?;
-
-        fs::write(
-            "exercises/README.md",
+    /// Dump all the embedded files of the exercises directory (see `exercises_dir`).
+    /// With `keep_existing`, only missing files are written.
+    pub fn init_exercises_dir(
+        &self,
+        exercise_infos: &[ExerciseInfo],
+        keep_existing: bool,
+    ) -> Result<()> {
+        let exercises_dir = exercises_dir();
+        create_dir_all_if_not_exists(exercises_dir)?;
+
+        write_file(
+            &format!("{exercises_dir}/README.md"),
             include_bytes!("../exercises/README.md"),
-        )
-        .context("Failed to write the file exercises/README.md")?;
+            keep_existing,
+        )?;
 
         for dir in self.exercise_dirs {
-            dir.init_on_disk()?;
+            dir.init_on_disk(keep_existing)?;
         }
 
-        let mut exercise_path = String::with_capacity(64);
-        let prefix = "exercises/";
-        exercise_path.push_str(prefix);
-
         for (exercise_info, exercise_files) in exercise_infos.iter().zip(self.exercise_files) {
             let dir = &self.exercise_dirs[exercise_files.dir_ind];
 
-            exercise_path.truncate(prefix.len());
-            exercise_path.push_str(dir.name);
-            exercise_path.push('/');
-            exercise_path.push_str(&exercise_info.name);
-            exercise_path.push_str(".rs");
+            write_file(
+                &exercise_info.path(),
+                exercise_files.exercise,
+                keep_existing,
+            )?;
 
-            fs::write(&exercise_path, exercise_files.exercise)
-                .with_context(|| format!("Failed to write the exercise file {exercise_path}"))?;
+            exercise_files.write_additional_files(dir, keep_existing)?;
         }
 
         Ok(())
@@ -99,38 +160,33 @@ impl EmbeddedFiles {
         let exercise_files = &self.exercise_files[exercise_ind];
         let dir = &self.exercise_dirs[exercise_files.dir_ind];
 
-        dir.init_on_disk()?;
+        dir.init_on_disk(false)?;
         fs::write(path, exercise_files.exercise)
-            .with_context(|| format!("Failed to write the exercise file {path}"))
+            .with_context(|| format!("Failed to write the exercise file {path}"))?;
+
+        exercise_files.write_additional_files(dir, false)
     }
 
-    /// Write the solution file to disk and return its path.
+    /// Write the solution file with its extra files to disk and return its path.
     pub fn write_solution_to_disk(
         &self,
         exercise_ind: usize,
         exercise_name: &str,
     ) -> Result<String> {
-        create_dir_if_not_exists("solutions")?;
+        let solutions_dir = solutions_dir();
+        create_dir_all_if_not_exists(solutions_dir)?;
 
         let exercise_files = &self.exercise_files[exercise_ind];
         let dir = &self.exercise_dirs[exercise_files.dir_ind];
 
-        // 14 = 10 + 1 + 3
-        // solutions/ + / + .rs
-        let mut dir_path = String::with_capacity(14 + dir.name.len() + exercise_name.len());
-        dir_path.push_str("solutions/");
+        // 1 = /
+        let mut dir_path = String::with_capacity(1 + solutions_dir.len() + dir.name.len());
+        dir_path.push_str(solutions_dir);
+        dir_path.push('/');
         dir_path.push_str(dir.name);
         create_dir_if_not_exists(&dir_path)?;
 
-        let mut solution_path = dir_path;
-        solution_path.push('/');
-        solution_path.push_str(exercise_name);
-        solution_path.push_str(".rs");
-
-        fs::write(&solution_path, exercise_files.solution)
-            .with_context(|| format!("Failed to write the solution file {solution_path}"))?;
-
-        Ok(solution_path)
+        exercise_files.write_solution(&dir_path, exercise_name)
     }
 }
 
@@ -143,6 +199,10 @@ mod tests {
     #[derive(Deserialize)]
     struct ExerciseInfo {
         dir: String,
+        #[serde(default)]
+        extra_files: Vec<String>,
+        #[serde(default)]
+        additional_files: Vec<String>,
     }
 
     #[derive(Deserialize)]
@@ -165,4 +225,57 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn extra_files() {
+        let exercises = toml_edit::de::from_str::<InfoFile>(EMBEDDED_FILES.info_file)
+            .expect("Failed to parse `info.toml`")
+            .exercises;
+
+        for (exercise, exercise_files) in exercises.iter().zip(EMBEDDED_FILES.exercise_files) {
+            let names =
+                |files: &[AdditionalFile]| files.iter().map(|file| file.name).collect::<Vec<_>>();
+            assert_eq!(
+                names(exercise_files.additional_files),
+                exercise
+                    .extra_files
+                    .iter()
+                    .chain(&exercise.additional_files)
+                    .collect::<Vec<_>>(),
+            );
+            assert_eq!(
+                names(exercise_files.solution_extra_files),
+                exercise.extra_files,
+            );
+        }
+    }
+
+    #[test]
+    fn write_solution() {
+        let exercise_files = ExerciseFiles {
+            exercise: b"",
+            solution: b"mod helper;\n\nfn main() {\n    helper::help();\n}\n",
+            additional_files: &[AdditionalFile {
+                name: "data.txt",
+                content: b"data",
+            }],
+            solution_extra_files: &[AdditionalFile {
+                name: "helper.rs",
+                content: b"pub fn help() {}\n",
+            }],
+            dir_ind: 0,
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let dir_path = dir.path().to_str().unwrap();
+
+        let solution_path = exercise_files.write_solution(dir_path, "modules4").unwrap();
+        assert_eq!(solution_path, format!("{dir_path}/modules4.rs"));
+        assert_eq!(fs::read(&solution_path).unwrap(), exercise_files.solution,);
+        assert_eq!(
+            fs::read(dir.path().join("helper.rs")).unwrap(),
+            b"pub fn help() {}\n",
+        );
+        // Additional files are only next to the exercise.
+        assert!(!dir.path().join("data.txt").exists());
+    }
 }
@@ -3,11 +3,16 @@ use crossterm::{
     style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor},
     QueueableCommand,
 };
-use std::io::{self, StdoutLock, Write};
+use std::{
+    hash::Hash,
+    io::{self, StdoutLock, Write},
+    time::Duration,
+};
 
 use crate::{
     cmd::CmdRunner,
-    term::{self, terminal_file_link, write_ansi, CountedWrite},
+    info_file::{exercises_dir, solutions_dir},
+    term::{self, strip_ansi, terminal_file_link, write_ansi, CountedWrite},
 };
 
 /// The initial capacity of the output buffer.
@@ -26,49 +31,178 @@ pub fn solution_link_line(stdout: &mut StdoutLock, solution_path: &str) -> io::R
     stdout.write_all(b"\n")
 }
 
-// Run an exercise binary and append its output to the `output` buffer.
+fn write_bin_output_header(output: &mut Vec<u8>) {
+    write_ansi(output, SetAttribute(Attribute::Underlined));
+    output.extend_from_slice(b"Output");
+    write_ansi(output, ResetColor);
+    output.push(b'\n');
+}
+
+fn write_bin_failure_line(output: &mut Vec<u8>) {
+    write_ansi(output, SetAttribute(Attribute::Bold));
+    write_ansi(output, SetForegroundColor(Color::Red));
+    output.extend_from_slice(b"The exercise didn't run successfully (nonzero exit code)");
+    write_ansi(output, ResetColor);
+    output.push(b'\n');
+}
+
+fn write_should_fail_line(output: &mut Vec<u8>) {
+    write_ansi(output, SetAttribute(Attribute::Bold));
+    write_ansi(output, SetForegroundColor(Color::Red));
+    output.extend_from_slice(
+        b"The exercise compiles, but it should fail to compile. Introduce the error that the exercise asks for",
+    );
+    write_ansi(output, ResetColor);
+    output.push(b'\n');
+}
+
+/// Extract the output of the binary from the output of running an exercise
+/// if the binary ran and failed.
+pub fn failed_bin_output(output: &[u8]) -> Option<&[u8]> {
+    let mut header = Vec::new();
+    write_bin_output_header(&mut header);
+    let mut failure_line = Vec::new();
+    write_bin_failure_line(&mut failure_line);
+
+    let bin_output_start = output
+        .windows(header.len())
+        .rposition(|window| window == header)?
+        + header.len();
+
+    output[bin_output_start..].strip_suffix(failure_line.as_slice())
+}
+
+/// The numbers of passed and failed tests of an exercise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// Parse the summary lines like `test result: FAILED. 3 passed; 2 failed; 0 ignored; …`
+/// of the test harness in the output of running an exercise.
+/// The pretty and the terse format have the same summary line.
+/// The counts of multiple summary lines (e.g. of unit tests and doc tests) are added.
+/// `None` if no summary line was found, e.g. because the tests didn't compile.
+pub fn test_counts(output: &[u8]) -> Option<TestCounts> {
+    let output = strip_ansi(&String::from_utf8_lossy(output));
+    let mut counts = None;
+
+    for line in output.lines() {
+        let Some((_, summary)) = line.split_once("test result: ") else {
+            continue;
+        };
+
+        let (mut passed, mut failed) = (None, None);
+        for field in summary.split(';') {
+            // The first field also contains the result like `ok. 3 passed`.
+            let mut words = field.split_whitespace();
+            let (Some(label), Some(n)) = (words.next_back(), words.next_back()) else {
+                continue;
+            };
+            match label {
+                "passed" => passed = n.parse::<usize>().ok(),
+                "failed" => failed = n.parse::<usize>().ok(),
+                _ => (),
+            }
+        }
+
+        if let (Some(passed), Some(failed)) = (passed, failed) {
+            let counts = counts.get_or_insert(TestCounts {
+                passed: 0,
+                failed: 0,
+            });
+            counts.passed += passed;
+            counts.failed += failed;
+        }
+    }
+
+    counts
+}
+
+// Run an exercise binary with the `input` as its stdin and append its output to the `output` buffer.
 // Compilation must be done before calling this method.
 fn run_bin(
     bin_name: &str,
+    input: Option<&[u8]>,
     mut output: Option<&mut Vec<u8>>,
     cmd_runner: &CmdRunner,
+    timeout: Option<Duration>,
 ) -> Result<bool> {
     if let Some(output) = output.as_deref_mut() {
-        write_ansi(output, SetAttribute(Attribute::Underlined));
-        output.extend_from_slice(b"Output");
-        write_ansi(output, ResetColor);
-        output.push(b'\n');
+        write_bin_output_header(output);
     }
 
-    let success = cmd_runner.run_debug_bin(bin_name, output.as_deref_mut())?;
+    let success = cmd_runner.run_debug_bin(bin_name, input, output.as_deref_mut(), timeout)?;
 
     if let Some(output) = output {
         if !success {
             // This output is important to show the user that something went wrong.
             // Otherwise, calling something like `exit(1)` in an exercise without further output
             // leaves the user confused about why the exercise isn't done yet.
-            write_ansi(output, SetAttribute(Attribute::Bold));
-            write_ansi(output, SetForegroundColor(Color::Red));
-            output.extend_from_slice(b"The exercise didn't run successfully (nonzero exit code)");
-            write_ansi(output, ResetColor);
-            output.push(b'\n');
+            write_bin_failure_line(output);
         }
     }
 
     Ok(success)
 }
 
+// The path of the file `{root}/{dir}/{name}.rs` or `{root}/{name}.rs` without a directory.
+fn rs_file_path(root: &str, dir: Option<&str>, name: &str) -> String {
+    let mut path = if let Some(dir) = dir {
+        // 5 = 1 + 1 + 3
+        // / + / + .rs
+        let mut path = String::with_capacity(5 + root.len() + dir.len() + name.len());
+        path.push_str(root);
+        path.push('/');
+        path.push_str(dir);
+        path.push('/');
+        path
+    } else {
+        // 4 = 1 + 3
+        // / + .rs
+        let mut path = String::with_capacity(4 + root.len() + name.len());
+        path.push_str(root);
+        path.push('/');
+        path
+    };
+
+    path.push_str(name);
+    path.push_str(".rs");
+
+    path
+}
+
 /// See `info_file::ExerciseInfo`
 pub struct Exercise {
     pub dir: Option<&'static str>,
     pub name: &'static str,
-    /// Path of the exercise file starting with the `exercises/` directory.
+    /// Path of the exercise file starting with the exercises directory.
     pub path: &'static str,
+    /// Paths of the extra and additional files next to the exercise file.
+    pub extra_paths: Vec<&'static str>,
     pub canonical_path: Option<String>,
     pub test: bool,
+    /// See `info_file::ExerciseInfo::test_command`
+    pub test_command: Option<&'static str>,
+    pub features: Vec<&'static str>,
     pub strict_clippy: bool,
-    pub hint: &'static str,
+    /// See `info_file::ExerciseInfo::should_fail`
+    pub should_fail: bool,
+    /// From a nudge to a near-solution.
+    pub hints: Vec<&'static str>,
+    /// Names of the exercises that should be done before this one.
+    pub prerequisites: Vec<&'static str>,
+    pub tags: Vec<&'static str>,
     pub done: bool,
+    /// Skipped in the watch mode to come back to it after the other pending exercises.
+    pub skipped: bool,
+    /// Seconds spent on the exercise in the watch mode until it passed.
+    pub secs_spent: u64,
+    /// Unix timestamp in seconds of when the exercise was done the last time.
+    pub completed_at: Option<u64>,
+    /// Unix timestamp in seconds of when the pending exercise became the current one.
+    pub started_at: Option<u64>,
 }
 
 impl Exercise {
@@ -87,21 +221,111 @@ pub trait RunnableExercise {
     fn strict_clippy(&self) -> bool;
     fn test(&self) -> bool;
 
+    /// The command to run instead of `cargo test` if `test` is true.
+    #[inline]
+    fn test_command(&self) -> Option<&str> {
+        None
+    }
+
+    /// The exercise is done when it fails to compile.
+    #[inline]
+    fn should_fail(&self) -> bool {
+        false
+    }
+
+    /// The Cargo features to activate.
+    #[inline]
+    fn features(&self) -> &[impl AsRef<str> + Hash] {
+        &[] as &[&str]
+    }
+
+    /// Abort running the exercise's binary or its tests after this timeout.
+    /// Compiling and Clippy aren't limited because they can take long on a cold build.
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
     // Compile, check and run the exercise or its solution (depending on `bin_name´).
+    // Clippy is skipped if `run_clippy` is false.
+    // The `input` is passed to the stdin of the binary. Without it, stdin is closed.
     // The output is written to the `output` buffer after clearing it.
+    // A successful run is cached in `cache::CACHE_DIR` to skip it if nothing changed.
     fn run<const FORCE_STRICT_CLIPPY: bool>(
         &self,
         bin_name: &str,
+        src_path: &str,
+        input: Option<&[u8]>,
         mut output: Option<&mut Vec<u8>>,
         cmd_runner: &CmdRunner,
+        run_clippy: bool,
     ) -> Result<bool> {
         if let Some(output) = output.as_deref_mut() {
             output.clear();
         }
 
+        let run_hash = cmd_runner.run_hash(
+            src_path,
+            (
+                run_clippy,
+                FORCE_STRICT_CLIPPY || self.strict_clippy(),
+                self.test(),
+                self.test_command(),
+                self.should_fail(),
+                self.features(),
+                self.timeout(),
+                input,
+            ),
+        );
+        if let Some(run_hash) = run_hash {
+            if let Some(cached_output) = cmd_runner.cached_success(bin_name, run_hash) {
+                if let Some(output) = output {
+                    output.extend_from_slice(&cached_output);
+                }
+                return Ok(true);
+            }
+        }
+
+        let success = self.run_uncached::<FORCE_STRICT_CLIPPY>(
+            bin_name,
+            input,
+            output.as_deref_mut(),
+            cmd_runner,
+            run_clippy,
+        );
+        cmd_runner.clean_sandbox(bin_name);
+        let success = success?;
+        // Without the output, a later run with output couldn't be served from the cache.
+        if let (Some(run_hash), Some(output)) = (run_hash, output) {
+            cmd_runner.cache_run(bin_name, run_hash, success, output);
+        }
+
+        Ok(success)
+    }
+
+    fn run_uncached<const FORCE_STRICT_CLIPPY: bool>(
+        &self,
+        bin_name: &str,
+        input: Option<&[u8]>,
+        mut output: Option<&mut Vec<u8>>,
+        cmd_runner: &CmdRunner,
+        run_clippy: bool,
+    ) -> Result<bool> {
+        let timeout = self.timeout();
+        let features = self.features();
+
         let build_success = cmd_runner
-            .cargo("build", bin_name, output.as_deref_mut())
+            .cargo("build", bin_name, features, output.as_deref_mut(), None)
             .run("cargo build …")?;
+        if self.should_fail() {
+            // The compiler errors are the expected output.
+            if build_success {
+                if let Some(output) = output {
+                    write_should_fail_line(output);
+                }
+            }
+            return Ok(!build_success);
+        }
         if !build_success {
             return Ok(false);
         }
@@ -112,14 +336,39 @@ pub trait RunnableExercise {
         }
 
         if self.test() {
-            let output_is_some = output.is_some();
-            let mut test_cmd = cmd_runner.cargo("test", bin_name, output.as_deref_mut());
-            if output_is_some {
-                test_cmd.args(["--", "--color", "always", "--format", "pretty"]);
-            }
-            let test_success = test_cmd.run("cargo test …")?;
+            let test_success = if let Some(test_command) = self.test_command() {
+                cmd_runner.run_test_command(
+                    test_command,
+                    bin_name,
+                    features,
+                    output.as_deref_mut(),
+                    timeout,
+                )?
+            } else {
+                // Compile the tests first because the timeout only applies to running them.
+                if timeout.is_some() {
+                    let mut build_tests_cmd =
+                        cmd_runner.cargo("test", bin_name, features, output.as_deref_mut(), None);
+                    build_tests_cmd.args(["--no-run"]);
+                    if !build_tests_cmd.run("cargo test --no-run …")? {
+                        return Ok(false);
+                    }
+
+                    if let Some(output) = output.as_deref_mut() {
+                        output.clear();
+                    }
+                }
+
+                let output_is_some = output.is_some();
+                let mut test_cmd =
+                    cmd_runner.cargo("test", bin_name, features, output.as_deref_mut(), timeout);
+                if output_is_some {
+                    test_cmd.args(["--", "--color", "always", "--format", "pretty"]);
+                }
+                test_cmd.run("cargo test …")?
+            };
             if !test_success {
-                run_bin(bin_name, output, cmd_runner)?;
+                run_bin(bin_name, input, output, cmd_runner, timeout)?;
                 return Ok(false);
             }
 
@@ -129,7 +378,12 @@ pub trait RunnableExercise {
             }
         }
 
-        let mut clippy_cmd = cmd_runner.cargo("clippy", bin_name, output.as_deref_mut());
+        if !run_clippy {
+            return run_bin(bin_name, input, output, cmd_runner, timeout);
+        }
+
+        let mut clippy_cmd =
+            cmd_runner.cargo("clippy", bin_name, features, output.as_deref_mut(), None);
 
         // `--profile test` is required to also check code with `#[cfg(test)]`.
         if FORCE_STRICT_CLIPPY || self.strict_clippy() {
@@ -139,7 +393,7 @@ pub trait RunnableExercise {
         }
 
         let clippy_success = clippy_cmd.run("cargo clippy …")?;
-        let run_success = run_bin(bin_name, output, cmd_runner)?;
+        let run_success = run_bin(bin_name, input, output, cmd_runner, timeout)?;
 
         Ok(clippy_success && run_success)
     }
@@ -148,43 +402,74 @@ pub trait RunnableExercise {
     /// The output is written to the `output` buffer after clearing it.
     #[inline]
     fn run_exercise(&self, output: Option<&mut Vec<u8>>, cmd_runner: &CmdRunner) -> Result<bool> {
-        self.run::<false>(self.name(), output, cmd_runner)
+        let path = rs_file_path(exercises_dir(), self.dir(), self.name());
+        self.run::<false>(self.name(), &path, None, output, cmd_runner, true)
+    }
+
+    /// Compile, check and run the exercise with the `input` as the stdin of its binary.
+    /// Needed for exercises that read from stdin like a guessing game.
+    /// The output is written to the `output` buffer after clearing it.
+    fn run_with_input(
+        &self,
+        input: &[u8],
+        output: &mut Vec<u8>,
+        cmd_runner: &CmdRunner,
+    ) -> Result<bool> {
+        let path = rs_file_path(exercises_dir(), self.dir(), self.name());
+        self.run::<false>(
+            self.name(),
+            &path,
+            Some(input),
+            Some(output),
+            cmd_runner,
+            true,
+        )
+    }
+
+    /// Only run Clippy on the exercise to show its warnings without running it.
+    /// The output is written to the `output` buffer after clearing it.
+    /// The boolean in the returned `Result` is false if Clippy failed, e.g. on a compilation error.
+    fn run_clippy(&self, output: &mut Vec<u8>, cmd_runner: &CmdRunner) -> Result<bool> {
+        output.clear();
+
+        let mut clippy_cmd =
+            cmd_runner.cargo("clippy", self.name(), self.features(), Some(output), None);
+        // `--profile test` is required to also check code with `#[cfg(test)]`.
+        clippy_cmd.args(["--profile", "test"]);
+        let success = clippy_cmd.run("cargo clippy …");
+        cmd_runner.clean_sandbox(self.name());
+
+        success
     }
 
     /// Compile, check and run the exercise's solution.
+    /// The `input` is passed as the stdin of its binary like for the exercise.
+    /// Clippy warnings are denied unless `run_clippy` is false which skips Clippy.
     /// The output is written to the `output` buffer after clearing it.
-    fn run_solution(&self, output: Option<&mut Vec<u8>>, cmd_runner: &CmdRunner) -> Result<bool> {
+    fn run_solution(
+        &self,
+        input: Option<&[u8]>,
+        output: Option<&mut Vec<u8>>,
+        cmd_runner: &CmdRunner,
+        run_clippy: bool,
+    ) -> Result<bool> {
         let name = self.name();
         let mut bin_name = String::with_capacity(name.len() + 4);
         bin_name.push_str(name);
         bin_name.push_str("_sol");
 
-        self.run::<true>(&bin_name, output, cmd_runner)
+        self.run::<true>(
+            &bin_name,
+            &self.sol_path(),
+            input,
+            output,
+            cmd_runner,
+            run_clippy,
+        )
     }
 
     fn sol_path(&self) -> String {
-        let name = self.name();
-
-        let mut path = if let Some(dir) = self.dir() {
-            // 14 = 10 + 1 + 3
-            // solutions/ + / + .rs
-            let mut path = String::with_capacity(14 + dir.len() + name.len());
-            path.push_str("solutions/");
-            path.push_str(dir);
-            path.push('/');
-            path
-        } else {
-            // 13 = 10 + 3
-            // solutions/ + .rs
-            let mut path = String::with_capacity(13 + name.len());
-            path.push_str("solutions/");
-            path
-        };
-
-        path.push_str(name);
-        path.push_str(".rs");
-
-        path
+        rs_file_path(solutions_dir(), self.dir(), self.name())
     }
 }
 
@@ -208,4 +493,48 @@ impl RunnableExercise for Exercise {
     fn test(&self) -> bool {
         self.test
     }
+
+    #[inline]
+    fn test_command(&self) -> Option<&str> {
+        self.test_command
+    }
+
+    #[inline]
+    fn should_fail(&self) -> bool {
+        self.should_fail
+    }
+
+    #[inline]
+    fn features(&self) -> &[impl AsRef<str> + Hash] {
+        &self.features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summaries() {
+        let counts = |passed, failed| Some(TestCounts { passed, failed });
+
+        assert_eq!(
+            test_counts(b"running 5 tests\ntest tests::a ... ok\n\ntest result: FAILED. 3 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n"),
+            counts(3, 2),
+        );
+        // Terse format with colors.
+        assert_eq!(
+            test_counts(b"running 2 tests\n..\ntest result: \x1b[32mok\x1b[0m. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s\n"),
+            counts(2, 0),
+        );
+        // Unit tests and doc tests.
+        assert_eq!(
+            test_counts(
+                b"test result: ok. 1 passed; 0 failed;\ntest result: FAILED. 0 passed; 1 failed;\n"
+            ),
+            counts(1, 1),
+        );
+        assert_eq!(test_counts(b"error[E0425]: cannot find value `x`\n"), None);
+        assert_eq!(test_counts(b"test result: garbage\n"), None);
+    }
 }
@@ -0,0 +1,316 @@
+use anyhow::{bail, Context, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    fs::{self, create_dir_all, read_dir},
+    io::{Cursor, ErrorKind, Read, Write},
+    iter,
+    path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, DateTime, ZipArchive, ZipWriter};
+
+use crate::{
+    cli::{Editor, ExportFormat},
+    dev::update::{self, cargo_toml_path_and_prefix},
+    embedded::EMBEDDED_FILES,
+    info_file::{exercises_dir, solutions_dir, PACKS_DIR},
+    stats::{civil_from_days, SECS_PER_DAY},
+};
+
+const METADATA_PATH: &str = "rustlings-export.json";
+
+/// The file `rustlings-export.json` in the root of the archive.
+#[derive(Serialize, Deserialize)]
+struct ExportMetadata {
+    rustlings_version: String,
+    /// Unix timestamp in seconds.
+    exported_at: u64,
+    includes_solutions: bool,
+}
+
+type Writer = ZipWriter<Cursor<Vec<u8>>>;
+
+// The modification time of all entries in UTC. Dates before 1980 can't be represented in ZIP.
+fn modification_time(timestamp: u64) -> DateTime {
+    let (year, month, day) = civil_from_days(timestamp / SECS_PER_DAY);
+    let secs_of_day = timestamp % SECS_PER_DAY;
+
+    let (Ok(year), Ok(month), Ok(day)) =
+        (u16::try_from(year), u8::try_from(month), u8::try_from(day))
+    else {
+        return DateTime::default();
+    };
+    DateTime::from_date_and_time(
+        year,
+        month,
+        day,
+        (secs_of_day / 3600) as u8,
+        (secs_of_day % 3600 / 60) as u8,
+        (secs_of_day % 60) as u8,
+    )
+    .unwrap_or_default()
+}
+
+// Add a file with its path relative to the root of the archive using `/` as separator.
+fn add_file(
+    writer: &mut Writer,
+    options: SimpleFileOptions,
+    archive_path: &str,
+    content: &[u8],
+) -> Result<()> {
+    writer
+        .start_file(archive_path, options)
+        .and_then(|()| writer.write_all(content).map_err(Into::into))
+        .with_context(|| format!("Failed to add the file {archive_path} to the archive"))
+}
+
+// Add all files in the directory and its subdirectories to the archive.
+// Their paths in the archive start with `archive_dir` instead of `dir`
+// to be relative to the root of the course even if `dir` is a custom or absolute path.
+fn add_dir(
+    writer: &mut Writer,
+    options: SimpleFileOptions,
+    dir: &Path,
+    archive_dir: &str,
+) -> Result<()> {
+    let mut entries = read_dir(dir)
+        .with_context(|| format!("Failed to open the directory {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to read the directory {}", dir.display()))?;
+    // Reproducible archives.
+    entries.sort_unstable();
+
+    for path in entries {
+        let Some(file_name) = path.file_name().and_then(|file_name| file_name.to_str()) else {
+            bail!("The path {} isn't valid UTF-8", path.display());
+        };
+        let archive_path = format!("{archive_dir}/{file_name}");
+
+        if path.is_dir() {
+            add_dir(writer, options, &path, &archive_path)?;
+            continue;
+        }
+
+        let content = fs::read(&path)
+            .with_context(|| format!("Failed to read the file {}", path.display()))?;
+        add_file(writer, options, &archive_path, &content)?;
+    }
+
+    Ok(())
+}
+
+pub fn export(format: ExportFormat, include_solutions: bool, output: &Path) -> Result<()> {
+    let ExportFormat::Zip = format;
+
+    if !Path::new(exercises_dir()).is_dir() {
+        bail!("No `{}` directory found. Run this command in a directory initialized by `rustlings init` or created by `rustlings dev new`", exercises_dir());
+    }
+    if output.exists() {
+        bail!("The file {} already exists", output.display());
+    }
+
+    // A clock before the Unix epoch can't be represented anyway.
+    let exported_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let metadata = ExportMetadata {
+        rustlings_version: String::from(env!("CARGO_PKG_VERSION")),
+        exported_at,
+        includes_solutions: include_solutions,
+    };
+    let mut writer = ZipWriter::new(Cursor::new(Vec::with_capacity(1 << 20)));
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .last_modified_time(modification_time(exported_at))
+        .unix_permissions(0o644);
+    add_file(
+        &mut writer,
+        options,
+        METADATA_PATH,
+        &serde_json::to_vec_pretty(&metadata).unwrap(),
+    )?;
+
+    // The official exercises are exported with their embedded `info.toml` to be self-contained.
+    let info_file = match fs::read("info.toml") {
+        Ok(v) => v,
+        Err(e) if e.kind() == ErrorKind::NotFound => EMBEDDED_FILES.info_file.as_bytes().to_vec(),
+        Err(e) => return Err(Error::from(e).context("Failed to read the `info.toml` file")),
+    };
+    add_file(&mut writer, options, "info.toml", &info_file)?;
+
+    // The debug build uses `dev/Cargo.toml` in addition to the root one.
+    let (cargo_toml_path, _) = cargo_toml_path_and_prefix();
+    let cargo_toml_paths = iter::once("Cargo.toml")
+        .chain((cargo_toml_path != "Cargo.toml").then_some(cargo_toml_path));
+    for path in cargo_toml_paths {
+        if let Ok(content) = fs::read(path) {
+            add_file(&mut writer, options, path, &content)?;
+        }
+    }
+
+    // Fetched packs are needed offline.
+    if Path::new(PACKS_DIR).is_dir() {
+        add_dir(&mut writer, options, Path::new(PACKS_DIR), PACKS_DIR)?;
+    }
+    // Stored under the default directory names to be importable without the environment variables.
+    add_dir(
+        &mut writer,
+        options,
+        Path::new(exercises_dir()),
+        "exercises",
+    )?;
+    if include_solutions && Path::new(solutions_dir()).is_dir() {
+        add_dir(
+            &mut writer,
+            options,
+            Path::new(solutions_dir()),
+            "solutions",
+        )?;
+    }
+
+    let archive = writer
+        .finish()
+        .context("Failed to finish the archive")?
+        .into_inner();
+    fs::write(output, archive)
+        .with_context(|| format!("Failed to write the file {}", output.display()))?;
+    println!("Exported the exercises to {}", output.display());
+
+    Ok(())
+}
+
+// Only relative paths without `..` can be extracted to stay in the target directory.
+fn is_safe_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains(['\\', ':'])
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+// Return the paths and contents of the files in the archive. Directory entries are skipped.
+fn read_files(archive: Vec<u8>) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut archive = ZipArchive::new(Cursor::new(archive))?;
+    let mut files = Vec::with_capacity(archive.len());
+
+    for ind in 0..archive.len() {
+        let mut file = archive.by_index(ind)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        let path = file.name().to_string();
+        // The size is only a hint.
+        let mut content = Vec::with_capacity(file.size().min(1 << 20) as usize);
+        file.read_to_end(&mut content)
+            .with_context(|| format!("Failed to read the file {path} in the archive"))?;
+        files.push((path, content));
+    }
+
+    Ok(files)
+}
+
+/// Extract the archive created by `rustlings export` into the directory and run `dev update` in it.
+/// The directory defaults to the file name of the archive without its extension.
+pub fn import(archive_path: &Path, dir: Option<PathBuf>) -> Result<()> {
+    let archive = fs::read(archive_path)
+        .with_context(|| format!("Failed to read the file {}", archive_path.display()))?;
+    let files = read_files(archive)
+        .with_context(|| format!("Failed to read the archive {}", archive_path.display()))?;
+
+    let Some((_, metadata)) = files.iter().find(|(path, _)| *path == METADATA_PATH) else {
+        bail!("The archive doesn't contain the file `{METADATA_PATH}`. Only archives created by `rustlings export` can be imported");
+    };
+    let metadata = serde_json::from_slice::<ExportMetadata>(metadata)
+        .with_context(|| format!("Failed to parse the file `{METADATA_PATH}` of the archive"))?;
+    if metadata.rustlings_version != env!("CARGO_PKG_VERSION") {
+        println!(
+            "Note: The exercises were exported with Rustlings {}. This is Rustlings {}\n",
+            metadata.rustlings_version,
+            env!("CARGO_PKG_VERSION"),
+        );
+    }
+
+    let dir = match dir {
+        Some(v) => v,
+        None => match archive_path.file_stem() {
+            Some(stem) => PathBuf::from(stem),
+            None => bail!("Failed to determine the directory to import into. Pass it with `--dir`"),
+        },
+    };
+    if dir.exists() {
+        bail!(
+            "The directory {} already exists. Pass another one with `--dir`",
+            dir.display(),
+        );
+    }
+
+    for (path, _) in &files {
+        if !is_safe_path(path) {
+            bail!("The archive contains the unsafe path `{path}`");
+        }
+    }
+
+    for (path, content) in files {
+        if path == METADATA_PATH {
+            continue;
+        }
+
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)
+                .with_context(|| format!("Failed to create the directory {}", parent.display()))?;
+        }
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write the file {}", path.display()))?;
+    }
+
+    env::set_current_dir(&dir)
+        .with_context(|| format!("Failed to change the directory to {}", dir.display()))?;
+    // Users store their solutions there when done.
+    create_dir_all(solutions_dir())
+        .with_context(|| format!("Failed to create the directory `{}`", solutions_dir()))?;
+    update::update(false, false, Editor::None)
+        .context("Failed to update the imported exercises")?;
+
+    println!(
+        "\nImported the exercises into {}. Run `cd {}` and then `rustlings` to start",
+        dir.display(),
+        dir.display(),
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modification_times() {
+        let time = modification_time(1_700_000_000);
+        assert_eq!((time.year(), time.month(), time.day()), (2023, 11, 14),);
+        assert_eq!((time.hour(), time.minute(), time.second()), (22, 13, 20));
+        // Before 1980.
+        assert_eq!(modification_time(0), DateTime::default());
+    }
+
+    #[test]
+    fn safe_paths() {
+        assert!(is_safe_path("exercises/00_intro/intro1.rs"));
+        assert!(is_safe_path("info.toml"));
+        for path in [
+            "",
+            "/etc/passwd",
+            "../x",
+            "exercises/../../x",
+            "C:\\x",
+            "a\\..\\x",
+            "./x",
+        ] {
+            assert!(!is_safe_path(path), "{path}");
+        }
+    }
+}
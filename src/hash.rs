@@ -0,0 +1,91 @@
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write,
+    hash::Hasher,
+    process::{Command, Stdio},
+    sync::OnceLock,
+};
+
+/// The SHA-256 hash of the data as a lowercase hex string.
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            // Writing to a string can't fail.
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// A hasher for persisted cache keys based on SHA-256.
+/// Unlike `DefaultHasher`, its algorithm doesn't change between Rust releases.
+#[derive(Default)]
+pub struct StableHasher(Sha256);
+
+impl Hasher for StableHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        let mut first_bytes = [0; 8];
+        first_bytes.copy_from_slice(&digest[..8]);
+        u64::from_le_bytes(first_bytes)
+    }
+}
+
+// The stdout of the command or `None` if it failed.
+fn cmd_stdout(program: &str, arg: &str) -> Option<Vec<u8>> {
+    Command::new(program)
+        .arg(arg)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| output.stdout)
+}
+
+/// The output of `rustc -vV` followed by the one of `clippy-driver -V`
+/// to invalidate caches when the toolchain changes.
+/// `None` if the version of `rustc` can't be determined.
+/// The version of Clippy is left out if Clippy isn't installed.
+pub fn toolchain_versions() -> Option<&'static [u8]> {
+    static VERSIONS: OnceLock<Option<Vec<u8>>> = OnceLock::new();
+    VERSIONS
+        .get_or_init(|| {
+            let mut versions = cmd_stdout("rustc", "-vV")?;
+            if let Some(clippy_version) = cmd_stdout("clippy-driver", "-V") {
+                versions.extend_from_slice(&clippy_version);
+            }
+            Some(versions)
+        })
+        .as_deref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        );
+    }
+
+    #[test]
+    fn stable_hasher() {
+        let mut hasher = StableHasher::default();
+        hasher.write(b"abc");
+        // The first 8 bytes of the SHA-256 hash above in little-endian.
+        assert_eq!(hasher.finish(), 0xeacf_018f_bf16_78ba);
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::{Context, Result};
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::{
+    exercise::RunnableExercise,
+    info_file::{InfoFile, TestMode},
+};
+
+/// Print the metadata of the exercise from `info.toml`.
+/// Only the number of hints is printed to not spoil the exercise.
+pub fn print(info_file: &InfoFile, name: &str) -> Result<()> {
+    let exercise_info = info_file
+        .exercises
+        .iter()
+        .find(|exercise_info| exercise_info.name == name)
+        .with_context(|| format!("No exercise found for '{name}'!"))?;
+
+    let mut fields = vec![
+        ("Name", exercise_info.name.clone()),
+        ("Path", exercise_info.path()),
+    ];
+
+    let sol_path = exercise_info.sol_path();
+    if Path::new(&sol_path).exists() {
+        fields.push(("Solution", sol_path));
+    }
+
+    fields.push((
+        "Checked by",
+        match (exercise_info.test, &exercise_info.test_command) {
+            _ if exercise_info.should_fail => String::from("Failing to compile"),
+            (TestMode::Cargo, _) => String::from("Tests"),
+            (TestMode::Custom, Some(test_command)) => format!("`{test_command}`"),
+            (TestMode::Off | TestMode::Custom, _) => String::from("Running `main`"),
+        },
+    ));
+    if let Some(edition) = &exercise_info.edition {
+        fields.push(("Edition", edition.clone()));
+    }
+    if !exercise_info.features.is_empty() {
+        fields.push(("Features", exercise_info.features.join(", ")));
+    }
+    if exercise_info.strict_clippy {
+        fields.push(("Clippy", String::from("No warnings allowed")));
+    }
+    if let Some(difficulty) = exercise_info.difficulty {
+        fields.push(("Difficulty", difficulty.to_string()));
+    }
+    if let Some(minutes) = exercise_info.estimated_minutes {
+        fields.push(("Estimated time", format!("{minutes} min")));
+    }
+    if !exercise_info.tags.is_empty() {
+        fields.push(("Tags", exercise_info.tags.join(", ")));
+    }
+    if let Some(group) = &exercise_info.group {
+        fields.push(("Group", group.clone()));
+    }
+    if !exercise_info.prerequisites.is_empty() {
+        fields.push(("Prerequisites", exercise_info.prerequisites.join(", ")));
+    }
+    if !exercise_info.extra_files.is_empty() {
+        fields.push(("Extra files", exercise_info.extra_files.join(", ")));
+    }
+    if !exercise_info.additional_files.is_empty() {
+        fields.push((
+            "Additional files",
+            exercise_info.additional_files.join(", "),
+        ));
+    }
+    if let Some(stdin_fixture) = &exercise_info.stdin_fixture {
+        fields.push(("Stdin fixture", stdin_fixture.clone()));
+    }
+    let n_hints = exercise_info.hints.len();
+    fields.push((
+        "Hints",
+        format!(
+            "{n_hints} level{} (`rustlings hint {name}`)",
+            if n_hints == 1 { "" } else { "s" },
+        ),
+    ));
+    if let Some(author) = &exercise_info.author {
+        fields.push(("Author", author.clone()));
+    }
+    if let Some(source_url) = &exercise_info.source_url {
+        fields.push(("Source", source_url.clone()));
+    }
+
+    // +1 for the colon.
+    let key_col_width = fields
+        .iter()
+        .fold(0, |width, (key, _)| width.max(key.len() + 1));
+    let mut stdout = io::stdout().lock();
+    for (key, value) in fields {
+        writeln!(stdout, "{:key_col_width$}  {value}", format!("{key}:"))?;
+    }
+
+    Ok(())
+}
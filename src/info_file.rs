@@ -1,49 +1,168 @@
 use anyhow::{bail, Context, Error, Result};
-use serde::Deserialize;
-use std::{fs, io::ErrorKind};
+use serde::{de, Deserialize, Deserializer};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    env, fs,
+    hash::Hash,
+    io::ErrorKind,
+    slice,
+    sync::OnceLock,
+    time::Duration,
+};
 
 use crate::{embedded::EMBEDDED_FILES, exercise::RunnableExercise};
 
 /// Deserialized from the `info.toml` file.
-#[derive(Deserialize)]
+#[derive(Deserialize, Hash)]
 pub struct ExerciseInfo {
     /// Exercise's unique name.
     pub name: String,
-    /// Exercise's directory name inside the `exercises/` directory.
+    /// Exercise's directory name inside the exercises directory.
     pub dir: Option<String>,
-    /// Run `cargo test` on the exercise.
-    #[serde(default = "default_true")]
-    pub test: bool,
+    /// Run `cargo test` on the exercise or `test_command` with `test = "custom"`.
+    #[serde(default = "default_test_mode")]
+    pub test: TestMode,
+    /// The command to run instead of `cargo test` with `test = "custom"`,
+    /// e.g. `cargo test -- --test-threads 1`.
+    pub test_command: Option<String>,
+    /// Cargo features of the course's `Cargo.toml` to activate when running the exercise.
+    #[serde(default)]
+    pub features: Vec<String>,
     /// Deny all Clippy warnings.
     #[serde(default)]
     pub strict_clippy: bool,
-    /// The exercise's hint to be shown to the user on request.
-    pub hint: String,
+    /// The exercise is done when it fails to compile, e.g. to show a rule of the borrow checker.
+    /// It isn't tested, checked with Clippy or run.
+    #[serde(default)]
+    pub should_fail: bool,
+    /// The exercise's hints from a nudge to a near-solution to be shown to the user on request.
+    /// A single hint can be specified as a string with the key `hint`.
+    #[serde(alias = "hint", deserialize_with = "deserialize_hints")]
+    pub hints: Vec<String>,
     /// The exercise is already solved. Ignore it when checking that all exercises are unsolved.
     #[serde(default)]
     pub skip_check_unsolved: bool,
+    /// The exercise intentionally triggers Clippy warnings. Ignore it in `dev lint`.
+    #[serde(default)]
+    pub skip_check_lint: bool,
+    /// Lints that the exercise intentionally allows with `#[allow(…)]` attributes.
+    #[serde(default)]
+    pub allowed_lints: Vec<String>,
+    /// Code patterns that would spoil the exercise and must not appear in the exercise file,
+    /// e.g. `.clone()` in a borrowing exercise.
+    #[serde(default)]
+    pub forbidden_patterns: Vec<String>,
+    /// The solution intentionally isn't formatted. Don't check it with `rustfmt` in `dev check`.
+    #[serde(default)]
+    pub skip_fmt_check: bool,
+    /// Optional difficulty from 1 (easiest) to 5 (hardest).
+    pub difficulty: Option<u8>,
+    /// Optional estimated time in minutes to solve the exercise.
+    pub estimated_minutes: Option<u8>,
+    /// Concepts covered by the exercise to be able to filter by them.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Optional name of the group in `[[groups]]` that the exercise is listed under.
+    pub group: Option<String>,
+    /// Additional Rust files of a multi-file exercise next to the exercise file.
+    /// The exercise file includes them as modules with `mod` declarations.
+    #[serde(default)]
+    pub extra_files: Vec<String>,
+    /// Non-Rust files next to the exercise file that the exercise needs (e.g. input data).
+    #[serde(default)]
+    pub additional_files: Vec<String>,
+    /// Optional file next to the exercise file whose content is passed to the standard input
+    /// of the exercise when `dev check` runs it, e.g. the guesses of a guessing game.
+    pub stdin_fixture: Option<String>,
+    /// Names of earlier exercises that should be done before this one.
+    /// `requires` is accepted as an alias.
+    #[serde(default, alias = "requires")]
+    pub prerequisites: Vec<String>,
+    /// Seconds after which the binary or the tests of the exercise are killed in `dev check`.
+    /// The default is `DEFAULT_TIMEOUT_SECS`.
+    pub timeout_secs: Option<u64>,
+    /// Optional Rust edition of the exercise and its solution instead of the one in `Cargo.toml`.
+    pub edition: Option<String>,
+    /// Optional author of a community-contributed exercise.
+    pub author: Option<String>,
+    /// Optional URL of the original source of the exercise.
+    pub source_url: Option<String>,
+}
+/// The default of `ExerciseInfo::timeout_secs`.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// How an exercise is tested. Deserialized from `test = true`, `test = false` or `test = "custom"`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TestMode {
+    /// Only run `main`.
+    Off,
+    /// Run `cargo test` before running `main`.
+    Cargo,
+    /// Run `test_command` instead of `cargo test`.
+    Custom,
 }
+
+impl<'de> Deserialize<'de> for TestMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Test {
+            Bool(bool),
+            Mode(String),
+        }
+
+        match Test::deserialize(deserializer)? {
+            Test::Bool(true) => Ok(Self::Cargo),
+            Test::Bool(false) => Ok(Self::Off),
+            Test::Mode(mode) if mode == "custom" => Ok(Self::Custom),
+            Test::Mode(mode) => Err(de::Error::custom(format!(
+                "invalid test mode `{mode}`, expected `true`, `false` or `\"custom\"`"
+            ))),
+        }
+    }
+}
+
 #[inline(always)]
-const fn default_true() -> bool {
-    true
+const fn default_test_mode() -> TestMode {
+    TestMode::Cargo
+}
+
+// Accept a single hint as a string or a list of hints.
+fn deserialize_hints<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Hints {
+        One(String),
+        Multiple(Vec<String>),
+    }
+
+    Ok(match Hints::deserialize(deserializer)? {
+        Hints::One(hint) => vec![hint],
+        Hints::Multiple(hints) => hints,
+    })
 }
 
 impl ExerciseInfo {
-    /// Path to the exercise file starting with the `exercises/` directory.
+    /// Path to the exercise file starting with the exercises directory (see `exercises_dir`).
     pub fn path(&self) -> String {
+        let exercises_dir = exercises_dir();
         let mut path = if let Some(dir) = &self.dir {
-            // 14 = 10 + 1 + 3
-            // exercises/ + / + .rs
-            let mut path = String::with_capacity(14 + dir.len() + self.name.len());
-            path.push_str("exercises/");
+            // 5 = 1 + 1 + 3
+            // / + / + .rs
+            let mut path =
+                String::with_capacity(5 + exercises_dir.len() + dir.len() + self.name.len());
+            path.push_str(exercises_dir);
+            path.push('/');
             path.push_str(dir);
             path.push('/');
             path
         } else {
-            // 13 = 10 + 3
-            // exercises/ + .rs
-            let mut path = String::with_capacity(13 + self.name.len());
-            path.push_str("exercises/");
+            // 4 = 1 + 3
+            // / + .rs
+            let mut path = String::with_capacity(4 + exercises_dir.len() + self.name.len());
+            path.push_str(exercises_dir);
+            path.push('/');
             path
         };
 
@@ -52,6 +171,34 @@ impl ExerciseInfo {
 
         path
     }
+
+    /// Paths to the extra files starting with `base_dir` (`exercises_dir()` or `solutions_dir()`).
+    pub fn extra_file_paths<'a>(&'a self, base_dir: &'a str) -> impl Iterator<Item = String> + 'a {
+        self.paths_next_to_exercise(base_dir, &self.extra_files)
+    }
+
+    /// Paths to the additional files starting with the exercises directory.
+    pub fn additional_file_paths(&self) -> impl Iterator<Item = String> + '_ {
+        self.paths_next_to_exercise(exercises_dir(), &self.additional_files)
+    }
+
+    /// Path to the stdin fixture starting with the exercises directory.
+    pub fn stdin_fixture_path(&self) -> Option<String> {
+        let stdin_fixture = self.stdin_fixture.as_ref()?;
+        self.paths_next_to_exercise(exercises_dir(), slice::from_ref(stdin_fixture))
+            .next()
+    }
+
+    fn paths_next_to_exercise<'a>(
+        &'a self,
+        base_dir: &'a str,
+        file_names: &'a [String],
+    ) -> impl Iterator<Item = String> + 'a {
+        file_names.iter().map(move |file_name| match &self.dir {
+            Some(dir) => format!("{base_dir}/{dir}/{file_name}"),
+            None => format!("{base_dir}/{file_name}"),
+        })
+    }
 }
 
 impl RunnableExercise for ExerciseInfo {
@@ -72,7 +219,115 @@ impl RunnableExercise for ExerciseInfo {
 
     #[inline]
     fn test(&self) -> bool {
-        self.test
+        self.test != TestMode::Off
+    }
+
+    #[inline]
+    fn should_fail(&self) -> bool {
+        self.should_fail
+    }
+
+    #[inline]
+    fn test_command(&self) -> Option<&str> {
+        self.test_command
+            .as_deref()
+            .filter(|_| self.test == TestMode::Custom)
+    }
+
+    #[inline]
+    fn features(&self) -> &[impl AsRef<str> + Hash] {
+        &self.features
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(
+            self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS),
+        ))
+    }
+}
+
+/// A section of exercises in `rustlings list`.
+#[derive(Deserialize)]
+pub struct GroupInfo {
+    /// Group's unique name which exercises reference with their `group`.
+    pub name: String,
+    /// Shown once above the exercises of the group.
+    pub description: String,
+}
+
+/// A pack of exercises from another source that is merged into the exercises of `info.toml`.
+#[derive(Deserialize)]
+pub struct PackInfo {
+    /// Pack's unique name. It is the prefix of the `dir` of all exercises in the pack.
+    pub name: String,
+    /// HTTPS URL of the pack's `info.toml` fragment containing only `[[exercises]]` entries.
+    pub url: String,
+    /// SHA-256 checksum of the fragment as a hex string.
+    pub sha256: String,
+}
+
+impl PackInfo {
+    /// Path of the fetched fragment.
+    pub fn path(&self) -> String {
+        format!("{PACKS_DIR}/{}.toml", self.name)
+    }
+}
+
+/// The directory of the fragments fetched by `rustlings pack fetch`.
+pub const PACKS_DIR: &str = "packs";
+
+/// Overrides the directory of the exercise files.
+pub const EXERCISES_DIR_ENV_VAR: &str = "RUSTLINGS_EXERCISES_DIR";
+/// Overrides the directory of the solution files.
+pub const SOLUTIONS_DIR_ENV_VAR: &str = "RUSTLINGS_SOLUTIONS_DIR";
+
+// Read the directory from the environment variable if it is set and not empty.
+fn dir_from_env_var(env_var: &str, default: &'static str) -> &'static str {
+    env::var(env_var)
+        .ok()
+        // A trailing separator would lead to double separators in the paths.
+        .map(|dir| dir.trim_end_matches(['/', '\\']).to_string())
+        .filter(|dir| !dir.is_empty())
+        // Leaking is not a problem because the directory is only read once per program run.
+        .map_or(default, |dir| dir.leak())
+}
+
+/// The directory of the exercise files. `exercises` unless `RUSTLINGS_EXERCISES_DIR` is set.
+/// It can also be an absolute path outside of the current directory.
+pub fn exercises_dir() -> &'static str {
+    static DIR: OnceLock<&str> = OnceLock::new();
+    DIR.get_or_init(|| dir_from_env_var(EXERCISES_DIR_ENV_VAR, "exercises"))
+}
+
+/// The directory of the solution files. `solutions` unless `RUSTLINGS_SOLUTIONS_DIR` is set.
+pub fn solutions_dir() -> &'static str {
+    static DIR: OnceLock<&str> = OnceLock::new();
+    DIR.get_or_init(|| dir_from_env_var(SOLUTIONS_DIR_ENV_VAR, "solutions"))
+}
+
+/// The deserialized `info.toml` fragment of a pack.
+#[derive(Deserialize)]
+pub struct PackFile {
+    pub exercises: Vec<ExerciseInfo>,
+}
+
+impl PackFile {
+    /// Parse a fragment and prefix the `dir` of its exercises with the pack's name
+    /// to avoid collisions with the other exercises.
+    pub fn parse(pack_name: &str, content: &str) -> Result<Self> {
+        let mut slf = toml_edit::de::from_str::<Self>(content).with_context(|| {
+            format!("Failed to parse the `info.toml` fragment of the pack `{pack_name}`")
+        })?;
+
+        for exercise_info in &mut slf.exercises {
+            exercise_info.dir = Some(match &exercise_info.dir {
+                Some(dir) => format!("{pack_name}_{dir}"),
+                None => String::from(pack_name),
+            });
+        }
+
+        Ok(slf)
     }
 }
 
@@ -85,35 +340,182 @@ pub struct InfoFile {
     pub welcome_message: Option<String>,
     /// Shown to users after finishing all exercises.
     pub final_message: Option<String>,
+    /// The minimum supported Rust version of the exercises like `1.75`.
+    /// The default of `dev check --msrv`.
+    pub msrv: Option<String>,
+    /// Whether `dev check` requires a `README.md` file with at least one link in every exercise directory.
+    /// Defaults to `true` for the official exercises and `false` for third-party exercises.
+    pub require_readmes: Option<bool>,
     /// List of all exercises.
     pub exercises: Vec<ExerciseInfo>,
+    /// Groups of exercises for `rustlings list`.
+    #[serde(default)]
+    pub groups: Vec<GroupInfo>,
+    /// Packs whose exercises are appended to `exercises` after `rustlings pack fetch`.
+    #[serde(default)]
+    pub packs: Vec<PackInfo>,
 }
 
 impl InfoFile {
     /// Official exercises: Parse the embedded `info.toml` file.
     /// Third-party exercises: Parse the `info.toml` file in the current directory.
+    /// The exercises of fetched packs are appended.
     pub fn parse() -> Result<Self> {
+        let mut slf = Self::parse_without_packs()?;
+
+        // Packs that weren't fetched yet are ignored.
+        for pack in &slf.packs {
+            let path = pack.path();
+            let content = match fs::read_to_string(&path) {
+                Ok(v) => v,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(Error::from(e).context(format!("Failed to read the file {path}")))
+                }
+            };
+
+            slf.exercises
+                .extend(PackFile::parse(&pack.name, &content)?.exercises);
+        }
+
+        if slf.exercises.is_empty() {
+            bail!("{NO_EXERCISES_ERR}");
+        }
+
+        Ok(slf)
+    }
+
+    /// Like `parse`, but without the exercises of packs.
+    pub fn parse_without_packs() -> Result<Self> {
         // Read a local `info.toml` if it exists.
-        let slf = match fs::read_to_string("info.toml") {
+        match fs::read_to_string("info.toml") {
             Ok(file_content) => toml_edit::de::from_str::<Self>(&file_content)
-                .context("Failed to parse the `info.toml` file")?,
+                .context("Failed to parse the `info.toml` file"),
             Err(e) => {
                 if e.kind() == ErrorKind::NotFound {
                     return toml_edit::de::from_str(EMBEDDED_FILES.info_file)
                         .context("Failed to parse the embedded `info.toml` file");
                 }
 
-                return Err(Error::from(e).context("Failed to read the `info.toml` file"));
+                Err(Error::from(e).context("Failed to read the `info.toml` file"))
             }
-        };
+        }
+    }
 
-        if slf.exercises.is_empty() {
-            bail!("{NO_EXERCISES_ERR}");
+    /// The exercises sorted topologically by their prerequisites using Kahn's algorithm.
+    /// The order of `info.toml` is kept as long as an exercise comes after its prerequisites.
+    /// Prerequisites that don't exist are ignored. Fails if the prerequisites contain a cycle.
+    pub fn exercises_in_order(&self) -> Result<Vec<&ExerciseInfo>> {
+        let indices = self
+            .exercises
+            .iter()
+            .enumerate()
+            .map(|(ind, exercise_info)| (exercise_info.name.as_str(), ind))
+            .collect::<HashMap<_, _>>();
+
+        // The number of pending prerequisites of every exercise.
+        let mut n_prerequisites = vec![0_usize; self.exercises.len()];
+        let mut dependents = vec![Vec::new(); self.exercises.len()];
+        for (ind, exercise_info) in self.exercises.iter().enumerate() {
+            for prerequisite in &exercise_info.prerequisites {
+                if let Some(&prerequisite_ind) = indices.get(prerequisite.as_str()) {
+                    n_prerequisites[ind] += 1;
+                    dependents[prerequisite_ind].push(ind);
+                }
+            }
         }
 
-        Ok(slf)
+        // A min-heap to always continue with the earliest exercise in `info.toml`.
+        let mut ready = n_prerequisites
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| **n == 0)
+            .map(|(ind, _)| Reverse(ind))
+            .collect::<BinaryHeap<_>>();
+        let mut ordered = Vec::with_capacity(self.exercises.len());
+        while let Some(Reverse(ind)) = ready.pop() {
+            ordered.push(&self.exercises[ind]);
+
+            for &dependent in &dependents[ind] {
+                n_prerequisites[dependent] -= 1;
+                if n_prerequisites[dependent] == 0 {
+                    ready.push(Reverse(dependent));
+                }
+            }
+        }
+
+        if ordered.len() < self.exercises.len() {
+            let blocked = self
+                .exercises
+                .iter()
+                .zip(n_prerequisites)
+                .filter(|(_, n)| *n > 0)
+                .map(|(exercise_info, _)| exercise_info.name.as_str())
+                .collect::<Vec<_>>();
+            bail!(
+                "The prerequisites of the exercises in `info.toml` contain a cycle. These exercises are part of it or depend on it: {}",
+                blocked.join(", "),
+            );
+        }
+
+        Ok(ordered)
     }
 }
 
 const NO_EXERCISES_ERR: &str = "There are no exercises yet!
 Add at least one exercise before testing.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(info_file: &InfoFile) -> Vec<&str> {
+        info_file
+            .exercises_in_order()
+            .unwrap()
+            .into_iter()
+            .map(|exercise_info| exercise_info.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn exercises_in_order() {
+        let info_file = toml_edit::de::from_str::<InfoFile>(
+            r#"format_version = 1
+exercises = [
+  { name = "a", hint = "" },
+  { name = "b", hint = "", prerequisites = ["a", "missing"] },
+  { name = "c", hint = "" },
+]"#,
+        )
+        .unwrap();
+        assert_eq!(names(&info_file), ["a", "b", "c"]);
+
+        let info_file = toml_edit::de::from_str::<InfoFile>(
+            r#"format_version = 1
+exercises = [
+  { name = "a", hint = "", prerequisites = ["c"] },
+  { name = "b", hint = "" },
+  { name = "c", hint = "", prerequisites = ["b"] },
+  { name = "d", hint = "" },
+]"#,
+        )
+        .unwrap();
+        assert_eq!(names(&info_file), ["b", "c", "a", "d"]);
+
+        let info_file = toml_edit::de::from_str::<InfoFile>(
+            r#"format_version = 1
+exercises = [
+  { name = "a", hint = "", prerequisites = ["c"] },
+  { name = "b", hint = "" },
+  { name = "c", hint = "", prerequisites = ["a"] },
+  { name = "d", hint = "", prerequisites = ["c"] },
+]"#,
+        )
+        .unwrap();
+        let Err(e) = info_file.exercises_in_order() else {
+            panic!("The cycle wasn't detected");
+        };
+        assert!(e.to_string().ends_with(": a, c, d"), "{e}");
+    }
+}
@@ -5,16 +5,19 @@ use crossterm::{
 };
 use serde::Deserialize;
 use std::{
-    env::set_current_dir,
-    fs::{self, create_dir},
-    io::{self, Write},
+    env::{current_dir, set_current_dir},
+    fs::{self, create_dir, create_dir_all},
+    io::{self, StdoutLock, Write},
     path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
 use crate::{
-    cargo_toml::updated_cargo_toml, embedded::EMBEDDED_FILES, exercise::RunnableExercise,
-    info_file::InfoFile, term::press_enter_prompt,
+    cargo_toml::updated_cargo_toml,
+    embedded::{create_dir_if_not_exists, write_file, EMBEDDED_FILES},
+    exercise::RunnableExercise,
+    info_file::{exercises_dir, solutions_dir, InfoFile},
+    term::press_enter_prompt,
 };
 
 #[derive(Deserialize)]
@@ -22,36 +25,44 @@ struct CargoLocateProject {
     root: PathBuf,
 }
 
-pub fn init() -> Result<()> {
-    let rustlings_dir = Path::new("rustlings");
-    if rustlings_dir.exists() {
-        bail!(RUSTLINGS_DIR_ALREADY_EXISTS_ERR);
-    }
-
+fn locate_workspace_manifest(dir: &Path) -> Result<Option<PathBuf>> {
     let locate_project_output = Command::new("cargo")
         .arg("locate-project")
         .arg("-q")
         .arg("--workspace")
+        .current_dir(dir)
         .stdin(Stdio::null())
         .stderr(Stdio::null())
         .output()
         .context(CARGO_LOCATE_PROJECT_ERR)?;
 
-    let mut stdout = io::stdout().lock();
+    if !locate_project_output.status.success() {
+        return Ok(None);
+    }
+
+    let workspace_manifest =
+        serde_json::de::from_slice::<CargoLocateProject>(&locate_project_output.stdout)
+            .context("Failed to read the field `root` from the output of `cargo locate-project …`")?
+            .root;
+
+    Ok(Some(workspace_manifest))
+}
+
+// Create the directory `rustlings/` in the current directory and change into it.
+// Returns whether Git should be initialized.
+fn enter_rustlings_dir(stdout: &mut StdoutLock) -> Result<bool> {
+    let rustlings_dir = Path::new("rustlings");
+    if rustlings_dir.exists() {
+        bail!(RUSTLINGS_DIR_ALREADY_EXISTS_ERR);
+    }
+
     let mut init_git = true;
 
-    if locate_project_output.status.success() {
+    if let Some(workspace_manifest) = locate_workspace_manifest(Path::new("."))? {
         if Path::new("exercises").exists() && Path::new("solutions").exists() {
             bail!(IN_INITIALIZED_DIR_ERR);
         }
 
-        let workspace_manifest =
-            serde_json::de::from_slice::<CargoLocateProject>(&locate_project_output.stdout)
-                .context(
-                    "Failed to read the field `root` from the output of `cargo locate-project …`",
-                )?
-                .root;
-
         let workspace_manifest_content = fs::read_to_string(&workspace_manifest)
             .with_context(|| format!("Failed to read the file {}", workspace_manifest.display()))?;
         if !workspace_manifest_content.contains("[workspace]\n")
@@ -61,7 +72,7 @@ pub fn init() -> Result<()> {
         }
 
         stdout.write_all(b"This command will create the directory `rustlings/` as a member of this Cargo workspace.\nPress ENTER to continue ")?;
-        press_enter_prompt(&mut stdout)?;
+        press_enter_prompt(stdout)?;
 
         // Make sure "rustlings" is added to `workspace.members` by making
         // Cargo initialize a new project.
@@ -84,35 +95,106 @@ pub fn init() -> Result<()> {
         init_git = false;
     } else {
         stdout.write_all(b"This command will create the directory `rustlings/` which will contain the exercises.\nPress ENTER to continue ")?;
-        press_enter_prompt(&mut stdout)?;
+        press_enter_prompt(stdout)?;
     }
 
     create_dir(rustlings_dir).context("Failed to create the `rustlings/` directory")?;
     set_current_dir(rustlings_dir)
         .context("Failed to change the current directory to `rustlings/`")?;
 
+    Ok(init_git)
+}
+
+// Check the directory passed with `--path`, create it if it doesn't exist and change into it.
+// Nothing is created if a check fails.
+fn enter_dir(path: &Path, force: bool, stdout: &mut StdoutLock) -> Result<()> {
+    let initialized = path.join("info.toml").exists()
+        || (path.join(exercises_dir()).exists() && path.join(solutions_dir()).exists());
+    if initialized && !force {
+        bail!(PATH_ALREADY_INITIALIZED_ERR);
+    }
+
+    // Only the `Cargo.toml` file of an earlier initialization is allowed.
+    // Cargo runs in the closest existing ancestor if the directory doesn't exist yet.
+    let existing_dir = path
+        .ancestors()
+        .map(|dir| {
+            if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            }
+        })
+        .find(|dir| dir.is_dir())
+        .unwrap_or(Path::new("."));
+    if let Some(workspace_manifest) = locate_workspace_manifest(existing_dir)? {
+        if fs::canonicalize(workspace_manifest).ok()
+            != fs::canonicalize(path.join("Cargo.toml")).ok()
+        {
+            bail!(
+                "The directory {} is part of a Cargo project.\nPlease initialize Rustlings in a different directory",
+                path.display(),
+            );
+        }
+    }
+
+    if !initialized
+        && path
+            .read_dir()
+            .is_ok_and(|mut entries| entries.next().is_some())
+    {
+        write!(
+            stdout,
+            "The directory {} isn't empty. This command will write the exercises into it.\nPress ENTER to continue ",
+            path.display(),
+        )?;
+        press_enter_prompt(stdout)?;
+    }
+
+    create_dir_all(path)
+        .with_context(|| format!("Failed to create the directory {}", path.display()))?;
+    set_current_dir(path).with_context(|| {
+        format!(
+            "Failed to change the current directory to {}",
+            path.display(),
+        )
+    })?;
+
+    Ok(())
+}
+
+/// Initialize the official exercises in the directory `path` or `rustlings/` in the current directory.
+/// With `force`, an already initialized `path` only gets its missing files.
+pub fn init(path: Option<&Path>, force: bool) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    let init_git = match path {
+        Some(path) => {
+            enter_dir(path, force, &mut stdout)?;
+            true
+        }
+        None => enter_rustlings_dir(&mut stdout)?,
+    };
+    let keep_existing = path.is_some() && force;
+
     let info_file = InfoFile::parse()?;
     EMBEDDED_FILES
-        .init_exercises_dir(&info_file.exercises)
-        .context("Failed to initialize the `rustlings/exercises` directory")?;
+        .init_exercises_dir(&info_file.exercises, keep_existing)
+        .context("Failed to initialize the `exercises` directory")?;
 
-    create_dir("solutions").context("Failed to create the `solutions/` directory")?;
-    fs::write(
-        "solutions/README.md",
+    let solutions_dir = solutions_dir();
+    create_dir_all(solutions_dir)
+        .with_context(|| format!("Failed to create the directory {solutions_dir}"))?;
+    write_file(
+        &format!("{solutions_dir}/README.md"),
         include_bytes!("../solutions/README.md"),
-    )
-    .context("Failed to create the file rustlings/solutions/README.md")?;
+        keep_existing,
+    )?;
     for dir in EMBEDDED_FILES.exercise_dirs {
-        let mut dir_path = String::with_capacity(10 + dir.name.len());
-        dir_path.push_str("solutions/");
-        dir_path.push_str(dir.name);
-        create_dir(&dir_path)
-            .with_context(|| format!("Failed to create the directory {dir_path}"))?;
+        create_dir_if_not_exists(&format!("{solutions_dir}/{}", dir.name))?;
     }
     for exercise_info in &info_file.exercises {
-        let solution_path = exercise_info.sol_path();
-        fs::write(&solution_path, INIT_SOLUTION_FILE)
-            .with_context(|| format!("Failed to create the file {solution_path}"))?;
+        write_file(&exercise_info.sol_path(), INIT_SOLUTION_FILE, keep_existing)?;
     }
 
     let current_cargo_toml = include_str!("../dev-Cargo.toml");
@@ -127,20 +209,19 @@ pub fn init() -> Result<()> {
         .context("The embedded `Cargo.toml` contains only one line")?;
     let updated_cargo_toml = updated_cargo_toml(&info_file.exercises, current_cargo_toml, b"")
         .context("Failed to generate `Cargo.toml`")?;
-    fs::write("Cargo.toml", updated_cargo_toml)
-        .context("Failed to create the file `rustlings/Cargo.toml`")?;
+    write_file("Cargo.toml", &updated_cargo_toml, keep_existing)?;
 
-    fs::write("rust-analyzer.toml", RUST_ANALYZER_TOML)
-        .context("Failed to create the file `rustlings/rust-analyzer.toml`")?;
+    write_file("rust-analyzer.toml", RUST_ANALYZER_TOML, keep_existing)?;
+    write_file(".gitignore", GITIGNORE, keep_existing)?;
 
-    fs::write(".gitignore", GITIGNORE)
-        .context("Failed to create the file `rustlings/.gitignore`")?;
+    create_dir_if_not_exists(".vscode")?;
+    write_file(
+        ".vscode/extensions.json",
+        VS_CODE_EXTENSIONS_JSON,
+        keep_existing,
+    )?;
 
-    create_dir(".vscode").context("Failed to create the directory `rustlings/.vscode`")?;
-    fs::write(".vscode/extensions.json", VS_CODE_EXTENSIONS_JSON)
-        .context("Failed to create the file `rustlings/.vscode/extensions.json`")?;
-
-    if init_git {
+    if init_git && !Path::new(".git").exists() {
         // Ignore any Git error because Git initialization is not required.
         let _ = Command::new("git")
             .arg("init")
@@ -156,7 +237,17 @@ pub fn init() -> Result<()> {
     stdout.write_all(b"\n\n")?;
 
     stdout.queue(SetAttribute(Attribute::Bold))?;
-    stdout.write_all(POST_INIT_MSG)?;
+    if path.is_some() {
+        let dir = current_dir().context("Failed to get the current directory")?;
+        writeln!(
+            stdout,
+            "The exercises are in the directory {}\nRun `cd {}` to go into it.\nThen run `rustlings` to get started.",
+            dir.display(),
+            dir.display(),
+        )?;
+    } else {
+        stdout.write_all(POST_INIT_MSG)?;
+    }
     stdout.queue(ResetColor)?;
 
     Ok(())
@@ -179,6 +270,7 @@ check.extraArgs = ["--profile", "test"]
 const GITIGNORE: &[u8] = b"Cargo.lock
 target/
 .vscode/
+.rustlings-cache/
 ";
 
 pub const VS_CODE_EXTENSIONS_JSON: &[u8] = br#"{"recommendations":["rust-lang.rust-analyzer"]}"#;
@@ -188,6 +280,9 @@ const IN_INITIALIZED_DIR_ERR: &str = "It looks like Rustlings is already initial
 If you already initialized Rustlings, run the command `rustlings` for instructions on getting started with the exercises.
 Otherwise, please run `rustlings init` again in a different directory.";
 
+const PATH_ALREADY_INITIALIZED_ERR: &str = "Rustlings is already initialized in this directory.
+Run `rustlings init --path <DIR> --force` to only add missing files without overwriting the existing ones";
+
 const RUSTLINGS_DIR_ALREADY_EXISTS_ERR: &str =
     "A directory with the name `rustlings` already exists in the current directory.
 You probably already initialized Rustlings.
@@ -16,6 +16,9 @@ use crate::app_state::AppState;
 
 use self::state::{Filter, ListState};
 
+pub use self::print::print;
+
+mod print;
 mod scroll_state;
 mod state;
 
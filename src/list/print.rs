@@ -0,0 +1,237 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::{
+    collections::BTreeSet,
+    io::{self, StdoutLock, Write},
+};
+
+use crate::{
+    app_state::done_exercise_names,
+    cli::{ListArgs, ListOutput, ListSort},
+    exercise::RunnableExercise,
+    info_file::{ExerciseInfo, InfoFile},
+};
+
+/// An exercise in the JSON output.
+#[derive(Serialize)]
+struct ExerciseJson<'a> {
+    name: &'a str,
+    dir: Option<&'a str>,
+    path: String,
+    hints: &'a [String],
+    test: bool,
+    skip_check_unsolved: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    difficulty: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_minutes: Option<u8>,
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_url: Option<&'a str>,
+}
+
+impl<'a> From<&'a ExerciseInfo> for ExerciseJson<'a> {
+    fn from(exercise_info: &'a ExerciseInfo) -> Self {
+        Self {
+            name: &exercise_info.name,
+            dir: exercise_info.dir.as_deref(),
+            path: exercise_info.path(),
+            hints: &exercise_info.hints,
+            test: exercise_info.test(),
+            skip_check_unsolved: exercise_info.skip_check_unsolved,
+            difficulty: exercise_info.difficulty,
+            estimated_minutes: exercise_info.estimated_minutes,
+            tags: &exercise_info.tags,
+            group: exercise_info.group.as_deref(),
+            author: exercise_info.author.as_deref(),
+            source_url: exercise_info.source_url.as_deref(),
+        }
+    }
+}
+
+/// Print all exercises in a table without entering the interactive list.
+pub fn print(info_file: &InfoFile, args: ListArgs) -> Result<()> {
+    // The number is the position of the exercise in `info.toml`.
+    let mut exercises = (1..)
+        .zip(&info_file.exercises)
+        .filter(|(_, exercise_info)| {
+            args.tag
+                .as_ref()
+                .map_or(true, |tag| exercise_info.tags.contains(tag))
+                && args
+                    .group
+                    .as_ref()
+                    .map_or(true, |group| exercise_info.group.as_ref() == Some(group))
+        })
+        .collect::<Vec<(usize, &ExerciseInfo)>>();
+
+    if let Some(tag) = &args.tag {
+        if exercises.is_empty() {
+            let tags = info_file
+                .exercises
+                .iter()
+                .flat_map(|exercise_info| &exercise_info.tags)
+                .map(String::as_str)
+                .collect::<BTreeSet<_>>();
+
+            if tags.is_empty() {
+                bail!("No exercises found with tag `{tag}`. The exercises don't have any tags");
+            }
+
+            let tags = tags.into_iter().collect::<Vec<_>>().join(", ");
+            bail!("No exercises found with tag `{tag}`. Available tags: {tags}");
+        }
+    }
+
+    if let Some(group) = &args.group {
+        if !info_file
+            .groups
+            .iter()
+            .any(|group_info| group_info.name == *group)
+        {
+            if info_file.groups.is_empty() {
+                bail!("The group `{group}` doesn't exist. The exercises don't have any groups");
+            }
+
+            let groups = info_file
+                .groups
+                .iter()
+                .map(|group_info| group_info.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!("The group `{group}` doesn't exist. Available groups: {groups}");
+        }
+    }
+
+    match args.sort {
+        // Stable sort to keep the `info.toml` order for the same difficulty.
+        Some(ListSort::Difficulty) => {
+            exercises.sort_by_key(|(_, exercise_info)| exercise_info.difficulty.unwrap_or(u8::MAX))
+        }
+        None => (),
+    }
+
+    let mut stdout = io::stdout().lock();
+
+    if let ListOutput::Json = args.output {
+        let exercises = exercises
+            .into_iter()
+            .map(|(_, exercise_info)| ExerciseJson::from(exercise_info))
+            .collect::<Vec<_>>();
+        serde_json::to_writer_pretty(&mut stdout, &exercises)?;
+        stdout.write_all(b"\n")?;
+
+        return Ok(());
+    }
+
+    let name_col_width = info_file.exercises.iter().fold(4, |width, exercise_info| {
+        width.max(exercise_info.name.len())
+    });
+
+    write!(stdout, "Num  Difficulty  {:name_col_width$}  ", "Name")?;
+    // Only needed to align the time column.
+    let path_col_width = if args.show_time {
+        let path_col_width = exercises.iter().fold(4, |width, (_, exercise_info)| {
+            width.max(exercise_info.path().len())
+        });
+        writeln!(stdout, "{:path_col_width$}  Time", "Path")?;
+        path_col_width
+    } else {
+        stdout.write_all(b"Path\n")?;
+        0
+    };
+
+    // Only the shown exercises are included.
+    let mut total_minutes = 0;
+    let mut print_rows = |stdout: &mut StdoutLock, exercises: &[(usize, &ExerciseInfo)]| {
+        for (num, exercise_info) in exercises {
+            let difficulty = exercise_info
+                .difficulty
+                .map_or(String::from("-"), |difficulty| difficulty.to_string());
+
+            write!(
+                stdout,
+                "{num:>3}  {difficulty:<10}  {:name_col_width$}  ",
+                exercise_info.name,
+            )?;
+
+            if !args.show_time {
+                writeln!(stdout, "{}", exercise_info.path())?;
+                continue;
+            }
+
+            let time = match exercise_info.estimated_minutes {
+                Some(minutes) => {
+                    total_minutes += u32::from(minutes);
+                    format!("{minutes} min")
+                }
+                None => String::from("-"),
+            };
+            writeln!(stdout, "{:path_col_width$}  {time}", exercise_info.path())?;
+        }
+
+        Ok::<_, io::Error>(())
+    };
+
+    if info_file.groups.is_empty() {
+        print_rows(&mut stdout, &exercises)?;
+    } else {
+        let done_exercise_names = done_exercise_names();
+
+        for group_info in &info_file.groups {
+            let group_exercises = exercises
+                .iter()
+                .filter(|(_, exercise_info)| exercise_info.group.as_ref() == Some(&group_info.name))
+                .copied()
+                .collect::<Vec<_>>();
+            if group_exercises.is_empty() {
+                continue;
+            }
+
+            let n_done = group_exercises
+                .iter()
+                .filter(|(_, exercise_info)| done_exercise_names.contains(&exercise_info.name))
+                .count();
+            writeln!(
+                stdout,
+                "\n{}: {} ({n_done}/{} done)",
+                group_info.name,
+                group_info.description,
+                group_exercises.len(),
+            )?;
+
+            // Collapse groups that are done.
+            if n_done < group_exercises.len() || args.expand || args.group.is_some() {
+                print_rows(&mut stdout, &group_exercises)?;
+            }
+        }
+
+        // Including the exercises with an undefined group which `dev check` reports.
+        let ungrouped_exercises = exercises
+            .iter()
+            .filter(|(_, exercise_info)| {
+                exercise_info.group.as_ref().map_or(true, |group| {
+                    !info_file
+                        .groups
+                        .iter()
+                        .any(|group_info| group_info.name == *group)
+                })
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        if !ungrouped_exercises.is_empty() {
+            stdout.write_all(b"\nOther exercises\n")?;
+            print_rows(&mut stdout, &ungrouped_exercises)?;
+        }
+    }
+
+    if args.show_time {
+        writeln!(stdout, "\nTotal estimated time: {total_minutes} minutes")?;
+    }
+
+    Ok(())
+}
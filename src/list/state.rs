@@ -175,6 +175,9 @@ impl<'a> ListState<'a> {
             if exercise.done {
                 writer.stdout.queue(SetForegroundColor(Color::Green))?;
                 writer.write_ascii(b"DONE   ")?;
+            } else if exercise.skipped {
+                writer.stdout.queue(SetForegroundColor(Color::Cyan))?;
+                writer.write_ascii(b"SKIPPED")?;
             } else {
                 writer.stdout.queue(SetForegroundColor(Color::Yellow))?;
                 writer.write_ascii(b"PENDING")?;
@@ -1,69 +1,51 @@
 use anyhow::{bail, Context, Result};
 use app_state::StateFileStatus;
-use clap::{Parser, Subcommand};
+use clap::Parser;
+use clap_complete::CompleteEnv;
 use std::{
-    io::{self, IsTerminal, Write},
+    io::{self, Write},
     path::Path,
     process::ExitCode,
+    time::Duration,
 };
 use term::{clear_terminal, press_enter_prompt};
 
-use self::{app_state::AppState, dev::DevCommands, info_file::InfoFile};
+use self::{
+    app_state::AppState,
+    cli::{Args, Subcommands},
+    info_file::InfoFile,
+};
 
 mod app_state;
+mod cache;
 mod cargo_toml;
+mod cli;
 mod cmd;
+mod completions;
 mod dev;
+mod diff;
+mod doctor;
 mod embedded;
 mod exercise;
+mod export;
+mod hash;
+mod info;
 mod info_file;
 mod init;
 mod list;
+mod pack;
 mod run;
+mod stats;
 mod term;
+mod verify;
 mod watch;
 
 const CURRENT_FORMAT_VERSION: u8 = 1;
 
-/// Rustlings is a collection of small exercises to get you used to writing and reading Rust code
-#[derive(Parser)]
-#[command(version)]
-struct Args {
-    #[command(subcommand)]
-    command: Option<Subcommands>,
-    /// Manually run the current exercise using `r` in the watch mode.
-    /// Only use this if Rustlings fails to detect exercise file changes.
-    #[arg(long)]
-    manual_run: bool,
-}
-
-#[derive(Subcommand)]
-enum Subcommands {
-    /// Initialize the official Rustlings exercises
-    Init,
-    /// Run a single exercise. Runs the next pending exercise if the exercise name is not specified
-    Run {
-        /// The name of the exercise
-        name: Option<String>,
-    },
-    /// Check all the exercises, marking them as done or pending accordingly.
-    CheckAll,
-    /// Reset a single exercise
-    Reset {
-        /// The name of the exercise
-        name: String,
-    },
-    /// Show a hint. Shows the hint of the next pending exercise if the exercise name is not specified
-    Hint {
-        /// The name of the exercise
-        name: Option<String>,
-    },
-    /// Commands for developing (third-party) Rustlings exercises
-    #[command(subcommand)]
-    Dev(DevCommands),
-}
-
 fn main() -> Result<ExitCode> {
+    // Answers the requests of the dynamic completion script and exits.
+    CompleteEnv::with_factory(completions::command).complete();
+
     let args = Args::parse();
 
     if cfg!(not(debug_assertions)) && Path::new("dev/rustlings-repo.txt").exists() {
@@ -72,15 +54,27 @@ fn main() -> Result<ExitCode> {
 
     'priority_cmd: {
         match args.command {
-            Some(Subcommands::Init) => init::init().context("Initialization failed")?,
-            Some(Subcommands::Dev(dev_command)) => dev_command.run()?,
+            Some(Subcommands::Init { path, force }) => {
+                init::init(path.as_deref(), force).context("Initialization failed")?;
+            }
+            Some(Subcommands::Dev(dev_command)) => return dev_command.run(),
+            Some(Subcommands::Pack(pack_command)) => pack_command.run()?,
+            Some(Subcommands::NewPack { name, no_git }) => dev::new::new_pack(&name, no_git)?,
+            Some(Subcommands::Export {
+                format,
+                include_solutions,
+                output,
+            }) => export::export(format, include_solutions, &output)?,
+            Some(Subcommands::Import { file, dir }) => export::import(&file, dir)?,
+            Some(Subcommands::Completions { shell }) => completions::print(shell)?,
+            Some(Subcommands::Doctor) => return doctor::doctor(),
             _ => break 'priority_cmd,
         }
 
         return Ok(ExitCode::SUCCESS);
     }
 
-    if !Path::new("exercises").is_dir() {
+    if !Path::new(info_file::exercises_dir()).is_dir() {
         println!("{PRE_INIT_MSG}");
         return Ok(ExitCode::FAILURE);
     }
@@ -91,15 +85,29 @@ fn main() -> Result<ExitCode> {
         bail!(FORMAT_VERSION_HIGHER_ERR);
     }
 
+    match args.command {
+        Some(Subcommands::List(list_args)) => {
+            list::print(&info_file, list_args)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(Subcommands::Info { name }) => {
+            info::print(&info_file, &name)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        _ => (),
+    }
+
+    // An exercise is never presented before its prerequisites.
     let (mut app_state, state_file_status) = AppState::new(
-        info_file.exercises,
-        info_file.final_message.unwrap_or_default(),
+        info_file.exercises_in_order()?,
+        info_file.final_message.clone().unwrap_or_default(),
+        args.sandbox,
     )?;
 
-    // Show the welcome message if the state file doesn't exist yet.
-    if let Some(welcome_message) = info_file.welcome_message {
-        match state_file_status {
-            StateFileStatus::NotRead => {
+    match state_file_status {
+        StateFileStatus::NotRead => {
+            // Show the welcome message if the state file doesn't exist yet.
+            if let Some(welcome_message) = info_file.welcome_message {
                 let mut stdout = io::stdout().lock();
                 clear_terminal(&mut stdout)?;
 
@@ -110,16 +118,25 @@ fn main() -> Result<ExitCode> {
                 // Flush to be able to show errors occurring before printing a newline to stdout.
                 stdout.flush()?;
             }
-            StateFileStatus::Read => (),
         }
+        StateFileStatus::UnknownCurrentExercise(exercise_name) => {
+            eprintln!(
+                "The exercise `{exercise_name}` from the state file doesn't exist anymore.\nContinuing at the exercise `{}`",
+                app_state.current_exercise().name,
+            );
+
+            // The watch mode clears the terminal.
+            if args.command.is_none() && term::is_ansi_terminal(&io::stdout()) {
+                let mut stdout = io::stdout().lock();
+                stdout.write_all(b"\nPress ENTER to continue ")?;
+                press_enter_prompt(&mut stdout)?;
+            }
+        }
+        StateFileStatus::Read => (),
     }
 
     match args.command {
         None => {
-            if !io::stdout().is_terminal() {
-                bail!("Unsupported or missing terminal/TTY");
-            }
-
             let notify_exercise_names = if args.manual_run {
                 None
             } else {
@@ -135,13 +152,51 @@ fn main() -> Result<ExitCode> {
                 )
             };
 
-            watch::watch(&mut app_state, notify_exercise_names)?;
+            watch::watch(
+                &mut app_state,
+                notify_exercise_names,
+                Duration::from_millis(args.debounce_ms),
+                args.notify,
+                !args.no_prefetch,
+            )?;
         }
-        Some(Subcommands::Run { name }) => {
-            if let Some(name) = name {
-                app_state.set_current_exercise_by_name(&name)?;
+        Some(Subcommands::Run {
+            name,
+            force,
+            watch,
+            json_errors,
+            no_output,
+        }) => {
+            let exercise_ind = if let Some(name) = name {
+                let exercise_ind = app_state.exercise_ind_by_name(&name)?;
+
+                if !force {
+                    let pending_prerequisites = app_state.pending_prerequisites(exercise_ind);
+                    if !pending_prerequisites.is_empty() {
+                        bail!(
+                            "The exercise `{name}` builds on the following exercises which aren't done yet: {}\nRun it with `--force` to skip them",
+                            pending_prerequisites.join(", "),
+                        );
+                    }
+                }
+
+                exercise_ind
+            } else {
+                app_state.current_exercise_ind()
+            };
+
+            // Only reports the errors of the exercise, e.g. for an editor integration.
+            // The learner's current exercise is kept.
+            if json_errors {
+                return run::print_json_errors(&app_state, exercise_ind);
+            }
+
+            app_state.set_current_exercise_ind(exercise_ind)?;
+            if watch {
+                run::run_watch(&mut app_state, Duration::from_millis(args.debounce_ms))?;
+            } else {
+                return run::run(&mut app_state, no_output);
             }
-            return run::run(&mut app_state);
         }
         Some(Subcommands::CheckAll) => {
             let mut stdout = io::stdout().lock();
@@ -171,19 +226,87 @@ fn main() -> Result<ExitCode> {
                 app_state.render_final_message(&mut stdout)?;
             }
         }
-        Some(Subcommands::Reset { name }) => {
+        Some(Subcommands::Verify) => return verify::verify(&app_state),
+        Some(Subcommands::Reset { name, yes }) => {
             app_state.set_current_exercise_by_name(&name)?;
+
+            if !yes {
+                let mut stdout = io::stdout().lock();
+                write!(
+                    stdout,
+                    "Resetting will undo all your changes to the file {}\nReset (y/n)? ",
+                    app_state.current_exercise().path,
+                )?;
+                stdout.flush()?;
+
+                let mut answer = String::new();
+                io::stdin()
+                    .read_line(&mut answer)
+                    .context("Failed to read the user's input")?;
+
+                if !matches!(answer.trim_ascii(), "y" | "Y") {
+                    stdout.write_all(b"Reset canceled\n")?;
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+
             let exercise_path = app_state.reset_current_exercise()?;
             println!("The exercise {exercise_path} has been reset");
         }
-        Some(Subcommands::Hint { name }) => {
+        Some(Subcommands::Hint { name, level, all }) => {
             if let Some(name) = name {
                 app_state.set_current_exercise_by_name(&name)?;
             }
-            println!("{}", app_state.current_exercise().hint);
+
+            let exercise = app_state.current_exercise();
+            let pending_prerequisites =
+                app_state.pending_prerequisites(app_state.current_exercise_ind());
+            if !pending_prerequisites.is_empty() {
+                eprintln!(
+                    "Note: The exercise `{}` builds on the following exercises which aren't done yet: {}\n",
+                    exercise.name,
+                    pending_prerequisites.join(", "),
+                );
+            }
+
+            let n_levels = exercise.hints.len();
+            if all {
+                for (level, hint) in (1..).zip(&exercise.hints) {
+                    println!("Hint {level}/{n_levels}:\n{hint}\n");
+                }
+            } else {
+                let Some(hint) = level.checked_sub(1).and_then(|ind| exercise.hints.get(ind))
+                else {
+                    bail!(
+                        "The level must be between 1 and {n_levels} for the exercise `{}`",
+                        exercise.name,
+                    );
+                };
+                println!("{hint}");
+            }
         }
-        // Handled in an earlier match.
-        Some(Subcommands::Init | Subcommands::Dev(_)) => (),
+        Some(Subcommands::Stats {
+            json,
+            export,
+            out,
+            include_incomplete,
+        }) => match export {
+            Some(format) => stats::export(&app_state, format, include_incomplete, out.as_deref())?,
+            None => stats::stats(&app_state, json)?,
+        },
+        // Handled earlier.
+        Some(
+            Subcommands::Init { .. }
+            | Subcommands::List(_)
+            | Subcommands::Info { .. }
+            | Subcommands::Pack(_)
+            | Subcommands::Dev(_)
+            | Subcommands::NewPack { .. }
+            | Subcommands::Export { .. }
+            | Subcommands::Import { .. }
+            | Subcommands::Completions { .. }
+            | Subcommands::Doctor,
+        ) => (),
     }
 
     Ok(ExitCode::SUCCESS)
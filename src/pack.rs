@@ -0,0 +1,117 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::HashSet,
+    fs::{self, create_dir_all},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::{
+    cli::{Editor, PackCommands},
+    dev::update,
+    hash::sha256_hex,
+    info_file::{InfoFile, PackFile, PackInfo, PACKS_DIR},
+};
+
+impl PackCommands {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Self::Fetch => fetch(),
+        }
+    }
+}
+
+// Download the content of the URL with `curl` which is available on all supported platforms.
+fn download(url: &str) -> Result<Vec<u8>> {
+    let output = Command::new("curl")
+        .arg("--fail")
+        .arg("--silent")
+        .arg("--show-error")
+        .arg("--location")
+        // Also for redirections.
+        .arg("--proto")
+        .arg("=https")
+        .arg("--")
+        .arg(url)
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run `curl`. Make sure that it is installed")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to download {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim_ascii(),
+        );
+    }
+
+    Ok(output.stdout)
+}
+
+// Download the pack's fragment and verify it. Return the fragment's content.
+fn fetch_pack(pack: &PackInfo, exercise_names: &mut HashSet<String>) -> Result<String> {
+    let name = &pack.name;
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        bail!("The pack name `{name}` must be nonempty and only contain alphanumeric characters and `_`");
+    }
+    if !pack.url.starts_with("https://") {
+        bail!("The URL of the pack `{name}` must start with `https://`");
+    }
+
+    let content = download(&pack.url)?;
+
+    let checksum = sha256_hex(&content);
+    if !checksum.eq_ignore_ascii_case(pack.sha256.trim_ascii()) {
+        bail!(
+            "The SHA-256 checksum of the pack `{name}` doesn't match.\nExpected: {}\nDownloaded: {checksum}",
+            pack.sha256,
+        );
+    }
+
+    let content = String::from_utf8(content)
+        .with_context(|| format!("The `info.toml` fragment of the pack `{name}` isn't UTF-8"))?;
+    for exercise_info in PackFile::parse(name, &content)?.exercises {
+        if !exercise_names.insert(exercise_info.name) {
+            bail!("An exercise of the pack `{name}` has a name that is already used by another exercise");
+        }
+    }
+
+    Ok(content)
+}
+
+fn fetch() -> Result<()> {
+    if !Path::new("info.toml").exists() {
+        bail!("No `info.toml` file found. Packs are only supported for third-party exercises");
+    }
+
+    let info_file = InfoFile::parse_without_packs()?;
+    if info_file.packs.is_empty() {
+        bail!("There are no packs in `info.toml`. Add them as `[[packs]]` entries with `name`, `url` and `sha256`");
+    }
+
+    let mut exercise_names = info_file
+        .exercises
+        .iter()
+        .map(|exercise_info| exercise_info.name.clone())
+        .collect::<HashSet<_>>();
+    let mut pack_names = HashSet::with_capacity(info_file.packs.len());
+    let mut fragments = Vec::with_capacity(info_file.packs.len());
+    for pack in &info_file.packs {
+        if !pack_names.insert(pack.name.as_str()) {
+            bail!("The pack name `{}` is duplicated in `info.toml`", pack.name);
+        }
+
+        fragments.push((pack, fetch_pack(pack, &mut exercise_names)?));
+    }
+
+    // Only write after all packs were verified to not leave a mix of old and new fragments.
+    create_dir_all(PACKS_DIR)
+        .with_context(|| format!("Failed to create the `{PACKS_DIR}` directory"))?;
+    for (pack, content) in fragments {
+        let path = pack.path();
+        fs::write(&path, content).with_context(|| format!("Failed to write the file {path}"))?;
+        println!("Fetched the pack `{}` into {path}", pack.name);
+    }
+
+    // `update` parses `info.toml` with the fetched packs.
+    update::update(false, false, Editor::None)
+}
@@ -1,36 +1,38 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use crossterm::{
     style::{Color, ResetColor, SetForegroundColor},
     QueueableCommand,
 };
+use serde::Deserialize;
 use std::{
-    io::{self, Write},
+    io::{self, StdoutLock, Write},
     process::ExitCode,
+    time::Duration,
 };
 
 use crate::{
     app_state::{AppState, ExercisesProgress},
     exercise::{solution_link_line, RunnableExercise, OUTPUT_CAPACITY},
+    term::clear_terminal,
+    watch::{ExerciseFileWatcher, InputPauseGuard},
 };
 
-pub fn run(app_state: &mut AppState) -> Result<ExitCode> {
+// Run the current exercise and print its output. Returns `true` on success.
+fn run_current_exercise(app_state: &mut AppState, stdout: &mut StdoutLock) -> Result<bool> {
     let exercise = app_state.current_exercise();
     let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
     let success = exercise.run_exercise(Some(&mut output), app_state.cmd_runner())?;
 
-    let mut stdout = io::stdout().lock();
     stdout.write_all(&output)?;
 
     if !success {
         app_state.set_pending(app_state.current_exercise_ind())?;
 
         stdout.write_all(b"Ran ")?;
-        app_state
-            .current_exercise()
-            .terminal_file_link(&mut stdout)?;
+        app_state.current_exercise().terminal_file_link(stdout)?;
         stdout.write_all(b" with errors\n")?;
 
-        return Ok(ExitCode::FAILURE);
+        return Ok(false);
     }
 
     stdout.queue(SetForegroundColor(Color::Green))?;
@@ -39,6 +41,39 @@ pub fn run(app_state: &mut AppState) -> Result<ExitCode> {
     stdout.queue(ResetColor)?;
     stdout.write_all(b"\n")?;
 
+    Ok(true)
+}
+
+// Run the current exercise without its output and only print `PASS` or `FAIL` with its name.
+// The current exercise isn't advanced to the next pending one after it passes.
+fn run_without_output(app_state: &mut AppState) -> Result<ExitCode> {
+    let exercise = app_state.current_exercise();
+    let name = exercise.name;
+    let success = exercise.run_exercise(None, app_state.cmd_runner())?;
+
+    let exercise_ind = app_state.current_exercise_ind();
+    if success {
+        app_state.set_done(exercise_ind)?;
+        println!("PASS {name}");
+        Ok(ExitCode::SUCCESS)
+    } else {
+        app_state.set_pending(exercise_ind)?;
+        println!("FAIL {name}");
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+/// With `no_output`, only a line with `PASS` or `FAIL` and the exercise name is printed.
+pub fn run(app_state: &mut AppState, no_output: bool) -> Result<ExitCode> {
+    if no_output {
+        return run_without_output(app_state);
+    }
+
+    let mut stdout = io::stdout().lock();
+    if !run_current_exercise(app_state, &mut stdout)? {
+        return Ok(ExitCode::FAILURE);
+    }
+
     if let Some(solution_path) = app_state.current_solution_path()? {
         stdout.write_all(b"\n")?;
         solution_link_line(&mut stdout, &solution_path)?;
@@ -58,3 +93,86 @@ pub fn run(app_state: &mut AppState) -> Result<ExitCode> {
 
     Ok(ExitCode::SUCCESS)
 }
+
+// The parts of a line of `cargo build --message-format json` that are needed.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    /// The diagnostic in the JSON format of `rustc`.
+    message: Option<serde_json::Value>,
+}
+
+/// Only build the exercise without making it the current one and print the compiler diagnostics as a JSON array
+/// with the original fields of `rustc` like `message`, `level` and `spans`.
+pub fn print_json_errors(app_state: &AppState, exercise_ind: usize) -> Result<ExitCode> {
+    let exercise = &app_state.exercises()[exercise_ind];
+    let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+    let mut build_cmd = app_state.cmd_runner().cargo(
+        "build",
+        exercise.name,
+        &exercise.features,
+        Some(&mut output),
+        None,
+    );
+    build_cmd.args(["--message-format", "json"]);
+    let success = build_cmd.run("cargo build …");
+    app_state.cmd_runner().clean_sandbox(exercise.name);
+    let success = success?;
+
+    // Lines that aren't JSON are from Cargo's human-readable output on stderr.
+    let diagnostics = output
+        .split(|c| *c == b'\n')
+        .filter_map(|line| serde_json::from_slice::<CargoMessage>(line).ok())
+        .filter(|message| message.reason == "compiler-message")
+        .filter_map(|message| message.message)
+        .collect::<Vec<_>>();
+
+    if !success && diagnostics.is_empty() {
+        // Failed without any diagnostic, e.g. because of a Cargo error.
+        bail!(
+            "Failed to build the exercise {}:\n{}",
+            exercise.name,
+            String::from_utf8_lossy(&output),
+        );
+    }
+
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &diagnostics)?;
+    stdout.write_all(b"\n")?;
+
+    Ok(if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Rerun the current exercise whenever its file or one of its extra files is updated.
+/// File changes are detected and debounced for `debounce_duration` like in the watch mode.
+/// Unlike the watch mode, it stays on the exercise after it succeeds.
+/// The terminal isn't modified. Therefore, `Ctrl+C` can exit without restoring it.
+pub fn run_watch(app_state: &mut AppState, debounce_duration: Duration) -> Result<()> {
+    let file_watcher = ExerciseFileWatcher::build(app_state, debounce_duration)?;
+    let exercise_ind = app_state.current_exercise_ind();
+
+    let mut stdout = io::stdout().lock();
+    loop {
+        clear_terminal(&mut stdout)?;
+        let success = {
+            // Ignore file changes until running the exercise is done.
+            let _input_pause_guard = InputPauseGuard::scoped_pause();
+            run_current_exercise(app_state, &mut stdout)?
+        };
+        if success {
+            app_state.set_done(exercise_ind)?;
+        }
+        write!(
+            stdout,
+            "\nWatching {} for changes. Press Ctrl+C to exit\n",
+            app_state.current_exercise().path,
+        )?;
+        stdout.flush()?;
+
+        file_watcher.wait_for_change(exercise_ind)?;
+    }
+}
@@ -0,0 +1,401 @@
+use anyhow::{Context, Result};
+use chrono::{Local, TimeZone};
+use crossterm::{
+    style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor},
+    QueueableCommand,
+};
+use serde::Serialize;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::{self, StdoutLock, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{app_state::AppState, cli::StatsExportFormat, term::format_duration};
+
+pub const SECS_PER_DAY: u64 = 24 * 60 * 60;
+// The exercises with the most time spent on them that are listed below the total.
+const N_LONGEST_EXERCISES: usize = 3;
+
+/// The completion of the exercises with a tag.
+#[derive(Serialize)]
+struct TagStats<'a> {
+    tag: &'a str,
+    n_exercises: usize,
+    n_done: usize,
+}
+
+/// The time spent on an exercise in the watch mode.
+#[derive(Serialize)]
+struct ExerciseTime<'a> {
+    name: &'a str,
+    secs: u64,
+}
+
+/// The output of the `stats` command.
+#[derive(Serialize)]
+struct Stats<'a> {
+    n_exercises: usize,
+    n_done: u16,
+    completion_percentage: f64,
+    /// Consecutive days with at least one done exercise until today.
+    current_streak_days: u64,
+    tags: Vec<TagStats<'a>>,
+    total_secs_spent: u64,
+    /// Only exercises with tracked time in the order of `info.toml`.
+    exercise_times: Vec<ExerciseTime<'a>>,
+}
+
+/// Convert days since the Unix epoch to the year, month and day.
+/// See https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+pub fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+// ISO 8601 in UTC like `2024-05-17T08:03:09Z`.
+fn utc_date_time(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days(timestamp / SECS_PER_DAY);
+    let secs_of_day = timestamp % SECS_PER_DAY;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+    )
+}
+
+// Count the consecutive days with at least one completion that end today.
+// The streak isn't broken yet if the last completion was yesterday.
+// Days are dates in the time zone `tz` which is the local one outside of tests.
+fn current_streak<Tz: TimeZone>(completion_timestamps: &[u64], now: u64, tz: &Tz) -> u64 {
+    let date = |timestamp: u64| {
+        let timestamp = i64::try_from(timestamp).ok()?;
+        Some(tz.timestamp_opt(timestamp, 0).single()?.date_naive())
+    };
+
+    let days = completion_timestamps
+        .iter()
+        .filter_map(|timestamp| date(*timestamp))
+        .collect::<HashSet<_>>();
+
+    let Some(today) = date(now) else {
+        return 0;
+    };
+    let mut day = if days.contains(&today) {
+        today
+    } else {
+        let Some(yesterday) = today.pred_opt() else {
+            return 0;
+        };
+        yesterday
+    };
+
+    let mut streak = 0;
+    while days.contains(&day) {
+        streak += 1;
+        let Some(previous_day) = day.pred_opt() else {
+            break;
+        };
+        day = previous_day;
+    }
+
+    streak
+}
+
+#[inline]
+fn percentage(n_done: usize, n_exercises: usize) -> f64 {
+    if n_exercises == 0 {
+        return 0.0;
+    }
+
+    100.0 * n_done as f64 / n_exercises as f64
+}
+
+// Green if everything is done, yellow if some exercises are done and red otherwise.
+fn write_colored_percentage(stdout: &mut StdoutLock, percentage: f64) -> io::Result<()> {
+    let color = if percentage >= 100.0 {
+        Color::Green
+    } else if percentage > 0.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    stdout.queue(SetForegroundColor(color))?;
+    write!(stdout, "{percentage:.1}%")?;
+    stdout.queue(ResetColor)?;
+
+    Ok(())
+}
+
+fn print_table(stdout: &mut StdoutLock, stats: &Stats) -> io::Result<()> {
+    stdout.queue(SetAttribute(Attribute::Bold))?;
+    stdout.write_all(b"Exercises done: ")?;
+    stdout.queue(ResetColor)?;
+    write!(stdout, "{}/{} (", stats.n_done, stats.n_exercises)?;
+    write_colored_percentage(stdout, stats.completion_percentage)?;
+    stdout.write_all(b")\n")?;
+
+    stdout.queue(SetAttribute(Attribute::Bold))?;
+    stdout.write_all(b"Current streak: ")?;
+    stdout.queue(ResetColor)?;
+    match stats.current_streak_days {
+        1 => stdout.write_all(b"1 day\n")?,
+        days => writeln!(stdout, "{days} days")?,
+    }
+
+    stdout.queue(SetAttribute(Attribute::Bold))?;
+    stdout.write_all(b"Time spent: ")?;
+    stdout.queue(ResetColor)?;
+    writeln!(stdout, "{}", format_duration(stats.total_secs_spent))?;
+
+    let mut longest = stats.exercise_times.iter().collect::<Vec<_>>();
+    longest.sort_by_key(|exercise_time| Reverse(exercise_time.secs));
+    for exercise_time in longest.into_iter().take(N_LONGEST_EXERCISES) {
+        writeln!(
+            stdout,
+            "  {}  {}",
+            format_duration(exercise_time.secs),
+            exercise_time.name,
+        )?;
+    }
+
+    if stats.tags.is_empty() {
+        return Ok(());
+    }
+
+    let done_col = stats
+        .tags
+        .iter()
+        .map(|tag_stats| format!("{}/{}", tag_stats.n_done, tag_stats.n_exercises))
+        .collect::<Vec<_>>();
+    let tag_col_width = stats
+        .tags
+        .iter()
+        .fold(3, |width, tag_stats| width.max(tag_stats.tag.len()));
+    let done_col_width = done_col.iter().fold(4, |width, done| width.max(done.len()));
+
+    stdout.write_all(b"\n")?;
+    stdout.queue(SetAttribute(Attribute::Bold))?;
+    write!(
+        stdout,
+        "{:tag_col_width$}  {:done_col_width$}  Rate",
+        "Tag", "Done",
+    )?;
+    stdout.queue(ResetColor)?;
+    stdout.write_all(b"\n")?;
+
+    for (tag_stats, done) in stats.tags.iter().zip(done_col) {
+        write!(
+            stdout,
+            "{:tag_col_width$}  {done:done_col_width$}  ",
+            tag_stats.tag,
+        )?;
+        write_colored_percentage(stdout, percentage(tag_stats.n_done, tag_stats.n_exercises))?;
+        stdout.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+/// Print the progress of the user.
+pub fn stats(app_state: &AppState, json: bool) -> Result<()> {
+    let exercises = app_state.exercises();
+
+    let mut tags = BTreeMap::<&str, TagStats>::new();
+    for exercise in exercises {
+        for tag in &exercise.tags {
+            let tag_stats = tags.entry(tag).or_insert(TagStats {
+                tag,
+                n_exercises: 0,
+                n_done: 0,
+            });
+            tag_stats.n_exercises += 1;
+            tag_stats.n_done += usize::from(exercise.done);
+        }
+    }
+
+    // A clock before the Unix epoch breaks every streak.
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let exercise_times = exercises
+        .iter()
+        .filter(|exercise| exercise.secs_spent > 0)
+        .map(|exercise| ExerciseTime {
+            name: exercise.name,
+            secs: exercise.secs_spent,
+        })
+        .collect::<Vec<_>>();
+
+    let stats = Stats {
+        n_exercises: exercises.len(),
+        n_done: app_state.n_done(),
+        completion_percentage: percentage(usize::from(app_state.n_done()), exercises.len()),
+        current_streak_days: current_streak(app_state.completion_timestamps(), now, &Local),
+        tags: tags.into_values().collect(),
+        total_secs_spent: exercise_times
+            .iter()
+            .map(|exercise_time| exercise_time.secs)
+            .sum(),
+        exercise_times,
+    };
+
+    let mut stdout = io::stdout().lock();
+    if json {
+        serde_json::to_writer_pretty(&mut stdout, &stats)?;
+        stdout.write_all(b"\n")?;
+    } else {
+        print_table(&mut stdout, &stats)?;
+    }
+
+    Ok(())
+}
+
+// Quote the field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if !field.contains([',', '"', '\n', '\r']) {
+        return String::from(field);
+    }
+
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+fn write_csv(
+    writer: &mut impl Write,
+    app_state: &AppState,
+    include_incomplete: bool,
+) -> io::Result<()> {
+    writer.write_all(b"exercise_name,completed,timestamp_utc,duration_seconds\n")?;
+
+    for exercise in app_state.exercises() {
+        if !exercise.done && !include_incomplete {
+            continue;
+        }
+
+        let mut timestamp = String::new();
+        let mut duration = String::new();
+        if exercise.done {
+            if let Some(completed_at) = exercise.completed_at {
+                timestamp = utc_date_time(completed_at);
+                // Exercises started before tracking the start have no duration.
+                if let Some(duration_secs) = exercise
+                    .started_at
+                    .and_then(|started_at| completed_at.checked_sub(started_at))
+                {
+                    duration = duration_secs.to_string();
+                }
+            }
+        }
+
+        writeln!(
+            writer,
+            "{},{},{timestamp},{duration}",
+            csv_field(exercise.name),
+            exercise.done,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Export the progress as CSV with a row per done exercise to stdout or the file.
+/// `include_incomplete` adds rows for pending exercises with empty time fields.
+pub fn export(
+    app_state: &AppState,
+    format: StatsExportFormat,
+    include_incomplete: bool,
+    out: Option<&Path>,
+) -> Result<()> {
+    let StatsExportFormat::Csv = format;
+
+    let Some(out) = out else {
+        let mut stdout = io::stdout().lock();
+        write_csv(&mut stdout, app_state, include_incomplete)?;
+        return Ok(());
+    };
+
+    let mut csv = Vec::with_capacity(64 * app_state.exercises().len());
+    write_csv(&mut csv, app_state, include_incomplete)?;
+    fs::write(out, csv).with_context(|| format!("Failed to write the file {}", out.display()))?;
+    println!("Exported the progress to {}", out.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, Utc};
+
+    use super::*;
+
+    #[test]
+    fn streak() {
+        let day = |n: u64| n * SECS_PER_DAY;
+        let now = day(100) + 3600;
+
+        assert_eq!(current_streak(&[], now, &Utc), 0);
+        assert_eq!(current_streak(&[day(100)], now, &Utc), 1);
+        // Not broken before the end of today.
+        assert_eq!(current_streak(&[day(99)], now, &Utc), 1);
+        assert_eq!(current_streak(&[day(98)], now, &Utc), 0);
+        assert_eq!(
+            current_streak(
+                &[day(97), day(98) + 10, day(98) + 20, day(99), day(100)],
+                now,
+                &Utc,
+            ),
+            4,
+        );
+        assert_eq!(current_streak(&[day(96), day(98), day(99)], now, &Utc), 2);
+        assert_eq!(current_streak(&[0], 10, &Utc), 1);
+    }
+
+    #[test]
+    fn streak_local_midnight() {
+        // Local midnight of the day 100 is at 22:00 UTC of the day 99.
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        let midnight = 100 * SECS_PER_DAY - 2 * 3600;
+        let now = midnight + 3600;
+
+        // The same UTC day but consecutive local days.
+        assert_eq!(
+            current_streak(&[midnight - 60, midnight + 60], now, &Utc),
+            1
+        );
+        assert_eq!(current_streak(&[midnight - 60, midnight + 60], now, &tz), 2);
+        // Yesterday in local time but two days ago in UTC.
+        let late_now = midnight + 2 * SECS_PER_DAY - 3600;
+        assert_eq!(current_streak(&[midnight + 60], late_now, &tz), 1);
+        assert_eq!(current_streak(&[midnight + 60], late_now, &Utc), 0);
+    }
+
+    #[test]
+    fn date_times() {
+        assert_eq!(utc_date_time(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_date_time(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(utc_date_time(1_715_932_989), "2024-05-17T08:03:09Z");
+    }
+
+    #[test]
+    fn csv_fields() {
+        assert_eq!(csv_field("intro1"), "intro1");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+}
@@ -5,8 +5,8 @@ use crossterm::{
     Command, QueueableCommand,
 };
 use std::{
-    fmt, fs,
-    io::{self, BufRead, StdoutLock, Write},
+    env, fmt, fs,
+    io::{self, BufRead, IsTerminal, StdoutLock, Write},
 };
 
 use crate::app_state::CheckProgress;
@@ -89,7 +89,8 @@ impl<'a> CountedWrite<'a> for StdoutLock<'a> {
 
 pub struct CheckProgressVisualizer<'a, 'lock> {
     stdout: &'a mut StdoutLock<'lock>,
-    n_cols: usize,
+    // `None` without a terminal to only show the result afterwards.
+    n_cols: Option<usize>,
 }
 
 impl<'a, 'lock> CheckProgressVisualizer<'a, 'lock> {
@@ -97,9 +98,17 @@ impl<'a, 'lock> CheckProgressVisualizer<'a, 'lock> {
     const DONE_COLOR: Color = Color::Green;
     const PENDING_COLOR: Color = Color::Red;
 
-    pub fn build(stdout: &'a mut StdoutLock<'lock>, term_width: u16) -> io::Result<Self> {
+    /// `term_width` is `None` without a terminal.
+    pub fn build(stdout: &'a mut StdoutLock<'lock>, term_width: Option<u16>) -> io::Result<Self> {
         clear_terminal(stdout)?;
         stdout.write_all("Checking all exercises…\n".as_bytes())?;
+        let Some(term_width) = term_width else {
+            stdout.flush()?;
+            return Ok(Self {
+                stdout,
+                n_cols: None,
+            });
+        };
 
         // Legend
         stdout.write_all(b"Color of exercise number: ")?;
@@ -120,10 +129,16 @@ impl<'a, 'lock> CheckProgressVisualizer<'a, 'lock> {
         // +1 because the last column doesn't end with a whitespace.
         let n_cols = usize::from(term_width + 1) / 4;
 
-        Ok(Self { stdout, n_cols })
+        Ok(Self {
+            stdout,
+            n_cols: Some(n_cols),
+        })
     }
 
     pub fn update(&mut self, progresses: &[CheckProgress]) -> io::Result<()> {
+        let Some(n_cols) = self.n_cols else {
+            return Ok(());
+        };
         self.stdout.queue(MoveTo(0, 2))?;
 
         let mut exercise_num = 1;
@@ -146,7 +161,7 @@ impl<'a, 'lock> CheckProgressVisualizer<'a, 'lock> {
             self.stdout.queue(ResetColor)?;
 
             if exercise_num != progresses.len() {
-                if exercise_num % self.n_cols == 0 {
+                if exercise_num % n_cols == 0 {
                     self.stdout.write_all(b"\n")?;
                 } else {
                     self.stdout.write_all(b" ")?;
@@ -175,10 +190,14 @@ pub fn progress_bar<'a>(
     const WRAPPER_WIDTH: u16 = PREFIX_WIDTH + POSTFIX_WIDTH;
     const MIN_LINE_WIDTH: u16 = WRAPPER_WIDTH + 4;
 
+    // Collapse to the percentage on narrow terminals.
     if term_width < MIN_LINE_WIDTH {
+        let percentage = (u32::from(progress) * 100)
+            .checked_div(u32::from(total))
+            .unwrap_or(100);
         writer.write_ascii(b"Progress: ")?;
         // Integers are in ASCII.
-        return writer.write_ascii(format!("{progress}/{total}").as_bytes());
+        return writer.write_ascii(format!("{percentage}%").as_bytes());
     }
 
     let stdout = writer.stdout();
@@ -210,7 +229,18 @@ pub fn progress_bar<'a>(
     write!(stdout, "] {progress:>3}/{total}")
 }
 
+/// Whether the output is a terminal that supports moving the cursor, clearing and links.
+/// Not the case for pipes and dumb terminals like Emacs shell buffers.
+pub fn is_ansi_terminal(output: &impl IsTerminal) -> bool {
+    output.is_terminal() && env::var_os("TERM").map_or(true, |term| term != "dumb")
+}
+
+/// Does nothing if stdout isn't an ANSI terminal to keep the previous output.
 pub fn clear_terminal(stdout: &mut StdoutLock) -> io::Result<()> {
+    if !is_ansi_terminal(stdout) {
+        return Ok(());
+    }
+
     stdout
         .queue(MoveTo(0, 0))?
         .queue(Clear(ClearType::All))?
@@ -247,6 +277,11 @@ pub fn terminal_file_link<'a>(
     canonical_path: &str,
     color: Color,
 ) -> io::Result<()> {
+    // Escape sequences of links would be printed literally.
+    if !is_ansi_terminal(writer.stdout()) {
+        return writer.write_str(path);
+    }
+
     writer
         .stdout()
         .queue(SetForegroundColor(color))?
@@ -277,3 +312,100 @@ pub fn write_ansi(output: &mut Vec<u8>, command: impl Command) {
 
     let _ = command.write_ansi(&mut FmtWriter(output));
 }
+
+/// Remove the escape sequences of colors and styles like `\x1b[1m`.
+pub fn strip_ansi(output: &str) -> String {
+    let mut stripped = String::with_capacity(output.len());
+    let mut chars = output.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+
+        // Skip until the final char of the sequence.
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    stripped
+}
+
+/// The number of terminal rows that the line takes when it is wrapped at the terminal width.
+/// ANSI escape sequences are skipped. Every character is counted as one column.
+pub fn n_rows(line: &[u8], term_width: u16) -> usize {
+    let mut width = 0_usize;
+    let mut bytes = line.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte == 0x1b {
+            match bytes.next() {
+                // CSI like colors. Ends with a byte in `@..=~`.
+                Some(b'[') => {
+                    for &byte in bytes.by_ref() {
+                        if (0x40..=0x7e).contains(&byte) {
+                            break;
+                        }
+                    }
+                }
+                // OSC like hyperlinks. Ends with BEL or `ESC \`.
+                Some(b']') => {
+                    let mut prev = 0;
+                    for &byte in bytes.by_ref() {
+                        if byte == 0x07 || (prev == 0x1b && byte == b'\\') {
+                            break;
+                        }
+                        prev = byte;
+                    }
+                }
+                _ => (),
+            }
+            continue;
+        }
+
+        // Skip UTF-8 continuation bytes.
+        if byte & 0xc0 != 0x80 {
+            width += 1;
+        }
+    }
+
+    width.div_ceil(usize::from(term_width.max(1))).max(1)
+}
+
+/// Format seconds as `m:ss` or as `h:mm:ss` if it is at least an hour.
+pub fn format_duration(secs: u64) -> String {
+    let (hours, mins, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours == 0 {
+        format!("{mins}:{secs:02}")
+    } else {
+        format!("{hours}:{mins:02}:{secs:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        assert_eq!(n_rows(b"", 80), 1);
+        assert_eq!(n_rows(&[b'x'; 80], 80), 1);
+        assert_eq!(n_rows(&[b'x'; 81], 80), 2);
+        assert_eq!(n_rows("ä✓".as_bytes(), 2), 1);
+        assert_eq!(n_rows(b"\x1b[1m\x1b[38;5;9merror\x1b[0m", 5), 1);
+        assert_eq!(n_rows(b"\x1b]8;;file:///x\x1b\\x\x1b]8;;\x1b\\", 1), 1);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(65), "1:05");
+        assert_eq!(format_duration(3599), "59:59");
+        assert_eq!(format_duration(3600), "1:00:00");
+        assert_eq!(format_duration(90061), "25:01:01");
+    }
+}
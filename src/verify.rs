@@ -0,0 +1,116 @@
+use anyhow::Result;
+use crossterm::{
+    style::{Color, ResetColor, SetForegroundColor},
+    QueueableCommand,
+};
+use std::{
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use crate::{
+    app_state::AppState,
+    exercise::{RunnableExercise, OUTPUT_CAPACITY},
+    term::strip_ansi,
+};
+
+const MAX_EXCERPT_LEN: usize = 80;
+
+/// Return the line of the output that explains the failure best.
+/// The first panic, then the first error and otherwise the first line.
+fn failure_excerpt(output: &[u8]) -> String {
+    let output = strip_ansi(&String::from_utf8_lossy(output));
+    let mut lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+
+    // Cargo also prints an error after failing tests.
+    let line = lines
+        .clone()
+        .find(|line| line.contains("panicked"))
+        .or_else(|| lines.clone().find(|line| line.starts_with("error")))
+        .or_else(|| lines.next())
+        .unwrap_or_default();
+
+    if line.chars().count() > MAX_EXCERPT_LEN {
+        let mut excerpt = line.chars().take(MAX_EXCERPT_LEN - 1).collect::<String>();
+        excerpt.push('…');
+        excerpt
+    } else {
+        line.to_string()
+    }
+}
+
+/// Run all exercises one after the other in their current state and print a row for each of them.
+/// Unlike `check-all`, the progress isn't changed.
+pub fn verify(app_state: &AppState) -> Result<ExitCode> {
+    let exercises = app_state.exercises();
+    let name_col_width = exercises
+        .iter()
+        .fold(0, |width, exercise| width.max(exercise.name.len()));
+
+    let mut stdout = io::stdout().lock();
+    let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+    let mut n_failed = 0;
+    for exercise in exercises {
+        write!(stdout, "{:name_col_width$}  ", exercise.name)?;
+        stdout.flush()?;
+
+        if exercise.run_exercise(Some(&mut output), app_state.cmd_runner())? {
+            stdout.queue(SetForegroundColor(Color::Green))?;
+            stdout.write_all(b"PASS")?;
+            stdout.queue(ResetColor)?;
+            stdout.write_all(b"\n")?;
+        } else {
+            stdout.queue(SetForegroundColor(Color::Red))?;
+            stdout.write_all(b"FAIL")?;
+            stdout.queue(ResetColor)?;
+            writeln!(stdout, "  {}", failure_excerpt(&output))?;
+            n_failed += 1;
+        }
+    }
+
+    let n_exercises = exercises.len();
+    writeln!(
+        stdout,
+        "\n{}/{n_exercises} exercises passed",
+        n_exercises - n_failed,
+    )?;
+    if n_failed == 0 {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    writeln!(
+        stdout,
+        "Run `rustlings run <name>` to see the full output of a failing exercise"
+    )?;
+    Ok(ExitCode::FAILURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt() {
+        assert_eq!(
+            failure_excerpt(
+                b"\x1b[1m\x1b[32m   Compiling\x1b[0m x\n\x1b[1m\x1b[91merror[E0425]\x1b[0m: cannot find value `x`\n"
+            ),
+            "error[E0425]: cannot find value `x`",
+        );
+        assert_eq!(
+            failure_excerpt(
+                b"\nrunning 1 test\nthread 'main' panicked at src/main.rs:1:1:\nerror: test failed\n"
+            ),
+            "thread 'main' panicked at src/main.rs:1:1:",
+        );
+        assert_eq!(failure_excerpt(b"\n  failed\n"), "failed");
+        assert_eq!(failure_excerpt(b""), "");
+
+        let excerpt = failure_excerpt("error: ä".repeat(MAX_EXCERPT_LEN).as_bytes());
+        assert_eq!(excerpt.chars().count(), MAX_EXCERPT_LEN);
+        assert!(excerpt.ends_with('…'));
+    }
+}
@@ -1,27 +1,40 @@
-use anyhow::{Error, Result};
+use anyhow::{Context, Error, Result};
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use std::{
     io::{self, Write},
     path::Path,
     sync::{
         atomic::{AtomicBool, Ordering::Relaxed},
-        mpsc::channel,
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
     },
     time::Duration,
 };
 
 use crate::{
     app_state::{AppState, ExercisesProgress},
+    info_file::exercises_dir,
     list,
+    term::is_ansi_terminal,
 };
 
-use self::{notify_event::NotifyEventHandler, state::WatchState, terminal_event::InputEvent};
+use self::{
+    desktop_notification::DesktopNotifier, keys::Keys, notify_event::NotifyEventHandler,
+    state::WatchState, terminal_event::InputEvent,
+};
 
+mod desktop_notification;
+mod external_editor;
+mod keys;
+mod line_event;
 mod notify_event;
 mod state;
 mod terminal_event;
+#[cfg(not(windows))]
+mod terminal_mode;
 
 static EXERCISE_RUNNING: AtomicBool = AtomicBool::new(false);
+// The interval for updating the timer of the current exercise.
+const TIMER_TICK: Duration = Duration::from_secs(1);
 
 // Private unit type to force using the constructor function.
 #[must_use = "When the guard is dropped, the input is unpaused"]
@@ -44,8 +57,16 @@ impl Drop for InputPauseGuard {
 
 enum WatchEvent {
     Input(InputEvent),
-    FileChange { exercise_ind: usize },
-    TerminalResize { width: u16 },
+    FileChange {
+        exercise_ind: usize,
+    },
+    TerminalResize {
+        width: u16,
+        height: u16,
+    },
+    // Continued after being suspended.
+    #[cfg(not(windows))]
+    Continue,
     NotifyErr(notify::Error),
     TerminalEventErr(io::Error),
 }
@@ -59,9 +80,103 @@ enum WatchExit {
     List,
 }
 
+// Watch the exercises directory and send a debounced file change event per updated exercise.
+// Dropping the returned watcher stops it.
+fn build_file_watcher(
+    app_state: &AppState,
+    exercise_names: &'static [&'static [u8]],
+    debounce_duration: Duration,
+    watch_event_sender: Sender<WatchEvent>,
+) -> Result<RecommendedWatcher> {
+    let extra_paths = app_state
+        .exercises()
+        .iter()
+        .enumerate()
+        .flat_map(|(exercise_ind, exercise)| {
+            exercise
+                .extra_paths
+                .iter()
+                .map(move |path| (Path::new(*path), exercise_ind))
+        })
+        .collect();
+    let notify_event_handler = NotifyEventHandler::build(
+        watch_event_sender,
+        exercise_names,
+        extra_paths,
+        debounce_duration,
+    )?;
+
+    let mut watcher = RecommendedWatcher::new(
+        notify_event_handler,
+        Config::default().with_poll_interval(Duration::from_secs(1)),
+    )?;
+    watcher.watch(Path::new(exercises_dir()), RecursiveMode::Recursive)?;
+
+    Ok(watcher)
+}
+
+/// Waits for changes of the files of an exercise for `rustlings run --watch`.
+/// The changes are detected and debounced like in the watch mode.
+pub struct ExerciseFileWatcher {
+    // Stops watching when dropped.
+    _watcher: RecommendedWatcher,
+    watch_event_receiver: Receiver<WatchEvent>,
+}
+
+impl ExerciseFileWatcher {
+    pub fn build(app_state: &AppState, debounce_duration: Duration) -> Result<Self> {
+        // For the notify event handler thread.
+        // Leaking is not a problem because the slice lives until the end of the program.
+        let exercise_names = &*app_state
+            .exercises()
+            .iter()
+            .map(|exercise| exercise.name.as_bytes())
+            .collect::<Vec<_>>()
+            .leak();
+        let (watch_event_sender, watch_event_receiver) = channel();
+        let watcher = build_file_watcher(
+            app_state,
+            exercise_names,
+            debounce_duration,
+            watch_event_sender,
+        )
+        .context("Failed to start the file watcher")?;
+
+        Ok(Self {
+            _watcher: watcher,
+            watch_event_receiver,
+        })
+    }
+
+    /// Block until a file of the exercise with the index `exercise_ind` is updated.
+    /// Changes while an exercise is running are ignored like in the watch mode.
+    pub fn wait_for_change(&self, exercise_ind: usize) -> Result<()> {
+        loop {
+            match self
+                .watch_event_receiver
+                .recv()
+                .context("The file watcher stopped unexpectedly")?
+            {
+                WatchEvent::FileChange {
+                    exercise_ind: changed_exercise_ind,
+                } if changed_exercise_ind == exercise_ind => return Ok(()),
+                WatchEvent::NotifyErr(e) => {
+                    return Err(Error::from(e).context("The file watcher failed"));
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
 fn run_watch(
     app_state: &mut AppState,
+    keys: Keys,
     notify_exercise_names: Option<&'static [&'static [u8]]>,
+    debounce_duration: Duration,
+    desktop_notifier: Option<&mut DesktopNotifier>,
+    prefetch: bool,
+    line_mode: bool,
 ) -> Result<WatchExit> {
     let (watch_event_sender, watch_event_receiver) = channel();
 
@@ -69,47 +184,106 @@ fn run_watch(
     // Prevent dropping the guard until the end of the function.
     // Otherwise, the file watcher exits.
     let _watcher_guard = if let Some(exercise_names) = notify_exercise_names {
-        let notify_event_handler =
-            NotifyEventHandler::build(watch_event_sender.clone(), exercise_names)?;
-
-        let mut watcher = RecommendedWatcher::new(
-            notify_event_handler,
-            Config::default().with_poll_interval(Duration::from_secs(1)),
+        let watcher = build_file_watcher(
+            app_state,
+            exercise_names,
+            debounce_duration,
+            watch_event_sender.clone(),
         )
         .inspect_err(|_| eprintln!("{NOTIFY_ERR}"))?;
 
-        watcher
-            .watch(Path::new("exercises"), RecursiveMode::Recursive)
-            .inspect_err(|_| eprintln!("{NOTIFY_ERR}"))?;
-
         Some(watcher)
     } else {
         manual_run = true;
         None
     };
 
-    let mut watch_state = WatchState::build(app_state, watch_event_sender, manual_run)?;
+    // The line mode doesn't change the terminal.
+    #[cfg(not(windows))]
+    let _suspend_handler = if line_mode {
+        None
+    } else {
+        Some(terminal_mode::SuspendHandler::build(
+            watch_event_sender.clone(),
+        )?)
+    };
+
+    let mut watch_state = WatchState::build(
+        app_state,
+        watch_event_sender,
+        keys,
+        manual_run,
+        desktop_notifier,
+        prefetch,
+        line_mode,
+    )?;
     let mut stdout = io::stdout().lock();
 
     watch_state.run_current_exercise(&mut stdout)?;
 
-    while let Ok(event) = watch_event_receiver.recv() {
+    loop {
+        let timeout = watch_state
+            .alert_timeout()
+            .map_or(TIMER_TICK, |alert_timeout| alert_timeout.min(TIMER_TICK));
+        let event = match watch_event_receiver.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => {
+                // The timer isn't rendered while the line is flashed.
+                if watch_state.alert_timeout().is_some() {
+                    watch_state.advance_alert(&mut stdout)?;
+                } else {
+                    watch_state.render_timer(&mut stdout)?;
+                }
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
         match event {
             WatchEvent::Input(InputEvent::Next) => match watch_state.next_exercise(&mut stdout)? {
-                ExercisesProgress::AllDone => break,
+                ExercisesProgress::AllDone => {
+                    watch_state.notify_all_done(&mut stdout)?;
+                    break;
+                }
                 ExercisesProgress::NewPending => watch_state.run_current_exercise(&mut stdout)?,
                 ExercisesProgress::CurrentPending => (),
             },
+            WatchEvent::Input(InputEvent::Previous) => {
+                watch_state.previous_exercise(&mut stdout)?;
+            }
+            WatchEvent::Input(InputEvent::Skip) => watch_state.skip_exercise(&mut stdout)?,
+            WatchEvent::Input(InputEvent::GoTo) => watch_state.go_to_exercise(&mut stdout)?,
             WatchEvent::Input(InputEvent::Run) => watch_state.run_current_exercise(&mut stdout)?,
             WatchEvent::Input(InputEvent::Hint) => watch_state.show_hint(&mut stdout)?,
-            WatchEvent::Input(InputEvent::List) => return Ok(WatchExit::List),
+            WatchEvent::Input(InputEvent::List) => {
+                watch_state.stop_timer()?;
+                return Ok(WatchExit::List);
+            }
             WatchEvent::Input(InputEvent::CheckAll) => match watch_state
                 .check_all_exercises(&mut stdout)?
             {
-                ExercisesProgress::AllDone => break,
+                ExercisesProgress::AllDone => {
+                    watch_state.notify_all_done(&mut stdout)?;
+                    break;
+                }
                 ExercisesProgress::NewPending => watch_state.run_current_exercise(&mut stdout)?,
                 ExercisesProgress::CurrentPending => watch_state.render(&mut stdout)?,
             },
+            WatchEvent::Input(InputEvent::Clippy) => watch_state.run_clippy(&mut stdout)?,
+            WatchEvent::Input(InputEvent::SolutionDiff) => {
+                watch_state.show_solution_diff(&mut stdout)?;
+            }
+            WatchEvent::Input(InputEvent::OpenEditor) => watch_state.open_in_editor(&mut stdout)?,
+            WatchEvent::Input(InputEvent::ToggleNotifications) => {
+                watch_state.toggle_notifications(&mut stdout)?;
+            }
+            WatchEvent::Input(InputEvent::ToggleTimerPause) => {
+                watch_state.toggle_timer_pause(&mut stdout)?;
+            }
+            WatchEvent::Input(InputEvent::ToggleBell) => watch_state.toggle_bell(&mut stdout)?,
+            WatchEvent::Input(InputEvent::Scroll(scroll)) => {
+                watch_state.scroll(scroll, &mut stdout)?;
+            }
             WatchEvent::Input(InputEvent::Reset) => watch_state.reset_exercise(&mut stdout)?,
             WatchEvent::Input(InputEvent::Quit) => {
                 stdout.write_all(QUIT_MSG)?;
@@ -118,9 +292,11 @@ fn run_watch(
             WatchEvent::FileChange { exercise_ind } => {
                 watch_state.handle_file_change(exercise_ind, &mut stdout)?;
             }
-            WatchEvent::TerminalResize { width } => {
-                watch_state.update_term_width(width, &mut stdout)?;
+            WatchEvent::TerminalResize { width, height } => {
+                watch_state.update_term_size(width, height, &mut stdout)?;
             }
+            #[cfg(not(windows))]
+            WatchEvent::Continue => watch_state.render(&mut stdout)?,
             WatchEvent::NotifyErr(e) => return Err(Error::from(e).context(NOTIFY_ERR)),
             WatchEvent::TerminalEventErr(e) => {
                 return Err(Error::from(e).context("Terminal event listener failed"));
@@ -128,15 +304,32 @@ fn run_watch(
         }
     }
 
+    watch_state.stop_timer()?;
     Ok(WatchExit::Shutdown)
 }
 
 fn watch_list_loop(
     app_state: &mut AppState,
+    keys: Keys,
     notify_exercise_names: Option<&'static [&'static [u8]]>,
+    debounce_duration: Duration,
+    desktop_notifications: bool,
+    prefetch: bool,
+    line_mode: bool,
 ) -> Result<()> {
+    // Outside of the loop to keep muting the notifications after the list mode.
+    let mut desktop_notifier = desktop_notifications.then(DesktopNotifier::new);
+
     loop {
-        match run_watch(app_state, notify_exercise_names)? {
+        match run_watch(
+            app_state,
+            keys,
+            notify_exercise_names,
+            debounce_duration,
+            desktop_notifier.as_mut(),
+            prefetch,
+            line_mode,
+        )? {
             WatchExit::Shutdown => break Ok(()),
             // It is much easier to exit the watch mode, launch the list mode and then restart
             // the watch mode instead of trying to pause the watch threads and correct the
@@ -147,30 +340,63 @@ fn watch_list_loop(
 }
 
 /// `notify_exercise_names` as None activates the manual run mode.
+/// File changes are debounced for `debounce_duration` to only rerun once per save.
+/// `desktop_notifications` activates notifications when an exercise or all exercises are done.
+/// `prefetch` activates building the next pending exercise in the background when the current one is done.
+/// Without an ANSI terminal, the watch mode falls back to the line mode which only appends to
+/// the output and reads commands terminated by Enter.
+/// Some keys can be changed in the `[keys]` table of `rustlings.toml`.
 pub fn watch(
     app_state: &mut AppState,
     notify_exercise_names: Option<&'static [&'static [u8]]>,
+    debounce_duration: Duration,
+    desktop_notifications: bool,
+    prefetch: bool,
 ) -> Result<()> {
+    // Validated before changing the terminal.
+    let keys = Keys::load()?;
+
+    if !is_ansi_terminal(&io::stdout()) {
+        return watch_list_loop(
+            app_state,
+            keys,
+            notify_exercise_names,
+            debounce_duration,
+            desktop_notifications,
+            prefetch,
+            true,
+        );
+    }
+
     #[cfg(not(windows))]
     {
-        let stdin_fd = rustix::stdio::stdin();
-        let mut termios = rustix::termios::tcgetattr(stdin_fd)?;
-        let original_local_modes = termios.local_modes;
-        // Disable stdin line buffering and hide input.
-        termios.local_modes -=
-            rustix::termios::LocalModes::ICANON | rustix::termios::LocalModes::ECHO;
-        rustix::termios::tcsetattr(stdin_fd, rustix::termios::OptionalActions::Now, &termios)?;
+        terminal_mode::enter()?;
 
-        let res = watch_list_loop(app_state, notify_exercise_names);
+        let res = watch_list_loop(
+            app_state,
+            keys,
+            notify_exercise_names,
+            debounce_duration,
+            desktop_notifications,
+            prefetch,
+            false,
+        );
 
-        termios.local_modes = original_local_modes;
-        rustix::termios::tcsetattr(stdin_fd, rustix::termios::OptionalActions::Now, &termios)?;
+        terminal_mode::release()?;
 
         res
     }
 
     #[cfg(windows)]
-    watch_list_loop(app_state, notify_exercise_names)
+    watch_list_loop(
+        app_state,
+        keys,
+        notify_exercise_names,
+        debounce_duration,
+        desktop_notifications,
+        prefetch,
+        false,
+    )
 }
 
 const QUIT_MSG: &[u8] = b"
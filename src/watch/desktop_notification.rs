@@ -0,0 +1,79 @@
+use std::{
+    env,
+    io::{self, ErrorKind},
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Sends desktop notifications using the notification tool of the platform.
+pub struct DesktopNotifier {
+    pub muted: bool,
+    // Set after the first failure to only report it once.
+    unavailable: bool,
+}
+
+impl DesktopNotifier {
+    pub fn new() -> Self {
+        Self {
+            muted: false,
+            unavailable: false,
+        }
+    }
+
+    fn command(summary: &str, body: &str) -> io::Result<Command> {
+        if cfg!(target_os = "macos") {
+            let mut cmd = Command::new("osascript");
+            // The Debug format escapes quotes and backslashes like AppleScript strings.
+            cmd.arg("-e").arg(format!(
+                "display notification {body:?} with title {summary:?}"
+            ));
+            return Ok(cmd);
+        }
+
+        if cfg!(windows) {
+            return Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "Not supported on Windows",
+            ));
+        }
+
+        if env::var_os("DISPLAY").is_none() && env::var_os("WAYLAND_DISPLAY").is_none() {
+            return Err(io::Error::new(
+                ErrorKind::NotFound,
+                "No graphical session found",
+            ));
+        }
+
+        let mut cmd = Command::new("notify-send");
+        cmd.arg("--app-name=Rustlings").arg(summary).arg(body);
+        Ok(cmd)
+    }
+
+    /// Send the notification in the background unless it is muted.
+    /// Returns a message to show to the user on the first failure.
+    /// Later notifications are skipped after a failure.
+    pub fn notify(&mut self, summary: &str, body: &str) -> Option<String> {
+        if self.muted || self.unavailable {
+            return None;
+        }
+
+        let child = Self::command(summary, body).and_then(|mut cmd| {
+            cmd.stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+        });
+
+        match child {
+            Ok(mut child) => {
+                // Reap the process without blocking the watch mode.
+                let _ = thread::Builder::new().spawn(move || child.wait());
+                None
+            }
+            Err(e) => {
+                self.unavailable = true;
+                Some(format!("Desktop notifications are disabled: {e}"))
+            }
+        }
+    }
+}
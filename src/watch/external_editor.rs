@@ -0,0 +1,102 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    env,
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+};
+
+// Editors that open their own window. Other editors are assumed to run in the terminal.
+const GUI_EDITORS: &[&str] = &[
+    "code",
+    "code-insiders",
+    "codium",
+    "cursor",
+    "gedit",
+    "gnome-text-editor",
+    "gvim",
+    "kate",
+    "mousepad",
+    "mvim",
+    "notepad",
+    "notepad++",
+    "open",
+    "subl",
+    "xdg-open",
+    "zed",
+];
+
+// `$EDITOR`, then `$VISUAL` and otherwise the program to open files on the platform.
+fn editor_command_line() -> String {
+    ["EDITOR", "VISUAL"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| {
+            String::from(if cfg!(windows) {
+                "notepad"
+            } else if cfg!(target_os = "macos") {
+                "open -t"
+            } else {
+                "xdg-open"
+            })
+        })
+}
+
+fn is_gui_editor(program: &str) -> bool {
+    let name = Path::new(program)
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    GUI_EDITORS.contains(&name)
+}
+
+// Give the terminal back to the editor by enabling the line buffering and the echo
+// that the watch mode disables. They are disabled again afterwards.
+fn run_in_terminal(cmd: &mut Command) -> Result<()> {
+    let status = {
+        #[cfg(not(windows))]
+        {
+            super::terminal_mode::release()?;
+            let status = cmd.status();
+            super::terminal_mode::reclaim()?;
+            status
+        }
+
+        #[cfg(windows)]
+        cmd.status()
+    }?;
+
+    if !status.success() {
+        bail!("The editor exited with {status}");
+    }
+
+    Ok(())
+}
+
+/// Open the file in the editor of the user.
+/// Waits for terminal editors to exit. GUI editors are spawned in the background.
+pub fn open(path: &str) -> Result<()> {
+    let command_line = editor_command_line();
+    let mut words = command_line.split_whitespace();
+    let Some(program) = words.next() else {
+        bail!("No editor found");
+    };
+
+    let mut cmd = Command::new(program);
+    cmd.args(words).arg(path);
+
+    if is_gui_editor(program) {
+        let mut child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to run the editor `{command_line}`"))?;
+        // Reap the process without blocking the watch mode.
+        let _ = thread::Builder::new().spawn(move || child.wait());
+        return Ok(());
+    }
+
+    run_in_terminal(&mut cmd).with_context(|| format!("Failed to run the editor `{command_line}`"))
+}
@@ -0,0 +1,182 @@
+use anyhow::{bail, Context, Error, Result};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, io::ErrorKind};
+
+/// The optional configuration file in the directory of the exercises.
+pub const CONFIG_FILE_PATH: &str = "rustlings.toml";
+
+// The keys of the other actions which can't be changed.
+const FIXED_KEYS: [(char, &str); 12] = [
+    ('b', "previous"),
+    ('s', "skip"),
+    ('g', "go to"),
+    (':', "go to"),
+    ('c', "check all"),
+    ('p', "clippy"),
+    ('d', "diff to solution"),
+    ('o', "open in editor"),
+    ('m', "mute"),
+    ('P', "pause timer"),
+    ('B', "bell"),
+    ('x', "reset"),
+];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    keys: BTreeMap<String, char>,
+}
+
+/// The keys of the actions in the watch mode that can be changed in the `[keys]` table of
+/// `rustlings.toml`, e.g. `next = "j"`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Keys {
+    pub next: char,
+    pub hint: char,
+    pub list: char,
+    pub quit: char,
+    pub run: char,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Self {
+            next: 'n',
+            hint: 'h',
+            list: 'l',
+            quit: 'q',
+            run: 'r',
+        }
+    }
+}
+
+impl Keys {
+    // The actions that can be mapped to other keys with their keys.
+    fn actions(self) -> [(&'static str, char); 5] {
+        [
+            ("next", self.next),
+            ("hint", self.hint),
+            ("list", self.list),
+            ("quit", self.quit),
+            ("run", self.run),
+        ]
+    }
+
+    fn key_mut(&mut self, action: &str) -> Option<&mut char> {
+        match action {
+            "next" => Some(&mut self.next),
+            "hint" => Some(&mut self.hint),
+            "list" => Some(&mut self.list),
+            "quit" => Some(&mut self.quit),
+            "run" => Some(&mut self.run),
+            _ => None,
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let config_file = toml_edit::de::from_str::<ConfigFile>(content)?;
+
+        let mut keys = Self::default();
+        for (action, key) in config_file.keys {
+            let Some(key_mut) = keys.key_mut(&action) else {
+                bail!(
+                    "Unknown action `{action}` in the `[keys]` table. The actions are: {}",
+                    keys.actions().map(|(action, _)| action).join(", "),
+                );
+            };
+
+            if key.is_whitespace() || key.is_control() {
+                bail!("The key {key:?} of the action `{action}` isn't a visible character");
+            }
+
+            *key_mut = key;
+        }
+
+        let mut used_keys = FIXED_KEYS.to_vec();
+        for (action, key) in keys.actions() {
+            if let Some((_, other_action)) = used_keys.iter().find(|(used, _)| *used == key) {
+                bail!("The key `{key}` is used by the actions `{other_action}` and `{action}`");
+            }
+            used_keys.push((key, action));
+        }
+
+        Ok(keys)
+    }
+
+    /// Read the keys from `rustlings.toml`.
+    /// The default keys are used if the file or the `[keys]` table doesn't exist.
+    pub fn load() -> Result<Self> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(content) => Self::parse(&content)
+                .with_context(|| format!("Failed to parse the file `{CONFIG_FILE_PATH}`")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => {
+                Err(Error::from(e).context(format!("Failed to read the file `{CONFIG_FILE_PATH}`")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::watch::terminal_event::InputEvent;
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(Keys::parse("").unwrap(), Keys::default());
+        assert_eq!(Keys::parse("[keys]\n").unwrap(), Keys::default());
+
+        // Swapping keys of configurable actions.
+        let keys = Keys::parse("[keys]\nnext = \"h\"\nhint = \"n\"\nquit = \"Q\"\n").unwrap();
+        assert_eq!(
+            keys,
+            Keys {
+                next: 'h',
+                hint: 'n',
+                quit: 'Q',
+                ..Keys::default()
+            },
+        );
+
+        let err = |content| Keys::parse(content).unwrap_err().to_string();
+        assert_eq!(
+            err("[keys]\nskip = \"j\"\n"),
+            "Unknown action `skip` in the `[keys]` table. The actions are: next, hint, list, quit, run",
+        );
+        assert_eq!(
+            err("[keys]\nnext = \"h\"\n"),
+            "The key `h` is used by the actions `next` and `hint`",
+        );
+        assert_eq!(
+            err("[keys]\nrun = \"x\"\n"),
+            "The key `x` is used by the actions `reset` and `run`",
+        );
+        assert_eq!(
+            err("[keys]\nquit = \" \"\n"),
+            "The key ' ' of the action `quit` isn't a visible character",
+        );
+        assert!(Keys::parse("[keys]\nnext = \"jk\"\n").is_err());
+        assert!(Keys::parse("[key]\nnext = \"j\"\n").is_err());
+    }
+
+    #[test]
+    fn fixed_keys_are_used() {
+        let keys = Keys::default();
+        for (key, _) in FIXED_KEYS {
+            assert!(InputEvent::from_char(key, keys, true, true).is_some());
+        }
+
+        // The default key of a remapped action is free.
+        let keys = Keys {
+            next: 'j',
+            ..Keys::default()
+        };
+        assert!(InputEvent::from_char('n', keys, true, true).is_none());
+        assert!(matches!(
+            InputEvent::from_char('j', keys, true, true),
+            Some(InputEvent::Next),
+        ));
+    }
+}
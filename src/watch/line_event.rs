@@ -0,0 +1,53 @@
+use std::{
+    io,
+    sync::mpsc::{Receiver, Sender},
+};
+
+use super::{keys::Keys, terminal_event::InputEvent, WatchEvent};
+
+/// Read a command per line in the line mode without a terminal.
+/// A command is the letter of a key in the watch mode.
+/// Unlike keys, commands entered while an exercise is running aren't ignored but handled
+/// afterwards because they can be piped in.
+/// The list mode isn't available because it needs a terminal.
+pub fn line_event_handler(
+    sender: Sender<WatchEvent>,
+    unpause_receiver: Receiver<()>,
+    keys: Keys,
+    manual_run: bool,
+    desktop_notifications: bool,
+) {
+    let mut line = String::with_capacity(8);
+    let last_watch_event = loop {
+        line.clear();
+        // Without locking stdin for longer to let the prompts read from it.
+        match io::stdin().read_line(&mut line) {
+            // Keep rerunning on file changes after the end of the input like in CI.
+            Ok(0) => return,
+            Ok(_) => (),
+            Err(e) => break WatchEvent::TerminalEventErr(e),
+        }
+
+        let mut chars = line.trim().chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let input_event = match InputEvent::from_char(c, keys, manual_run, desktop_notifications) {
+            Some(InputEvent::List) | None => continue,
+            Some(InputEvent::Quit) => break WatchEvent::Input(InputEvent::Quit),
+            Some(input_event) => input_event,
+        };
+
+        let prompt = input_event.reads_input();
+        if sender.send(WatchEvent::Input(input_event)).is_err() {
+            return;
+        }
+
+        // Pause input until quitting the prompt or the editor.
+        if prompt && unpause_receiver.recv().is_err() {
+            return;
+        }
+    };
+
+    let _ = sender.send(last_watch_event);
+}
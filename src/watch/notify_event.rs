@@ -1,12 +1,13 @@
 use anyhow::{Context, Result};
 use notify::{
-    event::{AccessKind, AccessMode, MetadataKind, ModifyKind, RenameMode},
+    event::{AccessKind, AccessMode, CreateKind, MetadataKind, ModifyKind, RenameMode},
     Event, EventKind,
 };
 use std::{
+    path::Path,
     sync::{
         atomic::Ordering::Relaxed,
-        mpsc::{sync_channel, RecvTimeoutError, Sender, SyncSender},
+        mpsc::{sync_channel, Receiver, RecvTimeoutError, Sender, SyncSender},
     },
     thread,
     time::Duration,
@@ -14,49 +15,35 @@ use std::{
 
 use super::{WatchEvent, EXERCISE_RUNNING};
 
-const DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
-
 pub struct NotifyEventHandler {
     error_sender: Sender<WatchEvent>,
     // Sends the index of the updated exercise.
     update_sender: SyncSender<usize>,
     // Used to report which exercise was modified.
     exercise_names: &'static [&'static [u8]],
+    // The paths of the other files of multi-file exercises with the index of their exercise.
+    extra_paths: Vec<(&'static Path, usize)>,
 }
 
 impl NotifyEventHandler {
+    /// File changes are only reported after no further changes happened during `debounce_duration`.
     pub fn build(
         watch_event_sender: Sender<WatchEvent>,
         exercise_names: &'static [&'static [u8]],
+        extra_paths: Vec<(&'static Path, usize)>,
+        debounce_duration: Duration,
     ) -> Result<Self> {
         let (update_sender, update_receiver) = sync_channel(0);
         let error_sender = watch_event_sender.clone();
 
-        // Debouncer
         thread::Builder::new()
             .spawn(move || {
-                let mut exercise_updated = vec![false; exercise_names.len()];
-
-                loop {
-                    match update_receiver.recv_timeout(DEBOUNCE_DURATION) {
-                        Ok(exercise_ind) => exercise_updated[exercise_ind] = true,
-                        Err(RecvTimeoutError::Timeout) => {
-                            for (exercise_ind, updated) in exercise_updated.iter_mut().enumerate() {
-                                if *updated {
-                                    if watch_event_sender
-                                        .send(WatchEvent::FileChange { exercise_ind })
-                                        .is_err()
-                                    {
-                                        break;
-                                    }
-
-                                    *updated = false;
-                                }
-                            }
-                        }
-                        Err(RecvTimeoutError::Disconnected) => break,
-                    }
-                }
+                debounce(
+                    &update_receiver,
+                    &watch_event_sender,
+                    exercise_names.len(),
+                    debounce_duration,
+                );
             })
             .context("Failed to spawn a thread to debounce file changes")?;
 
@@ -64,10 +51,91 @@ impl NotifyEventHandler {
             error_sender,
             update_sender,
             exercise_names,
+            extra_paths,
         })
     }
 }
 
+// Send one file change event per updated exercise after a burst of updates.
+fn debounce(
+    update_receiver: &Receiver<usize>,
+    watch_event_sender: &Sender<WatchEvent>,
+    n_exercises: usize,
+    debounce_duration: Duration,
+) {
+    let mut exercise_updated = vec![false; n_exercises];
+
+    loop {
+        let disconnected = match update_receiver.recv_timeout(debounce_duration) {
+            Ok(exercise_ind) => {
+                exercise_updated[exercise_ind] = true;
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            // The last updates are still sent.
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+
+        for (exercise_ind, updated) in exercise_updated.iter_mut().enumerate() {
+            if *updated {
+                if watch_event_sender
+                    .send(WatchEvent::FileChange { exercise_ind })
+                    .is_err()
+                {
+                    return;
+                }
+
+                *updated = false;
+            }
+        }
+
+        if disconnected {
+            return;
+        }
+    }
+}
+
+// The number of leading paths of the event to skip or `None` if the event doesn't update any file.
+// Editors like Vim save by writing a temporary file and renaming it to the exercise file.
+// Only the destination of such a rename is updated.
+// The watcher is registered on the exercises directory, so it keeps working
+// after a file is replaced by another one.
+fn n_skipped_paths(kind: EventKind) -> Option<usize> {
+    match kind {
+        EventKind::Any => Some(0),
+        EventKind::Modify(modify_kind) => match modify_kind {
+            ModifyKind::Any | ModifyKind::Data(_) => Some(0),
+            ModifyKind::Name(rename_mode) => match rename_mode {
+                RenameMode::Any | RenameMode::To => Some(0),
+                RenameMode::Both => Some(1),
+                RenameMode::From | RenameMode::Other => None,
+            },
+            ModifyKind::Metadata(metadata_kind) => match metadata_kind {
+                MetadataKind::Any | MetadataKind::WriteTime => Some(0),
+                MetadataKind::AccessTime
+                | MetadataKind::Permissions
+                | MetadataKind::Ownership
+                | MetadataKind::Extended
+                | MetadataKind::Other => None,
+            },
+            ModifyKind::Other => None,
+        },
+        EventKind::Access(access_kind) => match access_kind {
+            AccessKind::Any => Some(0),
+            AccessKind::Close(access_mode) => match access_mode {
+                AccessMode::Any | AccessMode::Write => Some(0),
+                AccessMode::Execute | AccessMode::Read | AccessMode::Other => None,
+            },
+            AccessKind::Read | AccessKind::Open(_) | AccessKind::Other => None,
+        },
+        // A file created after removing the old one (instead of being renamed).
+        EventKind::Create(CreateKind::Any | CreateKind::File) => Some(0),
+        EventKind::Create(CreateKind::Folder | CreateKind::Other)
+        | EventKind::Remove(_)
+        | EventKind::Other => None,
+    }
+}
+
 impl notify::EventHandler for NotifyEventHandler {
     fn handle_event(&mut self, input_event: notify::Result<Event>) {
         if EXERCISE_RUNNING.load(Relaxed) {
@@ -84,39 +152,23 @@ impl notify::EventHandler for NotifyEventHandler {
             }
         };
 
-        match input_event.kind {
-            EventKind::Any => (),
-            EventKind::Modify(modify_kind) => match modify_kind {
-                ModifyKind::Any | ModifyKind::Data(_) => (),
-                ModifyKind::Name(rename_mode) => match rename_mode {
-                    RenameMode::Any | RenameMode::To => (),
-                    RenameMode::From | RenameMode::Both | RenameMode::Other => return,
-                },
-                ModifyKind::Metadata(metadata_kind) => match metadata_kind {
-                    MetadataKind::Any | MetadataKind::WriteTime => (),
-                    MetadataKind::AccessTime
-                    | MetadataKind::Permissions
-                    | MetadataKind::Ownership
-                    | MetadataKind::Extended
-                    | MetadataKind::Other => return,
-                },
-                ModifyKind::Other => return,
-            },
-            EventKind::Access(access_kind) => match access_kind {
-                AccessKind::Any => (),
-                AccessKind::Close(access_mode) => match access_mode {
-                    AccessMode::Any | AccessMode::Write => (),
-                    AccessMode::Execute | AccessMode::Read | AccessMode::Other => return,
-                },
-                AccessKind::Read | AccessKind::Open(_) | AccessKind::Other => return,
-            },
-            EventKind::Create(_) | EventKind::Remove(_) | EventKind::Other => return,
-        }
+        let Some(n_skipped_paths) = n_skipped_paths(input_event.kind) else {
+            return;
+        };
 
         let _ = input_event
             .paths
             .into_iter()
+            .skip(n_skipped_paths)
             .filter_map(|path| {
+                if let Some((_, exercise_ind)) = self
+                    .extra_paths
+                    .iter()
+                    .find(|(extra_path, _)| path.ends_with(extra_path))
+                {
+                    return Some(*exercise_ind);
+                }
+
                 let file_name = path.file_name()?.to_str()?.as_bytes();
 
                 let [file_name_without_ext @ .., b'.', b'r', b's'] = file_name else {
@@ -130,3 +182,114 @@ impl notify::EventHandler for NotifyEventHandler {
             .try_for_each(|exercise_ind| self.update_sender.send(exercise_ind));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use notify::EventHandler;
+    use std::{path::PathBuf, sync::mpsc::channel};
+
+    use super::*;
+
+    fn event(kind: EventKind, paths: &[&str]) -> notify::Result<Event> {
+        Ok(paths.iter().fold(Event::new(kind), |event, path| {
+            event.add_path(PathBuf::from(path))
+        }))
+    }
+
+    // A handler without the debouncer thread. Its updates are collected after dropping it.
+    fn handler(
+        exercise_names: &'static [&'static [u8]],
+        extra_paths: Vec<(&'static Path, usize)>,
+    ) -> (NotifyEventHandler, Receiver<usize>) {
+        let (error_sender, _) = channel();
+        let (update_sender, update_receiver) = sync_channel(16);
+        let handler = NotifyEventHandler {
+            error_sender,
+            update_sender,
+            exercise_names,
+            extra_paths,
+        };
+
+        (handler, update_receiver)
+    }
+
+    #[test]
+    fn rename_save() {
+        let (mut handler, update_receiver) = handler(&[b"intro1", b"intro2"], Vec::new());
+
+        // Like Vim: Move the original file to a backup and write a new one.
+        handler.handle_event(event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+            &["exercises/intro2.rs", "exercises/intro2.rs~"],
+        ));
+        handler.handle_event(event(
+            EventKind::Create(CreateKind::File),
+            &["exercises/intro2.rs"],
+        ));
+        handler.handle_event(event(
+            EventKind::Access(AccessKind::Close(AccessMode::Write)),
+            &["exercises/intro2.rs"],
+        ));
+        handler.handle_event(event(
+            EventKind::Remove(notify::event::RemoveKind::File),
+            &["exercises/intro2.rs~"],
+        ));
+        // Like some IDEs: Rename a temporary file over the exercise file.
+        handler.handle_event(event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+            &["exercises/intro2.rs.tmp", "exercises/intro2.rs"],
+        ));
+        drop(handler);
+
+        let updates = update_receiver.iter().collect::<Vec<_>>();
+        assert_eq!(updates, [1, 1, 1]);
+
+        // Exactly one rerun. The debouncer never times out and only stops after all updates.
+        let (update_sender, update_receiver) = sync_channel(updates.len());
+        for exercise_ind in updates {
+            update_sender.send(exercise_ind).unwrap();
+        }
+        drop(update_sender);
+        let (watch_event_sender, watch_event_receiver) = channel();
+        debounce(&update_receiver, &watch_event_sender, 2, Duration::MAX);
+        drop(watch_event_sender);
+        assert!(matches!(
+            watch_event_receiver.iter().collect::<Vec<_>>()[..],
+            [WatchEvent::FileChange { exercise_ind: 1 }],
+        ));
+    }
+
+    #[test]
+    fn extra_file() {
+        let (mut handler, update_receiver) = handler(
+            &[b"modules1", b"modules2"],
+            vec![(Path::new("exercises/10_modules/helpers.rs"), 1)],
+        );
+
+        handler.handle_event(event(
+            EventKind::Modify(ModifyKind::Data(notify::event::DataChange::Content)),
+            &["/home/user/rustlings/exercises/10_modules/helpers.rs"],
+        ));
+        // A file with the same name in another directory.
+        handler.handle_event(event(
+            EventKind::Modify(ModifyKind::Data(notify::event::DataChange::Content)),
+            &["/home/user/rustlings/exercises/11_hashmaps/helpers.rs"],
+        ));
+        drop(handler);
+
+        assert_eq!(update_receiver.iter().collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn skipped_paths() {
+        assert_eq!(
+            n_skipped_paths(EventKind::Modify(ModifyKind::Name(RenameMode::Both))),
+            Some(1),
+        );
+        assert_eq!(
+            n_skipped_paths(EventKind::Modify(ModifyKind::Name(RenameMode::From))),
+            None,
+        );
+        assert_eq!(n_skipped_paths(EventKind::Create(CreateKind::Folder)), None);
+    }
+}
@@ -1,25 +1,39 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Error, Result};
 use crossterm::{
+    cursor::{MoveToColumn, MoveUp, RestorePosition, SavePosition},
     style::{
         Attribute, Attributes, Color, ResetColor, SetAttribute, SetAttributes, SetForegroundColor,
     },
     terminal, QueueableCommand,
 };
 use std::{
-    io::{self, Read, StdoutLock, Write},
+    fs,
+    io::{self, ErrorKind, Read, StdoutLock, Write},
+    process::Child,
     sync::mpsc::{sync_channel, Sender, SyncSender},
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
     app_state::{AppState, ExercisesProgress},
     clear_terminal,
-    exercise::{solution_link_line, RunnableExercise, OUTPUT_CAPACITY},
-    term::progress_bar,
+    diff::unified_diff,
+    exercise::{solution_link_line, test_counts, RunnableExercise, TestCounts, OUTPUT_CAPACITY},
+    term::{format_duration, n_rows, progress_bar, write_ansi},
 };
 
-use super::{terminal_event::terminal_event_handler, InputPauseGuard, WatchEvent};
+use super::{
+    desktop_notification::DesktopNotifier,
+    external_editor,
+    keys::Keys,
+    line_event::line_event_handler,
+    terminal_event::{terminal_event_handler, Scroll},
+    InputPauseGuard, WatchEvent,
+};
 
+const LINE_MODE_WIDTH: u16 = 80;
+const FLASH_DURATION: Duration = Duration::from_millis(150);
 const HEADING_ATTRIBUTES: Attributes = Attributes::none()
     .with(Attribute::Bold)
     .with(Attribute::Underlined);
@@ -34,10 +48,33 @@ enum DoneStatus {
 pub struct WatchState<'a> {
     app_state: &'a mut AppState,
     output: Vec<u8>,
-    show_hint: bool,
+    // The number of hint levels shown so far.
+    n_shown_hints: usize,
     done_status: DoneStatus,
+    // Parsed from the output of the last run of a test exercise. `None` if nothing was parsed.
+    test_counts: Option<TestCounts>,
+    keys: Keys,
     manual_run: bool,
+    // `None` if desktop notifications aren't activated.
+    desktop_notifier: Option<&'a mut DesktopNotifier>,
+    // Used to only notify when the exercise changes from pending to done while working on it.
+    last_run_exercise_ind: Option<usize>,
+    // The number of output lines hidden below the shown ones after scrolling up.
+    scroll_offset: usize,
     term_width: u16,
+    term_height: u16,
+    // The index of the exercise that the time is tracked for and when the tracking started.
+    timer: Option<(usize, Instant)>,
+    timer_paused: bool,
+    // Build the next pending exercise in the background when the current one is done.
+    prefetch: bool,
+    // The index of the exercise that is built in the background and the build process.
+    prefetch_build: Option<(usize, Child)>,
+    // Without a terminal, the output is only appended and commands are read per line.
+    line_mode: bool,
+    // The number of remaining steps of the alert after a rerun and when the next one is due.
+    // The steps alternate between ringing the bell with the line flashed and unflashing it.
+    alert_steps: Option<(u8, Instant)>,
     terminal_event_unpause_sender: SyncSender<()>,
 }
 
@@ -45,20 +82,35 @@ impl<'a> WatchState<'a> {
     pub fn build(
         app_state: &'a mut AppState,
         watch_event_sender: Sender<WatchEvent>,
+        keys: Keys,
         manual_run: bool,
+        desktop_notifier: Option<&'a mut DesktopNotifier>,
+        prefetch: bool,
+        line_mode: bool,
     ) -> Result<Self> {
-        let term_width = terminal::size()
-            .context("Failed to get the terminal size")?
-            .0;
+        let (term_width, term_height) = if line_mode {
+            // Only used for wrapping the progress bar.
+            terminal::size().unwrap_or((LINE_MODE_WIDTH, u16::MAX))
+        } else {
+            terminal::size().context("Failed to get the terminal size")?
+        };
 
         let (terminal_event_unpause_sender, terminal_event_unpause_receiver) = sync_channel(0);
+        let desktop_notifications = desktop_notifier.is_some();
 
+        let event_handler = if line_mode {
+            line_event_handler
+        } else {
+            terminal_event_handler
+        };
         thread::Builder::new()
             .spawn(move || {
-                terminal_event_handler(
+                event_handler(
                     watch_event_sender,
                     terminal_event_unpause_receiver,
+                    keys,
                     manual_run,
+                    desktop_notifications,
                 )
             })
             .context("Failed to spawn a thread to handle terminal events")?;
@@ -66,10 +118,22 @@ impl<'a> WatchState<'a> {
         Ok(Self {
             app_state,
             output: Vec::with_capacity(OUTPUT_CAPACITY),
-            show_hint: false,
+            n_shown_hints: 0,
             done_status: DoneStatus::Pending,
+            test_counts: None,
+            keys,
             manual_run,
+            desktop_notifier,
+            last_run_exercise_ind: None,
+            scroll_offset: 0,
             term_width,
+            term_height,
+            timer: None,
+            timer_paused: false,
+            prefetch,
+            prefetch_build: None,
+            line_mode,
+            alert_steps: None,
             terminal_event_unpause_sender,
         })
     }
@@ -78,19 +142,33 @@ impl<'a> WatchState<'a> {
         // Ignore any input until running the exercise is done.
         let _input_pause_guard = InputPauseGuard::scoped_pause();
 
-        self.show_hint = false;
+        self.n_shown_hints = 0;
+        self.scroll_offset = 0;
+
+        let current_exercise_ind = self.app_state.current_exercise_ind();
+        let rerun = self.last_run_exercise_ind == Some(current_exercise_ind);
+        let was_pending = self.done_status == DoneStatus::Pending && rerun;
+        self.last_run_exercise_ind = Some(current_exercise_ind);
 
         writeln!(
             stdout,
             "\nChecking the exercise `{}`. Please wait…",
             self.app_state.current_exercise().name,
         )?;
+        stdout.flush()?;
+        self.await_prefetch();
 
         let success = self
             .app_state
             .current_exercise()
             .run_exercise(Some(&mut self.output), self.app_state.cmd_runner())?;
         self.output.push(b'\n');
+        // The test output is discarded after the tests passed, e.g. to show Clippy's output.
+        self.test_counts = if self.app_state.current_exercise().test {
+            test_counts(&self.output)
+        } else {
+            None
+        };
         if success {
             self.done_status =
                 if let Some(solution_path) = self.app_state.current_solution_path()? {
@@ -98,6 +176,16 @@ impl<'a> WatchState<'a> {
                 } else {
                     DoneStatus::DoneWithoutSolution
                 };
+
+            if was_pending {
+                let body = format!(
+                    "The exercise `{}` is done 🦀",
+                    self.app_state.current_exercise().name,
+                );
+                self.notify(&body);
+            }
+
+            self.start_prefetch();
         } else {
             self.app_state
                 .set_pending(self.app_state.current_exercise_ind())?;
@@ -105,7 +193,397 @@ impl<'a> WatchState<'a> {
             self.done_status = DoneStatus::Pending;
         }
 
+        self.update_timer()?;
+        self.render(stdout)?;
+
+        if rerun && self.app_state.bell() {
+            self.alert(stdout, success)?;
+        }
+
+        Ok(())
+    }
+
+    // Build the next pending exercise in the background to have it compiled
+    // when the user moves on to it.
+    fn start_prefetch(&mut self) {
+        if !self.prefetch {
+            return;
+        }
+
+        let Some(exercise_ind) = self.app_state.next_pending_exercise_ind() else {
+            return;
+        };
+        if self
+            .prefetch_build
+            .as_ref()
+            .is_some_and(|(ind, _)| *ind == exercise_ind)
+        {
+            return;
+        }
+        self.cancel_prefetch();
+
+        let exercise = &self.app_state.exercises()[exercise_ind];
+        // Errors are ignored because prefetching is only an optimization.
+        if let Ok(child) = self
+            .app_state
+            .cmd_runner()
+            .cargo("build", exercise.name, &exercise.features, None, None)
+            .spawn_background("cargo build …")
+        {
+            self.prefetch_build = Some((exercise_ind, child));
+        }
+    }
+
+    // Commands of other exercises would wait for the background build because of Cargo's lock
+    // of the target directory. Therefore, the build is only awaited for the current exercise.
+    fn await_prefetch(&mut self) {
+        match &mut self.prefetch_build {
+            Some((exercise_ind, child))
+                if *exercise_ind == self.app_state.current_exercise_ind() =>
+            {
+                let _ = child.wait();
+                self.prefetch_build = None;
+            }
+            Some(_) => self.cancel_prefetch(),
+            None => (),
+        }
+    }
+
+    // Kill the background build if it is still running.
+    // The sandbox is cleaned because the build might have been the last use of it.
+    fn cancel_prefetch(&mut self) {
+        let Some((exercise_ind, mut child)) = self.prefetch_build.take() else {
+            return;
+        };
+
+        let _ = child.kill();
+        let _ = child.wait();
+        self.app_state
+            .cmd_runner()
+            .clean_sandbox(self.app_state.exercises()[exercise_ind].name);
+    }
+
+    // Ring the bell and flash the line of the current exercise once after a success
+    // and twice after a failure. The steps after the first one are done by `advance_alert`
+    // to not block the handling of input.
+    fn alert(&mut self, stdout: &mut StdoutLock, success: bool) -> io::Result<()> {
+        let n_steps = if success { 2 } else { 4 };
+        self.alert_steps = Some((n_steps, Instant::now()));
+        self.advance_alert(stdout)
+    }
+
+    /// The time until the next step of the alert is due. `None` without an ongoing alert.
+    pub fn alert_timeout(&self) -> Option<Duration> {
+        self.alert_steps
+            .map(|(_, due)| due.saturating_duration_since(Instant::now()))
+    }
+
+    /// Do the next step of the alert if it is due.
+    pub fn advance_alert(&mut self, stdout: &mut StdoutLock) -> io::Result<()> {
+        let Some((n_steps, due)) = self.alert_steps else {
+            return Ok(());
+        };
+        if Instant::now() < due {
+            return Ok(());
+        }
+
+        if n_steps % 2 == 0 {
+            stdout.write_all(b"\x07")?;
+            if self.line_mode {
+                stdout.flush()?;
+            } else {
+                self.rewrite_current_exercise_line(stdout, true)?;
+            }
+        } else if !self.line_mode {
+            self.rewrite_current_exercise_line(stdout, false)?;
+        }
+
+        self.alert_steps = (n_steps > 1).then(|| (n_steps - 1, Instant::now() + FLASH_DURATION));
+
+        Ok(())
+    }
+
+    pub fn toggle_bell(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        self.app_state.toggle_bell()?;
+        self.render(stdout)?;
+
+        Ok(())
+    }
+
+    // Track the time while the current exercise is pending unless the timer is paused.
+    fn update_timer(&mut self) -> Result<()> {
+        let current_exercise_ind = self.app_state.current_exercise_ind();
+        let running = !self.timer_paused && self.done_status == DoneStatus::Pending;
+
+        if let Some((exercise_ind, _)) = self.timer {
+            if running && exercise_ind == current_exercise_ind {
+                return Ok(());
+            }
+            self.stop_timer()?;
+        }
+
+        if running {
+            self.timer = Some((current_exercise_ind, Instant::now()));
+        }
+
+        Ok(())
+    }
+
+    /// Add the time tracked so far to its exercise in the state file.
+    pub fn stop_timer(&mut self) -> Result<()> {
+        if let Some((exercise_ind, start)) = self.timer.take() {
+            self.app_state
+                .add_secs_spent(exercise_ind, start.elapsed().as_secs())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn toggle_timer_pause(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        self.timer_paused = !self.timer_paused;
+        self.update_timer()?;
+        self.render(stdout)?;
+
+        Ok(())
+    }
+
+    // The time spent on the current exercise after its path.
+    // `None` if no time was tracked for it.
+    fn timer_text(&self) -> Option<String> {
+        let current_exercise_ind = self.app_state.current_exercise_ind();
+        let running_secs = self
+            .timer
+            .filter(|(exercise_ind, _)| *exercise_ind == current_exercise_ind)
+            .map(|(_, start)| start.elapsed().as_secs());
+        let secs = self.app_state.current_exercise().secs_spent + running_secs.unwrap_or(0);
+        if running_secs.is_none() && secs == 0 {
+            return None;
+        }
+
+        let mut text = format!("  Time: {}", format_duration(secs));
+        if self.timer_paused && self.done_status == DoneStatus::Pending {
+            text.push_str(" (paused)");
+        }
+        Some(text)
+    }
+
+    /// Update the running timer in place without rendering everything again.
+    pub fn render_timer(&self, stdout: &mut StdoutLock) -> io::Result<()> {
+        if self.timer.is_none() || self.line_mode {
+            return Ok(());
+        }
+        let Some(timer_text) = self.timer_text() else {
+            return Ok(());
+        };
+
+        let term_width = usize::from(self.term_width.max(1));
+        let column = (self.current_exercise_prefix().len()
+            + self.app_state.current_exercise().path.chars().count())
+            % term_width;
+        // Everything below has to move if the timer wraps to a new row.
+        if column + timer_text.len() > term_width {
+            return self.render(stdout);
+        }
+
+        stdout
+            .queue(SavePosition)?
+            .queue(MoveUp(self.rows_below_current_exercise_line() as u16))?
+            .queue(MoveToColumn(column as u16))?;
+        stdout.write_all(timer_text.as_bytes())?;
+        stdout.queue(RestorePosition)?;
+        stdout.flush()
+    }
+
+    // The rows between the last row of the line of the current exercise and the cursor at the
+    // end of the prompt: the empty line, the prerequisites and the prompt.
+    fn rows_below_current_exercise_line(&self) -> usize {
+        self.prompt_rows()
+            + 1
+            + self
+                .prerequisites_line()
+                .map_or(0, |line| n_rows(line.as_bytes(), self.term_width))
+    }
+
+    // Write the line of the current exercise again in place, optionally with inverted colors.
+    fn rewrite_current_exercise_line(
+        &self,
+        stdout: &mut StdoutLock,
+        inverted: bool,
+    ) -> io::Result<()> {
+        let width = self.current_exercise_prefix().len()
+            + self.app_state.current_exercise().path.chars().count()
+            + self.timer_text().map_or(0, |timer_text| timer_text.len());
+        let rows = width.div_ceil(usize::from(self.term_width.max(1))).max(1);
+
+        stdout
+            .queue(SavePosition)?
+            .queue(MoveUp(
+                (self.rows_below_current_exercise_line() + rows - 1) as u16,
+            ))?
+            .queue(MoveToColumn(0))?;
+        if inverted {
+            stdout.queue(SetAttribute(Attribute::Reverse))?;
+        }
+        self.write_current_exercise_line(stdout)?;
+        stdout
+            .queue(SetAttribute(Attribute::Reset))?
+            .queue(RestorePosition)?;
+        stdout.flush()
+    }
+
+    fn write_current_exercise_line(&self, stdout: &mut StdoutLock) -> io::Result<()> {
+        stdout.write_all(self.current_exercise_prefix().as_bytes())?;
+        self.app_state
+            .current_exercise()
+            .terminal_file_link(stdout)?;
+        if let Some(timer_text) = self.timer_text() {
+            stdout.write_all(timer_text.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Show the Clippy warnings of the current exercise.
+    /// The done status isn't changed because warnings don't affect it.
+    pub fn run_clippy(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        // Ignore any input until Clippy is done.
+        let _input_pause_guard = InputPauseGuard::scoped_pause();
+
+        writeln!(
+            stdout,
+            "\nRunning Clippy on the exercise `{}`. Please wait…",
+            self.app_state.current_exercise().name,
+        )?;
+        stdout.flush()?;
+        self.await_prefetch();
+
+        self.scroll_offset = 0;
+        let success = self
+            .app_state
+            .current_exercise()
+            .run_clippy(&mut self.output, self.app_state.cmd_runner())?;
+        if success && self.output.trim_ascii().is_empty() {
+            self.output.clear();
+            write_ansi(&mut self.output, SetForegroundColor(Color::Green));
+            self.output
+                .extend_from_slice("Clippy found no warnings ✓".as_bytes());
+            write_ansi(&mut self.output, ResetColor);
+            self.output.push(b'\n');
+        }
+        self.output.push(b'\n');
+
+        self.render(stdout)?;
+        Ok(())
+    }
+
+    // Send a desktop notification if activated. A failure is shown in the output.
+    fn notify(&mut self, body: &str) {
+        if let Some(error_msg) = self
+            .desktop_notifier
+            .as_deref_mut()
+            .and_then(|notifier| notifier.notify("Rustlings", body))
+        {
+            self.output.extend_from_slice(error_msg.as_bytes());
+            self.output.extend_from_slice(b"\n\n");
+        }
+    }
+
+    /// Send a desktop notification after the final message if activated.
+    pub fn notify_all_done(&mut self, stdout: &mut StdoutLock) -> io::Result<()> {
+        if let Some(error_msg) = self
+            .desktop_notifier
+            .as_deref_mut()
+            .and_then(|notifier| notifier.notify("Rustlings", "All exercises are done 🎉"))
+        {
+            writeln!(stdout, "{error_msg}")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn toggle_notifications(&mut self, stdout: &mut StdoutLock) -> io::Result<()> {
+        if let Some(notifier) = self.desktop_notifier.as_deref_mut() {
+            notifier.muted = !notifier.muted;
+            self.render(stdout)?;
+        }
+
+        Ok(())
+    }
+
+    /// Show the diff between the current exercise file and its solution.
+    /// Nothing is changed, not even the done status.
+    pub fn show_solution_diff(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        let exercise = self.app_state.current_exercise();
+        let sol_path = exercise.sol_path();
+        self.output.clear();
+        self.scroll_offset = 0;
+
+        let solution = match fs::read_to_string(&sol_path) {
+            Ok(v) => v,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                self.output
+                    .extend_from_slice(b"No solution available for this exercise\n\n");
+                self.render(stdout)?;
+                return Ok(());
+            }
+            Err(e) => {
+                return Err(
+                    Error::from(e).context(format!("Failed to read the solution file {sol_path}"))
+                )
+            }
+        };
+        let exercise_content = fs::read_to_string(exercise.path)
+            .with_context(|| format!("Failed to read the exercise file {}", exercise.path))?;
+
+        let Some(diff) = unified_diff(&exercise_content, &solution, exercise.path, &sol_path)
+        else {
+            self.output
+                .extend_from_slice(b"The exercise file is identical to the solution\n\n");
+            self.render(stdout)?;
+            return Ok(());
+        };
+
+        for line in diff.lines() {
+            let color = if line.starts_with("+++") || line.starts_with("---") {
+                None
+            } else if line.starts_with('+') {
+                Some(Color::Green)
+            } else if line.starts_with('-') {
+                Some(Color::Red)
+            } else if line.starts_with("@@") {
+                Some(Color::Cyan)
+            } else {
+                None
+            };
+
+            if let Some(color) = color {
+                write_ansi(&mut self.output, SetForegroundColor(color));
+                self.output.extend_from_slice(line.as_bytes());
+                write_ansi(&mut self.output, ResetColor);
+            } else {
+                self.output.extend_from_slice(line.as_bytes());
+            }
+            self.output.push(b'\n');
+        }
+        self.output.push(b'\n');
+
+        self.render(stdout)?;
+        Ok(())
+    }
+
+    /// Open the current exercise file in the editor from `$EDITOR` or `$VISUAL`.
+    /// A failure is shown in the output instead of exiting the watch mode.
+    pub fn open_in_editor(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        if let Err(e) = external_editor::open(self.app_state.current_exercise().path) {
+            writeln!(
+                self.output,
+                "{e:#}\nSet the environment variable `EDITOR` to the command of your editor\n",
+            )?;
+        }
+
         self.render(stdout)?;
+        self.terminal_event_unpause_sender.send(())?;
+
         Ok(())
     }
 
@@ -114,32 +592,25 @@ impl<'a> WatchState<'a> {
 
         stdout.write_all(b"Resetting will undo all your changes to the file ")?;
         stdout.write_all(self.app_state.current_exercise().path.as_bytes())?;
-        stdout.write_all(b"\nReset (y/n)? ")?;
+        stdout.write_all(b"\nReset (y/N)? ")?;
         stdout.flush()?;
 
-        {
-            let mut stdin = io::stdin().lock();
-            let mut answer = [0];
-            loop {
-                stdin
-                    .read_exact(&mut answer)
-                    .context("Failed to read the user's input")?;
-
-                match answer[0] {
-                    b'y' | b'Y' => {
-                        self.app_state.reset_current_exercise()?;
+        let mut answer = [0];
+        io::stdin()
+            .lock()
+            .read_exact(&mut answer)
+            .context("Failed to read the user's input")?;
 
-                        // The file watcher reruns the exercise otherwise.
-                        if self.manual_run {
-                            self.run_current_exercise(stdout)?;
-                        }
-                    }
-                    b'n' | b'N' => self.render(stdout)?,
-                    _ => continue,
-                }
+        // Any other key cancels because resetting is destructive.
+        if matches!(answer[0], b'y' | b'Y') {
+            self.app_state.reset_current_exercise()?;
 
-                break;
+            // The file watcher reruns the exercise otherwise.
+            if self.manual_run {
+                self.run_current_exercise(stdout)?;
             }
+        } else {
+            self.render(stdout)?;
         }
 
         self.terminal_event_unpause_sender.send(())?;
@@ -169,10 +640,106 @@ impl<'a> WatchState<'a> {
         self.app_state.done_current_exercise::<true>(stdout)
     }
 
+    /// Go back to the previous exercise in the order of `info.toml` even if it is done.
+    pub fn previous_exercise(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        let Some(previous_exercise_ind) = self.app_state.current_exercise_ind().checked_sub(1)
+        else {
+            return Ok(());
+        };
+
+        // The file watcher already watches all exercise files.
+        // Only file changes of the current exercise are handled.
+        self.app_state
+            .set_current_exercise_ind(previous_exercise_ind)?;
+        self.run_current_exercise(stdout)
+    }
+
+    /// Skip the current exercise if it is pending and continue at the next pending exercise.
+    /// The skipped exercise comes back after all other pending exercises are done.
+    pub fn skip_exercise(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        if self.done_status != DoneStatus::Pending {
+            return Ok(());
+        }
+
+        if self.app_state.skip_current_exercise()? {
+            return self.run_current_exercise(stdout);
+        }
+
+        self.render(stdout)?;
+        Ok(())
+    }
+
+    /// Prompt for the name or a prefix of the name of an exercise and continue at it.
+    /// Like in the list, its done status isn't changed.
+    pub fn go_to_exercise(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        clear_terminal(stdout)?;
+        stdout.write_all(b"Go to the exercise (Enter to confirm, Esc to cancel): ")?;
+        stdout.flush()?;
+
+        let mut input = String::with_capacity(32);
+        let confirmed = {
+            let mut stdin = io::stdin().lock();
+            let mut byte = [0];
+            loop {
+                stdin
+                    .read_exact(&mut byte)
+                    .context("Failed to read the user's input")?;
+
+                match byte[0] {
+                    b'\n' | b'\r' => break true,
+                    // Escape
+                    0x1b => break false,
+                    // Backspace or delete
+                    0x08 | 0x7f => {
+                        if input.pop().is_none() {
+                            continue;
+                        }
+
+                        // The terminal echoes the input on Windows and in the line mode.
+                        if !cfg!(windows) && !self.line_mode {
+                            stdout.write_all(b"\x08 \x08")?;
+                            stdout.flush()?;
+                        }
+                    }
+                    byte if byte.is_ascii_graphic() => {
+                        input.push(char::from(byte));
+                        if !cfg!(windows) && !self.line_mode {
+                            stdout.write_all(&[byte])?;
+                            stdout.flush()?;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        };
+
+        let input = input.trim();
+        if !confirmed || input.is_empty() {
+            self.render(stdout)?;
+        } else {
+            match self.app_state.exercise_ind_by_prefix(input) {
+                Ok(exercise_ind) => {
+                    self.app_state.set_current_exercise_ind(exercise_ind)?;
+                    self.run_current_exercise(stdout)?;
+                }
+                Err(e) => {
+                    self.output.clear();
+                    self.scroll_offset = 0;
+                    writeln!(self.output, "{e}\n")?;
+                    self.render(stdout)?;
+                }
+            }
+        }
+
+        self.terminal_event_unpause_sender.send(())?;
+
+        Ok(())
+    }
+
     fn show_prompt(&self, stdout: &mut StdoutLock) -> io::Result<()> {
         if self.done_status != DoneStatus::Pending {
             stdout.queue(SetAttribute(Attribute::Bold))?;
-            stdout.write_all(b"n")?;
+            write!(stdout, "{}", self.keys.next)?;
             stdout.queue(ResetColor)?;
             stdout.write_all(b":")?;
             stdout.queue(SetAttribute(Attribute::Underlined))?;
@@ -181,27 +748,188 @@ impl<'a> WatchState<'a> {
             stdout.write_all(b" / ")?;
         }
 
-        let mut show_key = |key, postfix| {
+        for (key, postfix) in self.prompt_keys() {
             stdout.queue(SetAttribute(Attribute::Bold))?;
-            stdout.write_all(&[key])?;
+            write!(stdout, "{key}")?;
             stdout.queue(ResetColor)?;
-            stdout.write_all(postfix)
-        };
+            stdout.write_all(postfix.as_bytes())?;
+        }
+
+        stdout.flush()
+    }
+
+    // The keys in the prompt after `n:next` with their descriptions.
+    fn prompt_keys(&self) -> Vec<(char, &'static str)> {
+        let mut keys = Vec::with_capacity(16);
 
         if self.manual_run {
-            show_key(b'r', b":run / ")?;
+            keys.push((self.keys.run, ":run / "));
+        }
+
+        let hints = &self.app_state.current_exercise().hints;
+        if self.n_shown_hints == 0 && !hints.is_empty() {
+            keys.push((self.keys.hint, ":hint / "));
+        } else if self.n_shown_hints < hints.len() {
+            keys.push((self.keys.hint, ":next hint / "));
+        }
+
+        keys.push(('b', ":previous / "));
+        if self.done_status == DoneStatus::Pending {
+            keys.push(('s', ":skip / "));
+        }
+        keys.push(('g', ":go to / "));
+        if !self.line_mode {
+            keys.push((self.keys.list, ":list / "));
+        }
+        keys.extend([
+            ('c', ":check all / "),
+            ('p', ":clippy / "),
+            ('d', ":diff to solution / "),
+            ('o', ":open in editor / "),
+            ('x', ":reset / "),
+        ]);
+        if self.done_status == DoneStatus::Pending {
+            if self.timer_paused {
+                keys.push(('P', ":resume timer / "));
+            } else {
+                keys.push(('P', ":pause timer / "));
+            }
+        }
+        if self.app_state.bell() {
+            keys.push(('B', ":bell (on) / "));
+        } else {
+            keys.push(('B', ":bell (off) / "));
         }
+        if let Some(notifier) = self.desktop_notifier.as_deref() {
+            if notifier.muted {
+                keys.push(('m', ":unmute / "));
+            } else {
+                keys.push(('m', ":mute / "));
+            }
+        }
+        keys.push((self.keys.quit, ":quit ? "));
 
-        if !self.show_hint {
-            show_key(b'h', b":hint / ")?;
+        keys
+    }
+
+    // The number of terminal rows below the output.
+    fn footer_rows(&self) -> usize {
+        let hints = &self.app_state.current_exercise().hints;
+        // A heading and an empty line for every shown hint.
+        let mut rows = hints[..self.n_shown_hints]
+            .iter()
+            .map(|hint| {
+                2 + hint
+                    .lines()
+                    .map(|line| n_rows(line.as_bytes(), self.term_width))
+                    .sum::<usize>()
+            })
+            .sum::<usize>();
+
+        match self.done_status {
+            // The done line, the solution, the line about moving on and an empty line.
+            DoneStatus::DoneWithSolution(_) => rows += 4,
+            DoneStatus::DoneWithoutSolution => rows += 3,
+            DoneStatus::Pending => (),
         }
 
-        show_key(b'l', b":list / ")?;
-        show_key(b'c', b":check all / ")?;
-        show_key(b'x', b":reset / ")?;
-        show_key(b'q', b":quit ? ")?;
+        if self.test_counts.is_some() {
+            rows += 1;
+        }
 
-        stdout.flush()
+        // The progress bar, the current exercise and an empty line.
+        rows += 3;
+        if self.prerequisites_line().is_some() {
+            rows += 1;
+        }
+
+        rows + self.prompt_rows()
+    }
+
+    fn prompt_rows(&self) -> usize {
+        let mut prompt_width = 0;
+        if self.done_status != DoneStatus::Pending {
+            prompt_width += "n:next / ".len();
+        }
+
+        prompt_width += self
+            .prompt_keys()
+            .iter()
+            .map(|(_, postfix)| 1 + postfix.len())
+            .sum::<usize>();
+        prompt_width.div_ceil(usize::from(self.term_width.max(1)))
+    }
+
+    fn current_exercise_prefix(&self) -> String {
+        format!(
+            "Current exercise {}/{}: ",
+            self.app_state.current_exercise_ind() + 1,
+            self.app_state.exercises().len(),
+        )
+    }
+
+    // The learner might have jumped ahead in the list.
+    fn prerequisites_line(&self) -> Option<String> {
+        let pending_prerequisites = self
+            .app_state
+            .pending_prerequisites(self.app_state.current_exercise_ind());
+        if pending_prerequisites.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "Builds on exercises that aren't done yet: {}",
+            pending_prerequisites.join(", "),
+        ))
+    }
+
+    // The lines of the output and the number of rows available for them if the output doesn't
+    // fit on the screen. One row is reserved for the scroll indicator.
+    fn scrollable_output(&self) -> Option<(Vec<&[u8]>, usize)> {
+        // The whole output is printed in the line mode.
+        if self.line_mode {
+            return None;
+        }
+
+        let mut lines = self.output.split(|byte| *byte == b'\n').collect::<Vec<_>>();
+        // The output ends with a newline.
+        if lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let rows = usize::from(self.term_height).saturating_sub(self.footer_rows());
+        let output_rows = lines
+            .iter()
+            .map(|line| n_rows(line, self.term_width))
+            .sum::<usize>();
+        if output_rows <= rows {
+            return None;
+        }
+
+        Some((lines, rows.saturating_sub(1).max(1)))
+    }
+
+    pub fn scroll(&mut self, scroll: Scroll, stdout: &mut StdoutLock) -> io::Result<()> {
+        let Some((lines, rows)) = self.scrollable_output() else {
+            return Ok(());
+        };
+        let max_offset = max_scroll_offset(&lines, rows, self.term_width);
+
+        let offset = self.scroll_offset.min(max_offset);
+        let offset = match scroll {
+            Scroll::LineUp => offset + 1,
+            Scroll::LineDown => offset.saturating_sub(1),
+            Scroll::PageUp => offset + rows,
+            Scroll::PageDown => offset.saturating_sub(rows),
+        }
+        .min(max_offset);
+
+        if offset != self.scroll_offset {
+            self.scroll_offset = offset;
+            self.render(stdout)?;
+        }
+
+        Ok(())
     }
 
     pub fn render(&self, stdout: &mut StdoutLock) -> io::Result<()> {
@@ -209,17 +937,46 @@ impl<'a> WatchState<'a> {
         stdout.write_all(b"\n")?;
         clear_terminal(stdout)?;
 
-        stdout.write_all(&self.output)?;
+        if let Some((lines, rows)) = self.scrollable_output() {
+            let end = lines.len()
+                - self
+                    .scroll_offset
+                    .min(max_scroll_offset(&lines, rows, self.term_width));
+            let start = window_start(&lines[..end], rows, self.term_width);
+            for line in &lines[start..end] {
+                stdout.write_all(line)?;
+                stdout.write_all(b"\n")?;
+            }
 
-        if self.show_hint {
+            // The window can end in the middle of colored output.
+            stdout
+                .queue(SetAttribute(Attribute::Reset))?
+                .queue(SetAttribute(Attribute::Dim))?;
+            writeln!(
+                stdout,
+                "Lines {}–{end} of {} (scroll with ↑/↓ and PgUp/PgDn)",
+                start + 1,
+                lines.len(),
+            )?;
+            stdout.queue(SetAttribute(Attribute::Reset))?;
+        } else {
+            stdout.write_all(&self.output)?;
+        }
+
+        let hints = &self.app_state.current_exercise().hints;
+        for (level, hint) in (1..).zip(&hints[..self.n_shown_hints]) {
             stdout
                 .queue(SetAttributes(HEADING_ATTRIBUTES))?
                 .queue(SetForegroundColor(Color::Cyan))?;
-            stdout.write_all(b"Hint")?;
+            if hints.len() == 1 {
+                stdout.write_all(b"Hint")?;
+            } else {
+                write!(stdout, "Hint {level}/{}", hints.len())?;
+            }
             stdout.queue(ResetColor)?;
             stdout.write_all(b"\n")?;
 
-            stdout.write_all(self.app_state.current_exercise().hint.as_bytes())?;
+            stdout.write_all(hint.as_bytes())?;
             stdout.write_all(b"\n\n")?;
         }
 
@@ -235,12 +992,27 @@ impl<'a> WatchState<'a> {
                 solution_link_line(stdout, solution_path)?;
             }
 
-            stdout.write_all(
-                "When done experimenting, enter `n` to move on to the next exercise 🦀\n\n"
-                    .as_bytes(),
+            writeln!(
+                stdout,
+                "When done experimenting, enter `{}` to move on to the next exercise 🦀\n",
+                self.keys.next,
             )?;
         }
 
+        if let Some(TestCounts { passed, failed }) = self.test_counts {
+            let color = if failed == 0 {
+                Color::Green
+            } else {
+                Color::Yellow
+            };
+            stdout
+                .queue(SetAttribute(Attribute::Bold))?
+                .queue(SetForegroundColor(color))?;
+            write!(stdout, "{passed}/{} tests passing", passed + failed)?;
+            stdout.queue(ResetColor)?;
+            stdout.write_all(b"\n")?;
+        }
+
         progress_bar(
             stdout,
             self.app_state.n_done(),
@@ -248,11 +1020,15 @@ impl<'a> WatchState<'a> {
             self.term_width,
         )?;
 
-        stdout.write_all(b"\nCurrent exercise: ")?;
-        self.app_state
-            .current_exercise()
-            .terminal_file_link(stdout)?;
-        stdout.write_all(b"\n\n")?;
+        stdout.write_all(b"\n")?;
+        self.write_current_exercise_line(stdout)?;
+        stdout.write_all(b"\n")?;
+
+        if let Some(prerequisites_line) = self.prerequisites_line() {
+            stdout.write_all(prerequisites_line.as_bytes())?;
+            stdout.write_all(b"\n")?;
+        }
+        stdout.write_all(b"\n")?;
 
         self.show_prompt(stdout)?;
 
@@ -260,8 +1036,9 @@ impl<'a> WatchState<'a> {
     }
 
     pub fn show_hint(&mut self, stdout: &mut StdoutLock) -> io::Result<()> {
-        if !self.show_hint {
-            self.show_hint = true;
+        // Show the next level.
+        if self.n_shown_hints < self.app_state.current_exercise().hints.len() {
+            self.n_shown_hints += 1;
             self.render(stdout)?;
         }
 
@@ -271,6 +1048,7 @@ impl<'a> WatchState<'a> {
     pub fn check_all_exercises(&mut self, stdout: &mut StdoutLock) -> Result<ExercisesProgress> {
         // Ignore any input until checking all exercises is done.
         let _input_pause_guard = InputPauseGuard::scoped_pause();
+        self.cancel_prefetch();
 
         if let Some(first_pending_exercise_ind) = self.app_state.check_all_exercises(stdout)? {
             // Only change exercise if the current one is done.
@@ -287,12 +1065,56 @@ impl<'a> WatchState<'a> {
         }
     }
 
-    pub fn update_term_width(&mut self, width: u16, stdout: &mut StdoutLock) -> io::Result<()> {
-        if self.term_width != width {
+    pub fn update_term_size(
+        &mut self,
+        width: u16,
+        height: u16,
+        stdout: &mut StdoutLock,
+    ) -> io::Result<()> {
+        if self.term_width != width || self.term_height != height {
             self.term_width = width;
+            self.term_height = height;
             self.render(stdout)?;
         }
 
         Ok(())
     }
 }
+
+impl Drop for WatchState<'_> {
+    fn drop(&mut self) {
+        self.cancel_prefetch();
+    }
+}
+
+// The index of the first line that fits into the rows with the last line at the end.
+// At least the last line is shown.
+fn window_start(lines: &[&[u8]], rows: usize, term_width: u16) -> usize {
+    let mut used_rows = 0;
+    let mut start = lines.len();
+    while let Some(line) = start.checked_sub(1).map(|ind| lines[ind]) {
+        used_rows += n_rows(line, term_width);
+        if used_rows > rows {
+            break;
+        }
+        start -= 1;
+    }
+
+    start.min(lines.len().saturating_sub(1))
+}
+
+// The highest number of lines that can be hidden below the shown ones
+// to still fill the rows when scrolling up to the first line.
+fn max_scroll_offset(lines: &[&[u8]], rows: usize, term_width: u16) -> usize {
+    let mut used_rows = 0;
+    let mut n_first_lines = 0;
+    for line in lines {
+        used_rows += n_rows(line, term_width);
+        if used_rows > rows {
+            break;
+        }
+        n_first_lines += 1;
+    }
+
+    lines.len().saturating_sub(n_first_lines.max(1))
+}
@@ -4,22 +4,86 @@ use std::sync::{
     mpsc::{Receiver, Sender},
 };
 
-use super::{WatchEvent, EXERCISE_RUNNING};
+use super::{keys::Keys, WatchEvent, EXERCISE_RUNNING};
+
+#[derive(Clone, Copy)]
+pub enum Scroll {
+    LineUp,
+    LineDown,
+    PageUp,
+    PageDown,
+}
 
 pub enum InputEvent {
     Next,
+    Previous,
+    Skip,
+    GoTo,
     Run,
     Hint,
     List,
     CheckAll,
+    Clippy,
+    SolutionDiff,
+    OpenEditor,
+    ToggleNotifications,
+    ToggleTimerPause,
+    ToggleBell,
+    Scroll(Scroll),
     Reset,
     Quit,
 }
 
+impl InputEvent {
+    /// The event of the key of a letter or `None` if the key isn't used.
+    pub fn from_char(
+        c: char,
+        keys: Keys,
+        manual_run: bool,
+        desktop_notifications: bool,
+    ) -> Option<Self> {
+        let input_event = match c {
+            // The configurable keys first because they can replace unused default keys.
+            c if c == keys.next => Self::Next,
+            c if c == keys.run => {
+                if !manual_run {
+                    return None;
+                }
+                Self::Run
+            }
+            c if c == keys.hint => Self::Hint,
+            c if c == keys.list => Self::List,
+            c if c == keys.quit => Self::Quit,
+            'b' => Self::Previous,
+            's' => Self::Skip,
+            'g' | ':' => Self::GoTo,
+            'c' => Self::CheckAll,
+            'p' => Self::Clippy,
+            'd' => Self::SolutionDiff,
+            'o' => Self::OpenEditor,
+            'm' if desktop_notifications => Self::ToggleNotifications,
+            'P' => Self::ToggleTimerPause,
+            'B' => Self::ToggleBell,
+            'x' => Self::Reset,
+            _ => return None,
+        };
+
+        Some(input_event)
+    }
+
+    // The prompts and terminal editors read the input themselves.
+    #[inline]
+    pub fn reads_input(&self) -> bool {
+        matches!(self, Self::Reset | Self::GoTo | Self::OpenEditor)
+    }
+}
+
 pub fn terminal_event_handler(
     sender: Sender<WatchEvent>,
     unpause_receiver: Receiver<()>,
+    keys: Keys,
     manual_run: bool,
+    desktop_notifications: bool,
 ) {
     let last_watch_event = loop {
         match event::read() {
@@ -34,33 +98,37 @@ pub fn terminal_event_handler(
                 }
 
                 let input_event = match key.code {
-                    KeyCode::Char('n') => InputEvent::Next,
-                    KeyCode::Char('r') if manual_run => InputEvent::Run,
-                    KeyCode::Char('h') => InputEvent::Hint,
-                    KeyCode::Char('l') => break WatchEvent::Input(InputEvent::List),
-                    KeyCode::Char('c') => InputEvent::CheckAll,
-                    KeyCode::Char('x') => {
-                        if sender.send(WatchEvent::Input(InputEvent::Reset)).is_err() {
-                            return;
+                    KeyCode::Char(c) => {
+                        match InputEvent::from_char(c, keys, manual_run, desktop_notifications) {
+                            Some(InputEvent::List) => break WatchEvent::Input(InputEvent::List),
+                            Some(InputEvent::Quit) => break WatchEvent::Input(InputEvent::Quit),
+                            Some(input_event) => input_event,
+                            None => continue,
                         }
-
-                        // Pause input until quitting the confirmation prompt.
-                        if unpause_receiver.recv().is_err() {
-                            return;
-                        };
-
-                        continue;
                     }
-                    KeyCode::Char('q') => break WatchEvent::Input(InputEvent::Quit),
+                    KeyCode::Up => InputEvent::Scroll(Scroll::LineUp),
+                    KeyCode::Down => InputEvent::Scroll(Scroll::LineDown),
+                    KeyCode::PageUp => InputEvent::Scroll(Scroll::PageUp),
+                    KeyCode::PageDown => InputEvent::Scroll(Scroll::PageDown),
                     _ => continue,
                 };
 
+                let prompt = input_event.reads_input();
+
                 if sender.send(WatchEvent::Input(input_event)).is_err() {
                     return;
                 }
+
+                // Pause input until quitting the prompt or the editor.
+                if prompt && unpause_receiver.recv().is_err() {
+                    return;
+                }
             }
-            Ok(Event::Resize(width, _)) => {
-                if sender.send(WatchEvent::TerminalResize { width }).is_err() {
+            Ok(Event::Resize(width, height)) => {
+                if sender
+                    .send(WatchEvent::TerminalResize { width, height })
+                    .is_err()
+                {
                     return;
                 }
             }
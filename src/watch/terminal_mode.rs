@@ -0,0 +1,196 @@
+use anyhow::{Context, Result};
+use rustix::termios::{self, LocalModes, OptionalActions, Termios};
+use signal_hook::{
+    consts::{SIGCONT, SIGTSTP},
+    iterator::{Handle, Signals},
+    low_level::emulate_default_handler,
+};
+use std::{
+    io,
+    sync::{mpsc::Sender, Mutex, MutexGuard, PoisonError},
+    thread,
+};
+
+use super::WatchEvent;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum State {
+    // The original modes are applied, e.g. before the watch mode or while an editor runs in it.
+    Released,
+    // The modes of the watch mode are applied.
+    Watch,
+    // Stopped after restoring the original modes.
+    Suspended,
+}
+
+impl State {
+    // The next state on `SIGTSTP` and whether the original modes need to be restored
+    // before stopping.
+    fn suspend(self) -> (Self, bool) {
+        match self {
+            Self::Watch => (Self::Suspended, true),
+            Self::Released | Self::Suspended => (self, false),
+        }
+    }
+
+    // The next state on `SIGCONT` and whether the modes of the watch mode need to be applied
+    // again followed by a redraw.
+    // After being stopped without `SIGTSTP` (e.g. by `SIGSTOP`), the shell might have reset
+    // the terminal too.
+    fn resume(self) -> (Self, bool) {
+        match self {
+            Self::Watch | Self::Suspended => (Self::Watch, true),
+            Self::Released => (self, false),
+        }
+    }
+}
+
+struct Terminal {
+    state: State,
+    // The modes before entering the watch mode.
+    original: Option<Termios>,
+}
+
+static TERMINAL: Mutex<Terminal> = Mutex::new(Terminal {
+    state: State::Released,
+    original: None,
+});
+
+// A panic while holding the lock doesn't leave the terminal in an inconsistent state.
+fn terminal() -> MutexGuard<'static, Terminal> {
+    TERMINAL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn apply(termios: &Termios) -> io::Result<()> {
+    termios::tcsetattr(rustix::stdio::stdin(), OptionalActions::Now, termios)?;
+    Ok(())
+}
+
+impl Terminal {
+    fn apply_watch_modes(&mut self) -> io::Result<()> {
+        if let Some(original) = &self.original {
+            let mut termios = original.clone();
+            // Disable stdin line buffering and hide input.
+            termios.local_modes -= LocalModes::ICANON | LocalModes::ECHO;
+            apply(&termios)?;
+        }
+
+        self.state = State::Watch;
+        Ok(())
+    }
+
+    fn apply_original_modes(&mut self, state: State) -> io::Result<()> {
+        if let Some(original) = &self.original {
+            apply(original)?;
+        }
+
+        self.state = state;
+        Ok(())
+    }
+}
+
+/// Save the original modes of the terminal and apply the modes of the watch mode.
+pub fn enter() -> io::Result<()> {
+    let mut terminal = terminal();
+    terminal.original = Some(termios::tcgetattr(rustix::stdio::stdin())?);
+    terminal.apply_watch_modes()
+}
+
+/// Restore the original modes, e.g. to give the terminal to an editor or when exiting.
+pub fn release() -> io::Result<()> {
+    terminal().apply_original_modes(State::Released)
+}
+
+/// Apply the modes of the watch mode again after `release`.
+pub fn reclaim() -> io::Result<()> {
+    terminal().apply_watch_modes()
+}
+
+// Restore the terminal if needed and stop the process like without a handler.
+fn suspend() -> io::Result<()> {
+    {
+        let mut terminal = terminal();
+        let (state, restore) = terminal.state.suspend();
+        if restore {
+            terminal.apply_original_modes(state)?;
+        }
+    }
+
+    // Not holding the lock while stopped.
+    emulate_default_handler(SIGTSTP)
+}
+
+// Returns whether the watch mode needs a redraw.
+fn resume() -> io::Result<bool> {
+    let mut terminal = terminal();
+    let (_, reclaim) = terminal.state.resume();
+    if reclaim {
+        terminal.apply_watch_modes()?;
+    }
+
+    Ok(reclaim)
+}
+
+/// Handles suspending the watch mode (Ctrl+Z) and continuing it (`fg`).
+/// The original modes of the terminal are restored before stopping.
+/// On continuing, the modes of the watch mode are applied again and a redraw is requested.
+/// The signals are handled until it is dropped.
+pub struct SuspendHandler(Handle);
+
+impl SuspendHandler {
+    pub fn build(watch_event_sender: Sender<WatchEvent>) -> Result<Self> {
+        let mut signals = Signals::new([SIGTSTP, SIGCONT])
+            .context("Failed to register handlers for the signals SIGTSTP and SIGCONT")?;
+        let handle = signals.handle();
+
+        thread::Builder::new()
+            .spawn(move || {
+                for signal in signals.forever() {
+                    let res = match signal {
+                        SIGTSTP => suspend().map(|()| false),
+                        SIGCONT => resume(),
+                        _ => continue,
+                    };
+
+                    let event = match res {
+                        Ok(false) => continue,
+                        Ok(true) => WatchEvent::Continue,
+                        Err(e) => WatchEvent::TerminalEventErr(e),
+                    };
+                    if watch_event_sender.send(event).is_err() {
+                        return;
+                    }
+                }
+            })
+            .context("Failed to spawn a thread to handle suspending the watch mode")?;
+
+        Ok(Self(handle))
+    }
+}
+
+impl Drop for SuspendHandler {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suspend_and_resume() {
+        assert_eq!(State::Watch.suspend(), (State::Suspended, true));
+        assert_eq!(State::Suspended.resume(), (State::Watch, true));
+
+        // Suspended twice before continuing.
+        assert_eq!(State::Suspended.suspend(), (State::Suspended, false));
+
+        // Stopped by `SIGSTOP` which can't be handled.
+        assert_eq!(State::Watch.resume(), (State::Watch, true));
+
+        // An editor is responsible for the terminal.
+        assert_eq!(State::Released.suspend(), (State::Released, false));
+        assert_eq!(State::Released.resume(), (State::Released, false));
+    }
+}
@@ -1,8 +1,16 @@
 use std::{
     env::{self, consts::EXE_SUFFIX},
-    process::{Command, Stdio},
+    ffi::OsString,
+    fs,
+    io::{Read, Write},
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
     str::from_utf8,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
 };
+use tempfile::TempDir;
 
 enum Output<'a> {
     FullStdout(&'a str),
@@ -12,10 +20,25 @@ enum Output<'a> {
 
 use Output::*;
 
+fn rustlings_bin() -> OsString {
+    let mut path = env::current_exe().unwrap();
+    // Pop test binary name
+    path.pop();
+    // Pop `/deps`
+    path.pop();
+
+    path.push("rustlings");
+    let mut path = path.into_os_string();
+    path.push(EXE_SUFFIX);
+    path
+}
+
 #[derive(Default)]
 struct Cmd<'a> {
     current_dir: Option<&'a str>,
     args: &'a [&'a str],
+    envs: &'a [(&'a str, &'a str)],
+    stdin: Option<&'a str>,
     output: Option<Output<'a>>,
 }
 
@@ -32,58 +55,84 @@ impl<'a> Cmd<'a> {
         self
     }
 
+    #[inline]
+    fn envs(&mut self, envs: &'a [(&'a str, &'a str)]) -> &mut Self {
+        self.envs = envs;
+        self
+    }
+
+    // Written to the command's stdin which is otherwise null.
+    #[inline]
+    fn stdin(&mut self, stdin: &'a str) -> &mut Self {
+        self.stdin = Some(stdin);
+        self
+    }
+
     #[inline]
     fn output(&mut self, output: Output<'a>) -> &mut Self {
         self.output = Some(output);
         self
     }
 
-    fn assert(&self, success: bool) {
-        let rustlings_bin = {
-            let mut path = env::current_exe().unwrap();
-            // Pop test binary name
-            path.pop();
-            // Pop `/deps`
-            path.pop();
-
-            path.push("rustlings");
-            let mut path = path.into_os_string();
-            path.push(EXE_SUFFIX);
-            path
-        };
-
-        let mut cmd = Command::new(rustlings_bin);
+    // Run the command and check its output.
+    // Returns the exit status and the command's debug representation for messages.
+    fn run(&self) -> (ExitStatus, String) {
+        let mut cmd = Command::new(rustlings_bin());
 
         if let Some(current_dir) = self.current_dir {
             cmd.current_dir(current_dir);
         }
 
-        cmd.args(self.args).stdin(Stdio::null());
+        cmd.args(self.args)
+            .envs(self.envs.iter().copied())
+            .stdin(if self.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            });
+
+        let (stdout, stderr) = match self.output {
+            None => (Stdio::null(), Stdio::null()),
+            Some(FullStdout(_) | PartialStdout(_)) => (Stdio::piped(), Stdio::null()),
+            Some(PartialStderr(_)) => (Stdio::null(), Stdio::piped()),
+        };
+        let mut child = cmd.stdout(stdout).stderr(stderr).spawn().unwrap();
+        if let Some(stdin) = self.stdin {
+            // Dropped after writing to close the stdin.
+            child
+                .stdin
+                .take()
+                .unwrap()
+                .write_all(stdin.as_bytes())
+                .unwrap();
+        }
+        let output = child.wait_with_output().unwrap();
 
-        let status = match self.output {
-            None => cmd
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .status()
-                .unwrap(),
+        match self.output {
+            None => (),
             Some(FullStdout(stdout)) => {
-                let output = cmd.stderr(Stdio::null()).output().unwrap();
                 assert_eq!(from_utf8(&output.stdout).unwrap(), stdout);
-                output.status
             }
             Some(PartialStdout(stdout)) => {
-                let output = cmd.stderr(Stdio::null()).output().unwrap();
                 assert!(from_utf8(&output.stdout).unwrap().contains(stdout));
-                output.status
             }
             Some(PartialStderr(stderr)) => {
-                let output = cmd.stdout(Stdio::null()).output().unwrap();
                 assert!(from_utf8(&output.stderr).unwrap().contains(stderr));
-                output.status
             }
-        };
+        }
+        let status = output.status;
+
+        (status, format!("{cmd:?}"))
+    }
+
+    fn assert(&self, success: bool) {
+        let (status, cmd) = self.run();
+        assert_eq!(status.success(), success, "{cmd}");
+    }
 
-        assert_eq!(status.success(), success, "{cmd:?}");
+    fn exit_code(&self, code: i32) {
+        let (status, cmd) = self.run();
+        assert_eq!(status.code(), Some(code), "{cmd}");
     }
 
     #[inline]
@@ -113,6 +162,35 @@ fn run_compilation_failure() {
         .fail();
 }
 
+#[test]
+fn run_json_errors() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "compilation_failure", "--json-errors"])
+        .output(PartialStdout(r#""level": "error""#))
+        .fail();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "compilation_success", "--json-errors"])
+        .output(FullStdout("[]\n"))
+        .success();
+
+    // The current exercise isn't changed.
+    let test_dir = temp_course();
+    let state_file = test_dir.path().join(".rustlings-state.txt");
+    fs::write(&state_file, "DON'T EDIT THIS FILE!\n\ntest_failure\n\n").unwrap();
+    Cmd::default()
+        .current_dir(test_dir.path().to_str().unwrap())
+        .args(&["run", "test_success", "--json-errors"])
+        .output(FullStdout("[]\n"))
+        .success();
+    assert_eq!(
+        fs::read_to_string(&state_file).unwrap(),
+        "DON'T EDIT THIS FILE!\n\ntest_failure\n\n",
+    );
+}
+
 #[test]
 fn run_test_success() {
     Cmd::default()
@@ -122,6 +200,37 @@ fn run_test_success() {
         .success();
 }
 
+#[test]
+fn run_cache() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+    let run = |stdout| {
+        Cmd::default()
+            .current_dir(test_dir_str)
+            .args(&["run", "test_success"])
+            .output(PartialStdout(stdout))
+            .success();
+    };
+
+    run("\nOutput from `main` function\n");
+    let entry_path = test_dir.join(".rustlings-cache/test_success");
+    let entry = fs::read_to_string(&entry_path).unwrap();
+    let (hash_line, output) = entry.split_once('\n').unwrap();
+    assert!(output.contains("Output from `main` function"));
+
+    // The cached output is shown without running the exercise again.
+    fs::write(&entry_path, format!("{hash_line}\nCached output\n")).unwrap();
+    run("Cached output\n");
+
+    // A change of the exercise invalidates the cache.
+    let exercise_path = test_dir.join("exercises/test_success.rs");
+    let mut exercise = fs::read_to_string(&exercise_path).unwrap();
+    exercise.push_str("// Changed\n");
+    fs::write(&exercise_path, exercise).unwrap();
+    run("\nOutput from `main` function\n");
+}
+
 #[test]
 fn run_test_failure() {
     Cmd::default()
@@ -138,11 +247,29 @@ fn run_exercise_not_in_info() {
         .fail();
 }
 
+#[test]
+fn verify() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["verify"])
+        .output(PartialStdout("\n2/4 exercises passed\n"))
+        .fail();
+}
+
 #[test]
 fn reset_without_exercise_name() {
     Cmd::default().args(&["reset"]).fail();
 }
 
+#[test]
+fn reset_without_confirmation() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["reset", "test_success"])
+        .output(PartialStdout("Reset canceled"))
+        .fail();
+}
+
 #[test]
 fn hint() {
     Cmd::default()
@@ -153,30 +280,2597 @@ fn hint() {
 }
 
 #[test]
-fn init() {
-    let test_dir = tempfile::TempDir::new().unwrap();
-    let test_dir = test_dir.path().to_str().unwrap();
+fn hint_levels() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["hint", "test_success"])
+        .output(FullStdout("Run the tests.\n"))
+        .success();
 
-    Cmd::default().current_dir(test_dir).fail();
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["hint", "test_success", "--level", "2"])
+        .output(FullStdout("The tests already pass.\n"))
+        .success();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["hint", "test_success", "--level", "3"])
+        .output(PartialStderr(
+            "The level must be between 1 and 2 for the exercise `test_success`",
+        ))
+        .fail();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["hint", "test_success", "--all"])
+        .output(FullStdout(
+            "Hint 1/2:\nRun the tests.\n\nHint 2/2:\nThe tests already pass.\n\n",
+        ))
+        .success();
+}
+
+#[test]
+fn list_sort_difficulty() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["list", "--sort", "difficulty"])
+        .output(PartialStdout(
+            "Path
+  4  1           test_failure         exercises/test_failure.rs
+  3  3           test_success         exercises/test_success.rs
+  1  -           compilation_success  exercises/compilation_success.rs
+",
+        ))
+        .success();
+}
+
+#[test]
+fn list_show_time() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["list", "--show-time"])
+        .output(FullStdout(
+            "Num  Difficulty  Name                 Path                              Time
+  1  -           compilation_success  exercises/compilation_success.rs  -
+  2  -           compilation_failure  exercises/compilation_failure.rs  -
+  3  3           test_success         exercises/test_success.rs         10 min
+  4  1           test_failure         exercises/test_failure.rs         5 min
+
+Total estimated time: 15 minutes
+",
+        ))
+        .success();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["list", "--show-time", "--tag", "answer"])
+        .output(PartialStdout("\nTotal estimated time: 5 minutes\n"))
+        .success();
+}
+
+#[test]
+fn list_output_json() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["list", "--tag", "answer", "--output", "json"])
+        .output(FullStdout(
+            r#"[
+  {
+    "name": "test_failure",
+    "dir": null,
+    "path": "exercises/test_failure.rs",
+    "hints": [
+      "The answer to everything: 42"
+    ],
+    "test": true,
+    "skip_check_unsolved": false,
+    "difficulty": 1,
+    "estimated_minutes": 5,
+    "tags": [
+      "tests",
+      "answer"
+    ],
+    "author": "Ferris",
+    "source_url": "https://github.com/rust-lang/rustlings"
+  }
+]
+"#,
+        ))
+        .success();
+}
+
+#[test]
+fn info() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["info", "test_failure"])
+        .output(FullStdout(
+            "Name:            test_failure
+Path:            exercises/test_failure.rs
+Checked by:      Tests
+Difficulty:      1
+Estimated time:  5 min
+Tags:            tests, answer
+Hints:           1 level (`rustlings hint test_failure`)
+Author:          Ferris
+Source:          https://github.com/rust-lang/rustlings
+",
+        ))
+        .success();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["info", "nope"])
+        .fail();
+}
+
+#[test]
+fn list_groups() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file_path = test_dir.join("info.toml");
+    let info_file = fs::read_to_string(&info_file_path)
+        .unwrap()
+        .replace(
+            "name = \"compilation_success\"\n",
+            "name = \"compilation_success\"\ngroup = \"compilation\"\n",
+        )
+        .replace(
+            "name = \"compilation_failure\"\n",
+            "name = \"compilation_failure\"\ngroup = \"compilation\"\n",
+        )
+        .replace(
+            "name = \"test_success\"\n",
+            "name = \"test_success\"\ngroup = \"tests\"\n",
+        )
+        + "
+[[groups]]
+name = \"tests\"
+description = \"Exercises with tests\"
+
+[[groups]]
+name = \"compilation\"
+description = \"Exercises that only need to compile\"
+";
+    fs::write(&info_file_path, info_file).unwrap();
+    fs::write(
+        test_dir.join(".rustlings-state.txt"),
+        "DON'T EDIT THIS FILE!\n\ntest_success\n\ncompilation_success\ncompilation_failure",
+    )
+    .unwrap();
+    let test_dir = test_dir.to_str().unwrap();
 
     Cmd::default()
         .current_dir(test_dir)
-        .args(&["init"])
+        .args(&["list"])
+        .output(FullStdout(
+            "Num  Difficulty  Name                 Path
+
+tests: Exercises with tests (0/1 done)
+  3  3           test_success         exercises/test_success.rs
+
+compilation: Exercises that only need to compile (2/2 done)
+
+Other exercises
+  4  1           test_failure         exercises/test_failure.rs
+",
+        ))
         .success();
 
-    // Running `init` after a successful initialization.
     Cmd::default()
         .current_dir(test_dir)
-        .args(&["init"])
-        .output(PartialStderr("`cd rustlings`"))
+        .args(&["list", "--group", "compilation"])
+        .output(FullStdout(
+            "Num  Difficulty  Name                 Path
+
+compilation: Exercises that only need to compile (2/2 done)
+  1  -           compilation_success  exercises/compilation_success.rs
+  2  -           compilation_failure  exercises/compilation_failure.rs
+",
+        ))
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["list", "--group", "missing"])
+        .output(PartialStderr(
+            "The group `missing` doesn't exist. Available groups: tests, compilation",
+        ))
         .fail();
+}
 
-    let initialized_dir = format!("{test_dir}/rustlings");
+#[test]
+fn list_tag() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["list", "--tag", "answer"])
+        .output(PartialStdout(
+            "Path\n  4  1           test_failure         exercises/test_failure.rs\n",
+        ))
+        .success();
 
-    // Running `init` in the initialized directory.
     Cmd::default()
-        .current_dir(&initialized_dir)
-        .args(&["init"])
-        .output(PartialStderr("already initialized"))
+        .current_dir("tests/test_exercises")
+        .args(&["list", "--tag", "lifetimes"])
+        .output(PartialStderr("Available tags: answer, tests"))
+        .fail();
+}
+
+#[test]
+fn dev_check_json() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["dev", "check", "--json"])
+        .output(PartialStdout(
+            r#"  "unsolved": {
+    "status": "failed",
+    "errors": [],
+    "exercises": [
+      {
+        "name": "compilation_success",
+        "path": "exercises/compilation_success.rs",
+        "status": "failed","#,
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_skip_check_unsolved() {
+    // An intentionally solved exercise.
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["dev", "check", "--json", "--only", "test_success"])
+        .output(PartialStdout(
+            r#"  "unsolved": {
+    "status": "passed",
+    "errors": [],
+    "exercises": [
+      {
+        "name": "test_success",
+        "path": "exercises/test_success.rs",
+        "status": "passed"
+      }
+    ]
+  },"#,
+        ))
+        .fail();
+
+    // A broken exercise.
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    fs::copy(
+        "tests/test_exercises/exercises/compilation_failure.rs",
+        test_dir.join("exercises/compilation_failure.rs"),
+    )
+    .unwrap();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"compilation_failure\"\n",
+            "name = \"compilation_failure\"\nskip_check_unsolved = true\n",
+        ),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "check", "--json", "--only", "compilation_failure"])
+        .output(PartialStdout(
+            r#"        "name": "compilation_failure",
+        "path": "exercises/compilation_failure.rs",
+        "status": "failed",
+        "output": "Running the exercise compilation_failure failed.\nThe exercise has `skip_check_unsolved = true`"#,
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_phases() {
+    for (arg, skipped) in [
+        ("--no-run", [r#""unsolved""#, r#""solutions""#]),
+        ("--solutions-only", [r#""info_file""#, r#""unsolved""#]),
+        ("--exercises-only", [r#""cargo_toml""#, r#""solutions""#]),
+    ] {
+        for category in skipped {
+            Cmd::default()
+                .current_dir("tests/test_exercises")
+                .args(&["dev", "check", "--json", arg])
+                .output(PartialStdout(&format!(
+                    "  {category}: {{\n    \"status\": \"skipped\","
+                )))
+                .fail();
+        }
+    }
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["dev", "check", "--no-run", "--exercises-only"])
+        .output(PartialStderr(
+            "the argument '--no-run' cannot be used with '--exercises-only'",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_only() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["dev", "check", "--json", "--only", "test_*"])
+        .output(PartialStdout(
+            r#"{
+  "partial": true,
+  "cargo_toml": {
+    "status": "skipped","#,
+        ))
+        .fail();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["dev", "check", "--only", "missing*"])
+        .output(PartialStderr("No exercise matches `missing*` of `--only`. The exercise names are: compilation_success, compilation_failure, test_success, test_failure"))
+        .fail();
+}
+
+// Copy the test exercises without their files to a temporary directory that can be modified.
+fn temp_course() -> TempDir {
+    let course_dir = TempDir::new().unwrap();
+    let path = course_dir.path();
+    for dir in ["dev", "exercises", "solutions"] {
+        fs::create_dir(path.join(dir)).unwrap();
+    }
+    for file in ["info.toml", "dev/Cargo.toml", "exercises/test_success.rs"] {
+        fs::copy(format!("tests/test_exercises/{file}"), path.join(file)).unwrap();
+    }
+    fs::write(
+        path.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"dev\"]\nresolver = \"2\"\n",
+    )
+    .unwrap();
+
+    course_dir
+}
+
+#[test]
+fn dev_check_orphaned_solution() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    fs::copy(
+        "tests/test_exercises/exercises/test_failure.rs",
+        test_dir.join("solutions/test_failure.rs"),
+    )
+    .unwrap();
+
+    // Delete the exercise `test_failure` but forget about its solution.
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    let (info_file, _) = info_file
+        .split_once("\n[[exercises]]\nname = \"test_failure\"")
+        .unwrap();
+    fs::write(test_dir.join("info.toml"), info_file).unwrap();
+    // A leftover of an exercise in a directory.
+    fs::create_dir(test_dir.join("solutions/removed")).unwrap();
+    fs::write(
+        test_dir.join("solutions/removed/removed1.rs"),
+        "fn main() {}\n",
+    )
+    .unwrap();
+
+    for orphan in ["solutions/test_failure.rs", "solutions/removed/removed1.rs"] {
+        Cmd::default()
+            .current_dir(test_dir.to_str().unwrap())
+            .args(&["dev", "check", "--json"])
+            .output(PartialStdout(&format!(
+                "Found the orphaned solution file `{orphan}`"
+            )))
+            .fail();
+    }
+}
+
+#[test]
+fn dev_check_exercise_dirs() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    let info_file = info_file.replace(
+        "name = \"test_success\"\n",
+        "name = \"test_success\"\ndir = \"tset\"\n",
+    );
+    fs::write(test_dir.join("info.toml"), info_file).unwrap();
+    fs::create_dir(test_dir.join("exercises/test")).unwrap();
+
+    for problem in [
+        "The directory `exercises/tset` doesn't exist but is referenced by the exercises: test_success",
+        "The directory `exercises/test` isn't referenced by any exercise in `info.toml`",
+    ] {
+        Cmd::default()
+            .current_dir(test_dir.to_str().unwrap())
+            .args(&["dev", "check", "--json"])
+            .output(PartialStdout(problem))
+            .fail();
+    }
+}
+
+#[test]
+fn dev_check_fix() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let cargo_toml_path = test_dir.join("dev/Cargo.toml");
+    let cargo_toml = fs::read_to_string(&cargo_toml_path).unwrap();
+    let outdated_cargo_toml = cargo_toml
+        .lines()
+        .filter(|line| !line.contains("test_failure"))
+        .map(|line| format!("{line}\n"))
+        .collect::<String>();
+    fs::write(&cargo_toml_path, outdated_cargo_toml).unwrap();
+    let sol_path = test_dir.join("solutions/test_success.rs");
+    let solution = fs::read_to_string(test_dir.join("exercises/test_success.rs")).unwrap();
+    fs::write(&sol_path, solution.replace("    ", "  ")).unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "check", "--json", "--fix"])
+        .output(PartialStdout(
+            r#"  "fixed_files": [
+    "dev/Cargo.toml",
+    "solutions/test_success.rs"
+  ]"#,
+        ))
+        .fail();
+    // With the binary of the new solution.
+    let updated_cargo_toml = cargo_toml.replace(
+        "\n  { name = \"test_failure\"",
+        "\n  { name = \"test_success_sol\", path = \"../solutions/test_success.rs\" },\n  { name = \"test_failure\"",
+    );
+    assert_eq!(
+        fs::read_to_string(&cargo_toml_path).unwrap(),
+        updated_cargo_toml,
+    );
+    assert_eq!(fs::read_to_string(&sol_path).unwrap(), solution);
+}
+
+#[test]
+fn dev_update_validate() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    for name in ["compilation_success", "compilation_failure", "test_failure"] {
+        fs::copy(
+            format!("tests/test_exercises/exercises/{name}.rs"),
+            test_dir.join(format!("exercises/{name}.rs")),
+        )
+        .unwrap();
+    }
+    let cargo_toml_path = test_dir.join("dev/Cargo.toml");
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    // Without solutions, only the manifest is validated and the unsolved exercises aren't built.
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update", "--validate"])
+        .success();
+    assert_eq!(fs::read_dir(test_dir.join("dev")).unwrap().count(), 1);
+
+    let cargo_toml = fs::read_to_string(&cargo_toml_path).unwrap();
+    let sol_path = test_dir.join("solutions/test_success.rs");
+    fs::write(&sol_path, "fn main() {\n    let x: u8 = \"\";\n}\n").unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update", "--validate"])
+        .output(PartialStderr("`cargo build --workspace` failed"))
+        .fail();
+    // Restored without leaving the backup behind.
+    assert_eq!(fs::read_to_string(&cargo_toml_path).unwrap(), cargo_toml);
+    assert_eq!(fs::read_dir(test_dir.join("dev")).unwrap().count(), 1);
+
+    fs::copy(test_dir.join("exercises/test_success.rs"), &sol_path).unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update", "--validate"])
+        .success();
+    assert!(fs::read_to_string(&cargo_toml_path)
+        .unwrap()
+        .contains("{ name = \"test_success_sol\", path = \"../solutions/test_success.rs\" },"));
+    assert_eq!(fs::read_dir(test_dir.join("dev")).unwrap().count(), 1);
+}
+
+#[test]
+fn dev_check_unformatted() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let solution = fs::read_to_string(test_dir.join("exercises/test_success.rs")).unwrap();
+    fs::write(
+        test_dir.join("solutions/test_success.rs"),
+        solution.replace("    ", "  "),
+    )
+    .unwrap();
+    let test_dir = test_dir.to_str().unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "update"])
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check", "--json"])
+        .output(PartialStdout(
+            "The solutions solutions/test_success.rs aren't formatted. Run `rustfmt` on them",
+        ))
+        .fail();
+
+    // Solutions with `skip_fmt_check` aren't passed to `rustfmt`.
+    let info_file = fs::read_to_string(format!("{test_dir}/info.toml")).unwrap();
+    fs::write(
+        format!("{test_dir}/info.toml"),
+        info_file.replace(
+            "name = \"test_success\"\n",
+            "name = \"test_success\"\nskip_fmt_check = true\n",
+        ),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check", "--json"])
+        .output(PartialStdout(
+            "  \"solutions\": {\n    \"status\": \"passed\",\n    \"errors\": [],\n",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_solution_identical_to_exercise() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"test_success\"\nskip_check_unsolved = true\n",
+            "name = \"test_success\"\n",
+        ),
+    )
+    .unwrap();
+    fs::copy(
+        test_dir.join("exercises/test_success.rs"),
+        test_dir.join("solutions/test_success.rs"),
+    )
+    .unwrap();
+    let test_dir = test_dir.to_str().unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "update"])
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check", "--json", "--solutions-only"])
+        .output(PartialStdout(
+            "The solution of the exercise `test_success` is identical to the exercise file exercises/test_success.rs. The exercise appears already solved",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_test_functions() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let exercise_path = test_dir.join("exercises/test_success.rs");
+    let exercise = fs::read_to_string(&exercise_path).unwrap();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    fs::write(
+        &exercise_path,
+        exercise.replace("#[cfg(test)]\nmod tests {", "mod tests {"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "The tests in the file `exercises/test_success.rs` aren't inside a module annotated with `#[cfg(test)]`",
+        ))
+        .fail();
+
+    fs::write(&exercise_path, exercise.replace("#[test]\n", "")).unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "The file `exercises/test_success.rs` doesn't contain any tests annotated with `#[test]`",
+        ))
+        .fail();
+}
+
+#[test]
+fn exercise_features() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"test_success\"\n",
+            "name = \"test_success\"\nfeatures = [\"fancy\"]\n",
+        ),
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("exercises/test_success.rs"),
+        "fn main() {\n    #[cfg(feature = \"fancy\")]\n    println!(\"Fancy output\");\n}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn passes() {}\n}\n",
+    )
+    .unwrap();
+
+    // The feature isn't declared yet.
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["run", "test_success"])
+        .fail();
+
+    let cargo_toml = fs::read_to_string(test_dir.join("dev/Cargo.toml")).unwrap();
+    fs::write(
+        test_dir.join("dev/Cargo.toml"),
+        format!("{cargo_toml}\n[features]\nfancy = []\n"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["run", "test_success"])
+        .output(PartialStdout("Fancy output"))
+        .success();
+}
+
+#[test]
+fn custom_test_command() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    let test_dir_str = test_dir.to_str().unwrap();
+    let with_test_command = |test_command: &str| {
+        info_file.replace(
+            "name = \"test_success\"\n",
+            &format!(
+                "name = \"test_success\"\ntest = \"custom\"\ntest_command = \"{test_command}\"\n"
+            ),
+        )
+    };
+
+    fs::write(
+        test_dir.join("info.toml"),
+        with_test_command("cargo test -- --test-threads 1"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["run", "test_success"])
+        .output(PartialStdout("Successfully ran"))
+        .success();
+
+    // The arguments are passed to the test binary.
+    fs::write(
+        test_dir.join("info.toml"),
+        with_test_command("cargo test -- --unknown-flag"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["run", "test_success"])
+        .output(PartialStdout("Unrecognized option: 'unknown-flag'"))
+        .fail();
+
+    fs::write(
+        test_dir.join("info.toml"),
+        with_test_command("cargo test | tee log"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "The `test_command` of the exercise `test_success` contains the shell metacharacter `|`",
+        ))
+        .fail();
+
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"test_success\"\n",
+            "name = \"test_success\"\ntest_command = \"cargo test\"\n",
+        ),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "The exercise `test_success` has a `test_command` but it is only run with `test = \\\"custom\\\"`",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_readmes() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    // The unexpected files are only checked if the exercises in `info.toml` are fine.
+    fs::write(
+        test_dir.join("info.toml"),
+        "format_version = 1\nrequire_readmes = true\n\n[[exercises]]\nname = \"test_success\"\ndir = \"topic\"\nskip_check_unsolved = true\nhint = \"Run the tests.\"\n",
+    )
+    .unwrap();
+    fs::create_dir(test_dir.join("exercises/topic")).unwrap();
+    let exercise = fs::read_to_string(test_dir.join("exercises/test_success.rs")).unwrap();
+    fs::write(
+        test_dir.join("exercises/topic/test_success.rs"),
+        format!("// TODO: Nothing to do.\n{exercise}"),
+    )
+    .unwrap();
+    fs::remove_file(test_dir.join("exercises/test_success.rs")).unwrap();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout("- `exercises/topic` has no `README.md` file"))
+        .fail();
+
+    fs::write(test_dir.join("exercises/topic/README.md"), "# Topic\n").unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "- `exercises/topic/README.md` doesn't contain any link",
+        ))
+        .fail();
+
+    fs::write(
+        test_dir.join("exercises/topic/README.md"),
+        "# Topic\n\n- [Book](https://doc.rust-lang.org/book/)\n",
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "\"unexpected_files\": {\n    \"status\": \"passed\"",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_exit_codes() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["dev", "check", "--json"])
+        .exit_code(2);
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&[
+            "dev",
+            "check",
+            "--exercises-only",
+            "--only",
+            "compilation_success",
+        ])
+        .exit_code(3);
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["dev", "check", "--exercises-only", "--only", "test_success"])
+        .output(PartialStdout(
+            "Summary:\n  Exercises checked  1 (0 cached)\n  Warnings           0\n",
+        ))
+        .success();
+
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let sol_path = test_dir.join("solutions/test_success.rs");
+    let solution = fs::read_to_string(test_dir.join("exercises/test_success.rs")).unwrap();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    fs::write(
+        &sol_path,
+        solution.replace("fn passes() {}", "fn passes() { panic!() }"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update"])
+        .success();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--solutions-only"])
+        .exit_code(4);
+
+    fs::write(&sol_path, solution.replace("    ", "  ")).unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--solutions-only"])
+        .exit_code(5);
+}
+
+#[test]
+fn dev_check_cache() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    fs::write(
+        test_dir.join("info.toml"),
+        "format_version = 1\n\n[[exercises]]\nname = \"test_success\"\nskip_check_unsolved = true\nhint = \"The tests already pass.\"\n",
+    )
+    .unwrap();
+    let exercise_path = test_dir.join("exercises/test_success.rs");
+    let solution = fs::read_to_string(&exercise_path).unwrap();
+    fs::write(
+        &exercise_path,
+        format!("// TODO: Nothing to do.\n{solution}"),
+    )
+    .unwrap();
+    fs::write(test_dir.join("solutions/test_success.rs"), &solution).unwrap();
+    let test_dir_str = test_dir.to_str().unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update"])
+        .success();
+
+    let check = |n_cached| {
+        Cmd::default()
+            .current_dir(test_dir_str)
+            .args(&["dev", "check"])
+            .output(PartialStdout(&format!(
+                "Exercises checked             1 ({n_cached} cached)"
+            )))
+            .success();
+    };
+    check(0);
+    check(1);
+
+    // A change of the exercise invalidates its entry.
+    fs::write(
+        &exercise_path,
+        format!("// TODO: Still nothing.\n{solution}"),
+    )
+    .unwrap();
+    check(0);
+    check(1);
+}
+
+#[test]
+fn dev_check_timings() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "scaffold", "answer1", "--hint", "42"])
+        .success();
+
+    // The durations vary between runs.
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--only", "answer1", "--timings"])
+        .output(PartialStdout("s  answer1\nSum of 1 exercises: "))
+        .success();
+
+    // A change of the exercise invalidates its cache entry which has no duration.
+    let exercise_path = test_dir.join("exercises/answer1.rs");
+    let exercise = fs::read_to_string(&exercise_path).unwrap();
+    fs::write(&exercise_path, format!("{exercise}// Changed.\n")).unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--only", "answer1", "--timings", "--json"])
+        .output(PartialStdout(
+            "\"name\": \"answer1\",\n        \"path\": \"exercises/answer1.rs\",\n        \"status\": \"passed\",\n        \"duration_secs\": ",
+        ))
+        .success();
+}
+
+#[test]
+fn dev_lint() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file =
+        "format_version = 1\n\n[[exercises]]\nname = \"test_success\"\nhint = \"No hint\"\n";
+    fs::write(test_dir.join("info.toml"), info_file).unwrap();
+    fs::write(
+        test_dir.join("exercises/test_success.rs"),
+        "fn answer() -> u32 {\n    return 42;\n}\n\nfn main() {\n    println!(\"{}\", answer());\n}\n",
+    )
+    .unwrap();
+    let test_dir_str = test_dir.to_str().unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update"])
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "lint"])
+        .output(PartialStderr("unneeded `return` statement"))
+        .fail();
+
+    fs::write(
+        test_dir.join("info.toml"),
+        format!("{info_file}skip_check_lint = true\n"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "lint"])
+        .output(PartialStdout("No Clippy warnings found!\n"))
+        .success();
+}
+
+#[test]
+fn export_import() {
+    let course_dir = temp_course();
+    let course_dir = course_dir.path();
+    fs::copy(
+        course_dir.join("exercises/test_success.rs"),
+        course_dir.join("solutions/test_success.rs"),
+    )
+    .unwrap();
+    let course_dir_str = course_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(course_dir_str)
+        .args(&["export", "--output", "course.zip"])
+        .output(PartialStdout("Exported the exercises to course.zip"))
+        .success();
+    // The archive already exists.
+    Cmd::default()
+        .current_dir(course_dir_str)
+        .args(&["export", "--output", "course.zip"])
+        .fail();
+
+    let import_dir = TempDir::new().unwrap();
+    let import_dir = import_dir.path();
+    let archive_path = course_dir.join("course.zip");
+    Cmd::default()
+        .current_dir(import_dir.to_str().unwrap())
+        .args(&["import", archive_path.to_str().unwrap()])
+        .output(PartialStdout("Imported the exercises into course"))
+        .success();
+
+    let imported_dir = import_dir.join("course");
+    for file in ["info.toml", "dev/Cargo.toml", "exercises/test_success.rs"] {
+        assert_eq!(
+            fs::read(imported_dir.join(file)).unwrap(),
+            fs::read(course_dir.join(file)).unwrap(),
+            "{file}",
+        );
+    }
+    // Without `--include-solutions`.
+    assert!(!imported_dir.join("solutions/test_success.rs").exists());
+    assert!(!imported_dir.join("rustlings-export.json").exists());
+
+    // Not an archive.
+    Cmd::default()
+        .current_dir(import_dir.to_str().unwrap())
+        .args(&["import", "course/info.toml", "--dir", "other"])
+        .output(PartialStderr("Failed to read the archive course/info.toml"))
+        .fail();
+
+    // The entries are relative to the course even with an absolute exercises directory.
+    let exercises_dir = course_dir.join("exercises");
+    Cmd::default()
+        .current_dir(course_dir_str)
+        .args(&["export", "--include-solutions", "--output", "absolute.zip"])
+        .envs(&[("RUSTLINGS_EXERCISES_DIR", exercises_dir.to_str().unwrap())])
+        .success();
+    Cmd::default()
+        .current_dir(import_dir.to_str().unwrap())
+        .args(&["import", course_dir.join("absolute.zip").to_str().unwrap()])
+        .success();
+    for file in ["exercises/test_success.rs", "solutions/test_success.rs"] {
+        assert!(import_dir.join("absolute").join(file).exists(), "{file}");
+    }
+}
+
+#[test]
+fn dev_check_msrv() {
+    // `--msrv` needs rustup. Use the toolchain that runs the tests instead of assuming `stable`.
+    let Some(toolchain) = Command::new("rustup")
+        .args(["show", "active-toolchain"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| {
+            let stdout = String::from_utf8(output.stdout).ok()?;
+            stdout.split_whitespace().next().map(String::from)
+        })
+    else {
+        eprintln!("Skipping the test because rustup isn't available");
+        return;
+    };
+
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    fs::copy(
+        test_dir.join("exercises/test_success.rs"),
+        test_dir.join("solutions/test_success.rs"),
+    )
+    .unwrap();
+    let test_dir = test_dir.to_str().unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "update"])
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check", "--solutions-only", "--msrv", &toolchain])
+        .output(PartialStdout("Everything looks fine!"))
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check", "--solutions-only", "--msrv", "0.1"])
+        .output(PartialStderr(
+            "The toolchain `0.1` of the minimum supported Rust version isn't installed",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_migrate() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "migrate"])
+        .output(FullStdout(
+            "The file `info.toml` already has the current format version 1\n",
+        ))
+        .success();
+    assert_eq!(
+        fs::read_to_string(test_dir.join("info.toml")).unwrap(),
+        info_file,
+    );
+
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace("format_version = 1", "format_version = 2"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "migrate"])
+        .output(PartialStderr(
+            "The format version 2 of `info.toml` is higher than the last one supported (1)",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_update_check() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let cargo_toml_path = test_dir.join("dev/Cargo.toml");
+    let cargo_toml = fs::read_to_string(&cargo_toml_path).unwrap();
+    fs::copy(
+        test_dir.join("exercises/test_success.rs"),
+        test_dir.join("solutions/test_success.rs"),
+    )
+    .unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "update", "--check"])
+        .output(PartialStdout(
+            "\n+  { name = \"test_success_sol\", path = \"../solutions/test_success.rs\" },\n",
+        ))
+        .exit_code(1);
+    assert_eq!(fs::read_to_string(&cargo_toml_path).unwrap(), cargo_toml);
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "update"])
+        .success();
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "update", "--dry-run"])
+        .output(FullStdout("`dev/Cargo.toml` is up to date\n"))
+        .exit_code(0);
+}
+
+#[test]
+fn dev_update_editor() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update", "--editor", "vscode"])
+        .output(FullStdout(
+            "Updated `dev/Cargo.toml`\nUpdated `.vscode/settings.json`\n",
+        ))
+        .success();
+    assert_eq!(
+        fs::read_to_string(test_dir.join(".vscode/settings.json")).unwrap(),
+        "{\n  \"rust-analyzer.linkedProjects\": [\n    \"dev/Cargo.toml\"\n  ]\n}\n",
+    );
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update", "--editor", "ra-project"])
+        .success();
+    let rust_project_json_path = test_dir.join("rust-project.json");
+    let rust_project_json = fs::read_to_string(&rust_project_json_path).unwrap();
+    assert!(rust_project_json.contains(
+        r#"      "display_name": "test_success",
+      "root_module": "exercises/test_success.rs",
+      "edition": "2021",
+      "deps": [],
+      "cfg": [
+        "test"
+      ],"#
+    ));
+
+    // Kept up to date without `--editor`.
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update", "--dry-run"])
+        .output(FullStdout(
+            "`dev/Cargo.toml` and `rust-project.json` are up to date\n",
+        ))
+        .success();
+
+    fs::write(&rust_project_json_path, "{}\n").unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json"])
+        .output(PartialStdout(
+            r#"  "editor_files": {
+    "status": "failed",
+    "errors": [
+      "The file `rust-project.json` is outdated."#,
+        ))
+        .fail();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update"])
+        .success();
+    assert_eq!(
+        fs::read_to_string(&rust_project_json_path).unwrap(),
+        rust_project_json,
+    );
+}
+
+#[test]
+fn dev_check_silent_failure() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    fs::write(
+        test_dir.join("exercises/compilation_success.rs"),
+        "fn main() {\n    std::process::exit(1);\n}\n",
+    )
+    .unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "check", "--json"])
+        .output(PartialStdout(
+            "The exercise `compilation_success` fails without any output when it's unsolved",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_stdin_fixture() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"compilation_success\"\n",
+            "name = \"compilation_success\"\nstdin_fixture = \"guess.txt\"\n",
+        ),
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("exercises/compilation_success.rs"),
+        "fn main() {\n    let mut guess = String::new();\n    std::io::stdin().read_line(&mut guess).unwrap();\n    if guess.trim() != \"42\" {\n        println!(\"Wrong guess\");\n        std::process::exit(1);\n    }\n}\n",
+    )
+    .unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "The stdin fixture `exercises/guess.txt` doesn't exist",
+        ))
+        .fail();
+
+    // The fixture solves the exercise.
+    fs::write(test_dir.join("exercises/guess.txt"), "42\n").unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--only", "compilation_success"])
+        .output(PartialStdout(
+            "The exercise compilation_success is already solved.",
+        ))
+        .fail();
+
+    // The solution gets the fixture as stdin too.
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replacen("hint = \"\"", "hint = \"Read the guess.\"", 1),
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("exercises/compilation_success.rs"),
+        "fn main() {\n    let mut guess = String::new();\n    std::io::stdin().read_line(&mut guess).unwrap();\n    // TODO: Check the guess.\n    println!(\"Not implemented yet\");\n    std::process::exit(1);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("solutions/compilation_success.rs"),
+        "fn main() {\n    let mut guess = String::new();\n    std::io::stdin().read_line(&mut guess).unwrap();\n    assert_eq!(guess.trim(), \"42\");\n}\n",
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update"])
+        .success();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--only", "compilation_success"])
+        .success();
+
+    // The fixture must not escape the exercise's directory.
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    for stdin_fixture in ["../guess.txt", "..\\\\guess.txt"] {
+        fs::write(
+            test_dir.join("info.toml"),
+            info_file.replace("\"guess.txt\"", &format!("\"{stdin_fixture}\"")),
+        )
+        .unwrap();
+        Cmd::default()
+            .current_dir(test_dir_str)
+            .args(&["dev", "check", "--json", "--no-run"])
+            .output(PartialStdout(
+                "of the exercise `compilation_success` must be in the same directory as the exercise file",
+            ))
+            .fail();
+    }
+}
+
+#[test]
+fn dev_check_unfinished_solution() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+    let sol_path = test_dir.join("solutions/test_success.rs");
+    let solution = fs::read_to_string(test_dir.join("exercises/test_success.rs")).unwrap();
+
+    fs::write(
+        &sol_path,
+        solution.replace("fn passes() {}", "fn passes() {\n        todo!();\n    }"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "update"])
+        .success();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--solutions-only", "--only", "test_success"])
+        .output(PartialStderr(
+            "The solution `solutions/test_success.rs` of the exercise test_success contains `todo!()` in line 9. Solutions must be finished",
+        ))
+        .exit_code(4);
+
+    // Only whole lines are markers.
+    fs::write(
+        &sol_path,
+        solution.replace(
+            "fn passes() {}",
+            "// TODO: Explain the task.\n    fn passes() {\n        let _ = \"todo!()\";\n    }",
+        ),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--solutions-only", "--only", "test_success"])
+        .success();
+}
+
+#[test]
+fn dev_check_timeout() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"compilation_success\"\n",
+            "name = \"compilation_success\"\ntimeout_secs = 1\n",
+        ),
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("exercises/compilation_success.rs"),
+        "fn main() {\n    // TODO: Stop looping.\n    loop {\n        std::thread::park();\n    }\n}\n",
+    )
+    .unwrap();
+
+    // Only the binary is killed, not `cargo build`.
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "check", "--json", "--only", "compilation_success"])
+        .output(PartialStdout(
+            "compilation_success` didn't exit within the timeout of 1 seconds and was killed",
+        ))
+        .fail();
+}
+
+// `cargo test` is killed together with the test binary that it spawned.
+#[cfg(unix)]
+#[test]
+fn dev_check_test_timeout() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"test_success\"\n",
+            "name = \"test_success\"\ntimeout_secs = 1\n",
+        ),
+    )
+    .unwrap();
+    let pid_path = test_dir.join("test.pid");
+    fs::write(
+        test_dir.join("exercises/test_success.rs"),
+        format!(
+            "fn main() {{}}\n\n#[cfg(test)]\nmod tests {{\n    #[test]\n    fn loops() {{\n        // TODO: Stop looping.\n        std::fs::write({:?}, std::process::id().to_string()).unwrap();\n        loop {{\n            std::thread::park();\n        }}\n    }}\n}}\n",
+            pid_path.to_str().unwrap(),
+        ),
+    )
+    .unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "check", "--json", "--only", "test_success"])
+        .output(PartialStdout(
+            "The command `cargo test …` didn't exit within the timeout of 1 seconds and was killed",
+        ))
+        .fail();
+
+    // The killed test binary is reaped by the init process which can take a moment.
+    let pid = fs::read_to_string(&pid_path).unwrap();
+    let is_running = || {
+        Command::new("kill")
+            .args(["-0", &pid])
+            .stderr(Stdio::null())
+            .status()
+            .unwrap()
+            .success()
+    };
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while is_running() {
+        assert!(
+            Instant::now() < deadline,
+            "The test binary {pid} is still running"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn dev_check_emit_report() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+
+    // The test course has problems like empty hints and a missing exercise file.
+    // The report path is absolute, but its prefix depends on the platform.
+    let output = Command::new(rustlings_bin())
+        .current_dir(test_dir)
+        .args(["dev", "check", "--emit-report", "report.json"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = from_utf8(&output.stderr).unwrap();
+    assert!(
+        stderr.lines().any(|line| {
+            line.contains("Some checks failed. See the report ") && line.ends_with("report.json")
+        }),
+        "{stderr}",
+    );
+
+    let report = fs::read_to_string(test_dir.join("report.json")).unwrap();
+    for expected in [
+        r#""rustlings_version": ""#,
+        r#""format_version": 1,"#,
+        r#""name": "compilation_success","#,
+        r#""solutions": ["#,
+        r#""errors": ["#,
+    ] {
+        assert!(report.contains(expected), "{expected}\n{report}");
+    }
+
+    // Written for an error that stops checking before any exercise runs.
+    fs::write(test_dir.join("info.toml"), "format_version = 1\n").unwrap();
+    fs::remove_file(test_dir.join("report.json")).unwrap();
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "check", "--emit-report", "report.json"])
+        .fail();
+    let report = fs::read_to_string(test_dir.join("report.json")).unwrap();
+    assert!(report.contains(r#""exercises": [],"#), "{report}");
+    assert!(report.contains("info.toml"), "{report}");
+}
+
+#[test]
+fn should_fail() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    fs::copy(
+        "tests/test_exercises/exercises/compilation_failure.rs",
+        test_dir.join("exercises/compilation_failure.rs"),
+    )
+    .unwrap();
+    fs::write(
+        test_dir.join("exercises/compilation_success.rs"),
+        "// #[should_fail]\nfn main() {}\n",
+    )
+    .unwrap();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--json", "--no-run"])
+        .output(PartialStdout(
+            "The file `exercises/compilation_success.rs` contains the comment `// #[should_fail]` but the exercise `compilation_success` doesn't have `should_fail = true`",
+        ))
+        .fail();
+
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file
+            .replace(
+                "name = \"compilation_success\"\n",
+                "name = \"compilation_success\"\nshould_fail = true\n",
+            )
+            .replace(
+                "name = \"compilation_failure\"\n",
+                "name = \"compilation_failure\"\nshould_fail = true\n",
+            ),
+    )
+    .unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["run", "compilation_success"])
+        .output(PartialStdout(
+            "The exercise compiles, but it should fail to compile",
+        ))
+        .fail();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["run", "compilation_failure"])
+        .success();
+}
+
+#[test]
+fn dev_check_collect_problems() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    // The test course has empty hints, missing exercise files and a missing `// TODO` comment.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check"])
+        .output(PartialStderr(
+            "Found problems in the exercises of the `info.toml` file (6):",
+        ))
+        .fail();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check", "--fail-fast"])
+        .output(PartialStderr(
+            "Error: `compilation_success` (exercises/compilation_success.rs): The hint of level 1",
+        ))
+        .fail();
+}
+
+#[test]
+fn dev_check_name_collisions() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let mut info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    info_file.push_str("\n[[exercises]]\nname = \"test_success_sol\"\nhint = \"No hint\"\n");
+    info_file.push_str("\n[[exercises]]\nname = \"test_failure\"\nhint = \"No hint\"\n");
+    fs::write(test_dir.join("info.toml"), info_file).unwrap();
+
+    // `--only` skips the check of `Cargo.toml` which fails at the first collision.
+    let output = Command::new(rustlings_bin())
+        .current_dir(test_dir)
+        .args(["dev", "check", "--only", "test_*"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = from_utf8(&output.stderr).unwrap();
+    for expected in [
+        "The name `test_success_sol` of the exercise #5 in `info.toml` collides with the name of the solution binary of the exercise `test_success` (#3) in `Cargo.toml`",
+        "The exercise name `test_failure` of the exercise #6 in `info.toml` is already used by the exercise #4",
+    ] {
+        assert!(stderr.contains(expected), "{expected}\n{stderr}");
+    }
+}
+
+#[test]
+fn dev_check_hints() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+
+    for (hint, strict, problem) in [
+        (
+            "TODO: write a hint",
+            false,
+            "The hint of level 2 of the exercise `test_success` is the placeholder `TODO: write a hint`",
+        ),
+        (
+            "Call `renamed_fn`",
+            true,
+            "The hint of level 2 mentions `renamed_fn` which doesn't appear in the exercise",
+        ),
+    ] {
+        fs::write(
+            test_dir.join("info.toml"),
+            info_file.replace("The tests already pass.", hint),
+        )
+        .unwrap();
+
+        let args: &[&str] = if strict {
+            &["dev", "check", "--json", "--strict"]
+        } else {
+            &["dev", "check", "--json"]
+        };
+        Cmd::default()
+            .current_dir(test_dir.to_str().unwrap())
+            .args(args)
+            .output(PartialStdout(problem))
+            .fail();
+    }
+}
+
+#[test]
+fn dev_check_dirs_differing_in_case() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file
+            .replace(
+                "name = \"test_success\"\n",
+                "name = \"test_success\"\ndir = \"tests\"\n",
+            )
+            .replace(
+                "name = \"test_failure\"\n",
+                "name = \"test_failure\"\ndir = \"Tests\"\n",
+            ),
+    )
+    .unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["dev", "check", "--json", "--strict"])
+        .output(PartialStdout(
+            "The dir `Tests` of the exercise `test_failure` only differs in case from the dir `tests` of the exercise `test_success`",
+        ))
+        .fail();
+}
+
+#[test]
+fn prerequisites() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file.replace(
+            "name = \"test_failure\"\n",
+            "name = \"test_failure\"\nrequires = [\"test_success\"]\n",
+        ),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["hint", "test_failure"])
+        .output(PartialStderr(
+            "The exercise `test_failure` builds on the following exercises which aren't done yet: test_success",
+        ))
+        .success();
+
+    fs::write(
+        test_dir.join("info.toml"),
+        info_file
+            .replace(
+                "name = \"compilation_success\"\n",
+                "name = \"compilation_success\"\nrequires = [\"compilation_failure\"]\n",
+            )
+            .replace(
+                "name = \"compilation_failure\"\n",
+                "name = \"compilation_failure\"\nrequires = [\"compilation_success\", \"missing\"]\n",
+            ),
+    )
+    .unwrap();
+    for problem in [
+        "The prerequisite `compilation_failure` (#2) of the exercise `compilation_success` (#1) must come before it in `info.toml`",
+        "The prerequisites of the exercises `compilation_success` (#1) → `compilation_failure` (#2) → `compilation_success` (#1) form a cycle",
+        "The prerequisite `missing` of the exercise `compilation_failure` (#2) doesn't exist",
+    ] {
+        Cmd::default()
+            .current_dir(test_dir.to_str().unwrap())
+            .args(&["dev", "check", "--json"])
+            .output(PartialStdout(problem))
+            .fail();
+    }
+}
+
+// `curl` is replaced by a script that prints the fragment instead of downloading it.
+#[cfg(unix)]
+#[test]
+fn pack_fetch() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin_dir = TempDir::new().unwrap();
+    let fragment_path = bin_dir.path().join("fragment.toml");
+    fs::write(
+        &fragment_path,
+        "[[exercises]]\nname = \"pack1\"\ndir = \"basics\"\nhint = \"No hint\"\n",
+    )
+    .unwrap();
+    let curl_path = bin_dir.path().join("curl");
+    fs::write(
+        &curl_path,
+        format!("#!/bin/sh\ncat '{}'\n", fragment_path.display()),
+    )
+    .unwrap();
+    fs::set_permissions(&curl_path, fs::Permissions::from_mode(0o755)).unwrap();
+    let path_env = format!(
+        "{}:{}",
+        bin_dir.path().display(),
+        env::var("PATH").unwrap_or_default(),
+    );
+    let envs = [("PATH", path_env.as_str())];
+
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    let pack = |sha256: &str| {
+        format!("{info_file}\n[[packs]]\nname = \"community\"\nurl = \"https://example.com/pack.toml\"\nsha256 = \"{sha256}\"\n")
+    };
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    // Nothing is written if the checksum doesn't match.
+    fs::write(test_dir.join("info.toml"), pack(&"0".repeat(64))).unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["pack", "fetch"])
+        .envs(&envs)
+        .output(PartialStderr(
+            "The SHA-256 checksum of the pack `community` doesn't match",
+        ))
+        .fail();
+    assert!(!test_dir.join("packs").exists());
+
+    fs::write(
+        test_dir.join("info.toml"),
+        pack("0eb9c11e43781e23173df06d3591bae4a419631300632b5dd19904d61ea17367"),
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["pack", "fetch"])
+        .envs(&envs)
+        .output(PartialStdout(
+            "Fetched the pack `community` into packs/community.toml\n",
+        ))
+        .success();
+    assert_eq!(
+        fs::read(test_dir.join("packs/community.toml")).unwrap(),
+        fs::read(&fragment_path).unwrap(),
+    );
+    // The `dir` of the pack's exercise is prefixed with the pack's name.
+    assert!(fs::read_to_string(test_dir.join("dev/Cargo.toml"))
+        .unwrap()
+        .contains(r#"{ name = "pack1", path = "../exercises/community_basics/pack1.rs" },"#));
+}
+
+#[test]
+fn new_pack() {
+    let test_dir = TempDir::new().unwrap();
+    let test_dir = test_dir.path();
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["new-pack", "my-pack", "--no-git"])
+        .output(PartialStdout("Created the exercise pack `my-pack` ✓"))
+        .success();
+
+    let pack_dir = test_dir.join("my-pack");
+    assert!(fs::read_to_string(pack_dir.join("Cargo.toml"))
+        .unwrap()
+        .contains("bin = [\n  { name = \"intro1\", path = \"exercises/intro1.rs\" },\n  { name = \"intro1_sol\", path = \"solutions/intro1.rs\" },\n]\n"));
+    assert!(fs::read_to_string(pack_dir.join("info.toml"))
+        .unwrap()
+        .contains("[[exercises]]\nname = \"intro1\"\n"));
+    assert!(fs::read_to_string(pack_dir.join("exercises/intro1.rs"))
+        .unwrap()
+        .contains("// TODO"));
+    assert!(fs::read_to_string(pack_dir.join("README.md"))
+        .unwrap()
+        .starts_with("# my-pack 🦀\n"));
+
+    Cmd::default()
+        .current_dir(test_dir.to_str().unwrap())
+        .args(&["new-pack", "my pack"])
+        .fail();
+}
+
+#[test]
+fn dev_scaffold() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "scaffold", "answer1", "--difficulty", "6"])
+        .output(PartialStderr("The difficulty must be in the range 1-5"))
+        .fail();
+    assert!(!test_dir.join("exercises/answer1.rs").exists());
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&[
+            "dev",
+            "scaffold",
+            "answer1",
+            "--hint",
+            "The answer is \"42\"",
+            "--difficulty",
+            "2",
+        ])
+        .output(PartialStdout(
+            "Created the file exercises/answer1.rs\nCreated the file solutions/answer1.rs\nAdded the exercise `answer1` to `info.toml`\n",
+        ))
+        .success();
+    assert!(fs::read_to_string(test_dir.join("info.toml"))
+        .unwrap()
+        .ends_with("\n[[exercises]]\nname = \"answer1\"\ntest = false\ndifficulty = 2\nhint = \"The answer is \\\"42\\\"\"\n"));
+    assert!(fs::read_to_string(test_dir.join("exercises/answer1.rs"))
+        .unwrap()
+        .contains("fn main() {"));
+
+    // The solution passes and the exercise doesn't.
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["run", "answer1"])
+        .fail();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "check", "--only", "answer1"])
+        .success();
+}
+
+#[test]
+fn dev_new_exercise() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&[
+            "dev",
+            "new-exercise",
+            "answer1",
+            "--dir",
+            "answers",
+            "--test",
+        ])
+        .success();
+    assert!(fs::read_to_string(test_dir.join("info.toml"))
+        .unwrap()
+        .ends_with("\n[[exercises]]\nname = \"answer1\"\ndir = \"answers\"\nhint = \"TODO\"\n"));
+    assert!(
+        fs::read_to_string(test_dir.join("exercises/answers/answer1.rs"))
+            .unwrap()
+            .contains("#[cfg(test)]")
+    );
+    assert!(test_dir.join("solutions/answers/answer1.rs").is_file());
+    assert!(fs::read_to_string(test_dir.join("dev/Cargo.toml"))
+        .unwrap()
+        .contains(r#"{ name = "answer1_sol", path = "../solutions/answers/answer1.rs" },"#));
+
+    // The exercise already exists.
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "new-exercise", "answer1"])
+        .fail();
+}
+
+#[test]
+fn dev_remove_exercise() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+    let info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    let cargo_toml = fs::read_to_string(test_dir.join("dev/Cargo.toml")).unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "new-exercise", "answer1", "--dir", "answers"])
+        .success();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "remove-exercise", "answer1", "--dry-run"])
+        .output(PartialStdout(
+            "Would delete the file exercises/answers/answer1.rs\n",
+        ))
+        .success();
+    assert!(test_dir.join("exercises/answers/answer1.rs").exists());
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "remove-exercise", "answer1"])
+        .output(PartialStdout(
+            "Deleted the empty directory solutions/answers\n",
+        ))
+        .success();
+    assert_eq!(
+        fs::read_to_string(test_dir.join("info.toml")).unwrap(),
+        info_file,
+    );
+    assert_eq!(
+        fs::read_to_string(test_dir.join("dev/Cargo.toml")).unwrap(),
+        cargo_toml,
+    );
+    assert!(!test_dir.join("exercises/answers").exists());
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "remove-exercise", "answer1"])
+        .fail();
+}
+
+#[test]
+fn dev_rename_exercise() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["dev", "new-exercise", "answer1", "--dir", "answers"])
+        .success();
+    fs::write(
+        test_dir.join(".rustlings-state.txt"),
+        "DON'T EDIT THIS FILE!\n\nanswer1\n\ntest_success\nanswer1",
+    )
+    .unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&[
+            "dev",
+            "rename-exercise",
+            "answer1",
+            "answer-2",
+            "--keep-progress",
+        ])
+        .output(PartialStdout(
+            "Updated `info.toml`
+Renamed the file exercises/answers/answer1.rs to exercises/answers/answer-2.rs
+Renamed the file solutions/answers/answer1.rs to solutions/answers/answer-2.rs
+Updated `dev/Cargo.toml`
+Updated the state file .rustlings-state.txt
+",
+        ))
+        .success();
+    assert!(fs::read_to_string(test_dir.join("info.toml"))
+        .unwrap()
+        .contains("name = \"answer-2\"\n"));
+    assert!(fs::read_to_string(test_dir.join("dev/Cargo.toml"))
+        .unwrap()
+        .contains("{ name = \"answer-2\", path = \"../exercises/answers/answer-2.rs\" }"));
+    assert!(test_dir.join("solutions/answers/answer-2.rs").exists());
+    assert!(!test_dir.join("exercises/answers/answer1.rs").exists());
+    assert_eq!(
+        fs::read_to_string(test_dir.join(".rustlings-state.txt")).unwrap(),
+        "DON'T EDIT THIS FILE!\n\nanswer-2\n\ntest_success\nanswer-2",
+    );
+
+    for new in ["test_success", "answer 3"] {
+        Cmd::default()
+            .current_dir(test_dir_str)
+            .args(&["dev", "rename-exercise", "answer-2", new])
+            .fail();
+    }
+}
+
+#[test]
+fn sandbox() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path().to_str().unwrap();
+    let sandbox_dir = TempDir::new().unwrap();
+    let envs = [("RUSTLINGS_TARGET_DIR", sandbox_dir.path().to_str().unwrap())];
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["run", "test_success"])
+        .envs(&envs)
+        .output(PartialStdout("Successfully ran"))
+        .success();
+    // Cleaned up after the run.
+    assert_eq!(fs::read_dir(sandbox_dir.path()).unwrap().count(), 0);
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["--keep-artifacts", "run", "test_success"])
+        .envs(&envs)
+        .success();
+    let target_dirs = fs::read_dir(sandbox_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(target_dirs.len(), 1);
+    assert!(target_dirs[0].starts_with("test_success-"));
+
+    // The same directory is reused.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["--keep-artifacts", "run", "test_success"])
+        .envs(&envs)
+        .success();
+    assert_eq!(fs::read_dir(sandbox_dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn custom_dirs() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    fs::create_dir(test_dir.join("course")).unwrap();
+    fs::rename(
+        test_dir.join("exercises"),
+        test_dir.join("course/exercises"),
+    )
+    .unwrap();
+    fs::remove_dir(test_dir.join("solutions")).unwrap();
+    fs::create_dir(test_dir.join("sols")).unwrap();
+    fs::write(test_dir.join("sols/stray.rs"), "fn main() {}\n").unwrap();
+    let test_dir = test_dir.to_str().unwrap();
+    let envs = [
+        ("RUSTLINGS_EXERCISES_DIR", "course/exercises"),
+        ("RUSTLINGS_SOLUTIONS_DIR", "sols/"),
+    ];
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "update"])
+        .envs(&envs)
+        .success();
+    assert!(fs::read_to_string(format!("{test_dir}/dev/Cargo.toml"))
+        .unwrap()
+        .contains(r#"{ name = "test_success", path = "../course/exercises/test_success.rs" },"#));
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["run", "test_success"])
+        .envs(&envs)
+        .output(PartialStdout(
+            "Successfully ran course/exercises/test_success.rs",
+        ))
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "check", "--json"])
+        .envs(&envs)
+        .output(PartialStdout(
+            "Found the orphaned solution file `sols/stray.rs`",
+        ))
+        .fail();
+}
+
+#[test]
+fn hyphen_names() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let mut info_file = fs::read_to_string(test_dir.join("info.toml")).unwrap();
+    info_file.push_str(
+        "
+[[exercises]]
+name = \"error-handling-1\"
+dir = \"error-handling\"
+test = false
+hint = \"\"
+",
+    );
+    fs::write(test_dir.join("info.toml"), info_file).unwrap();
+    fs::create_dir(test_dir.join("exercises/error-handling")).unwrap();
+    fs::write(
+        test_dir.join("exercises/error-handling/error-handling-1.rs"),
+        "fn main() {}\n",
+    )
+    .unwrap();
+    let test_dir = test_dir.to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["dev", "update"])
+        .success();
+    assert!(fs::read_to_string(format!("{test_dir}/dev/Cargo.toml"))
+        .unwrap()
+        .contains(r#"{ name = "error-handling-1", path = "../exercises/error-handling/error-handling-1.rs" },"#));
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["run", "error-handling-1"])
+        .output(PartialStdout(
+            "Successfully ran exercises/error-handling/error-handling-1.rs",
+        ))
+        .success();
+
+    // The exercise is saved as done in the state file.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["stats", "--json"])
+        .output(PartialStdout(r#""n_done": 1,"#))
+        .success();
+}
+
+#[test]
+fn time_spent() {
+    let test_dir = temp_course();
+    let state_file = test_dir.path().join(".rustlings-state.txt");
+    fs::write(
+        &state_file,
+        "DON'T EDIT THIS FILE!\n\ntest_success\n\ncompilation_success\n\n1700000000\n\ncompilation_success 90\ntest_success 30\n",
+    )
+    .unwrap();
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["stats", "--json"])
+        .output(PartialStdout(r#""total_secs_spent": 120"#))
+        .success();
+
+    // Writing the state file keeps the time.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["run", "test_success"])
+        .success();
+    let state = fs::read_to_string(&state_file).unwrap();
+    assert!(state.contains("\n\ncompilation_success 90\ntest_success 30"));
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["stats"])
+        .output(PartialStdout("2:00\n  1:30  compilation_success\n"))
+        .success();
+}
+
+#[test]
+fn bell_setting() {
+    let test_dir = temp_course();
+    let state_file = test_dir.path().join(".rustlings-state.txt");
+    fs::write(
+        &state_file,
+        "DON'T EDIT THIS FILE!\n\ntest_success\n\n\n\n\nbell",
+    )
+    .unwrap();
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    // Writing the state file keeps the setting.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["run", "test_success"])
+        .success();
+    let state = fs::read_to_string(&state_file).unwrap();
+    assert!(state.contains("\ntest_success\n\n"));
+    assert!(state.ends_with("\n\nbell"));
+}
+
+#[test]
+fn removed_current_exercise() {
+    let test_dir = temp_course();
+    let state_file = test_dir.path().join(".rustlings-state.txt");
+    fs::write(
+        &state_file,
+        "DON'T EDIT THIS FILE!\n\nremoved\n\ncompilation_success\ncompilation_failure\n",
+    )
+    .unwrap();
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    // Continues at the first pending exercise and moves on after it is done.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["run"])
+        .output(PartialStderr(
+            "The exercise `removed` from the state file doesn't exist anymore.\nContinuing at the exercise `test_success`",
+        ))
+        .success();
+    let state = fs::read_to_string(&state_file).unwrap();
+    assert!(state.starts_with("DON'T EDIT THIS FILE!\n\ntest_failure\n\ncompilation_success\ncompilation_failure\ntest_success\n"), "{state}");
+}
+
+#[test]
+fn skipped_exercises() {
+    let test_dir = temp_course();
+    let state_file = test_dir.path().join(".rustlings-state.txt");
+    fs::write(
+        &state_file,
+        "DON'T EDIT THIS FILE!\n\ncompilation_success\n\n\n\n\n\ncompilation_failure\ntest_success",
+    )
+    .unwrap();
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    // Doing a skipped exercise unskips it. The next exercise isn't a skipped one.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["run", "test_success"])
+        .output(PartialStdout("Next exercise: exercises/test_failure.rs"))
+        .success();
+    let state = fs::read_to_string(&state_file).unwrap();
+    assert!(state.contains("\ntest_failure\n\ntest_success\n\n"));
+    assert!(state.ends_with("\n\n\ncompilation_failure"));
+}
+
+#[test]
+fn stats_export_csv() {
+    let test_dir = temp_course();
+    fs::write(
+        test_dir.path().join(".rustlings-state.txt"),
+        // The duration is from becoming the current exercise until the completion,
+        // not the time spent in the watch mode.
+        "DON'T EDIT THIS FILE!\n\ntest_success\n\ncompilation_success\n\n1715932989\n\ncompilation_success 30 1715932989 1715932899\ntest_success 30 - 1715932999\n",
+    )
+    .unwrap();
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["stats", "--export", "csv"])
+        .output(FullStdout(
+            "exercise_name,completed,timestamp_utc,duration_seconds
+compilation_success,true,2024-05-17T08:03:09Z,90
+",
+        ))
+        .success();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&[
+            "stats",
+            "--export",
+            "csv",
+            "--include-incomplete",
+            "--out",
+            "progress.csv",
+        ])
+        .success();
+    assert_eq!(
+        fs::read_to_string(format!("{test_dir}/progress.csv")).unwrap(),
+        "exercise_name,completed,timestamp_utc,duration_seconds
+compilation_success,true,2024-05-17T08:03:09Z,90
+compilation_failure,false,,
+test_success,false,,
+test_failure,false,,
+",
+    );
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["stats", "--include-incomplete"])
+        .fail();
+}
+
+#[test]
+fn watch_line_mode() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let state_file = test_dir.join(".rustlings-state.txt");
+    let test_dir_str = test_dir.to_str().unwrap();
+
+    // Stdout isn't a terminal. Without the file watcher, the watch mode ends with the input.
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["--manual-run"])
+        .output(PartialStdout(
+            "Current exercise 1/4: exercises/compilation_success.rs",
+        ))
+        .success();
+
+    // Move on from the done first exercise and quit.
+    for name in ["compilation_success", "compilation_failure"] {
+        fs::copy(
+            format!("tests/test_exercises/exercises/{name}.rs"),
+            test_dir.join(format!("exercises/{name}.rs")),
+        )
+        .unwrap();
+    }
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["--manual-run"])
+        .stdin("n\nq\n")
+        .output(PartialStdout(
+            "Current exercise 2/4: exercises/compilation_failure.rs",
+        ))
+        .success();
+    let state = fs::read_to_string(&state_file).unwrap();
+    assert!(
+        state.starts_with("DON'T EDIT THIS FILE!\n\ncompilation_failure\n\ncompilation_success\n"),
+        "{state}",
+    );
+}
+
+#[test]
+fn run_watch() {
+    let test_dir = temp_course();
+    let test_dir = test_dir.path();
+    let exercise_path = test_dir.join("exercises/test_success.rs");
+
+    // Kills the command which only exits on Ctrl+C, also if an assertion fails.
+    struct KillOnDrop(Child);
+    impl Drop for KillOnDrop {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    let mut child = KillOnDrop(
+        Command::new(rustlings_bin())
+            .current_dir(test_dir)
+            .args(["run", "test_success", "--watch"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap(),
+    );
+    let mut child_stdout = child.0.stdout.take().unwrap();
+    let (output_sender, output_receiver) = mpsc::channel();
+    thread::Builder::new()
+        .spawn(move || {
+            let mut buf = [0; 1024];
+            while let Ok(n @ 1..) = child_stdout.read(&mut buf) {
+                if output_sender.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        })
+        .unwrap();
+
+    // Wait until the output contains the watching message `n` times.
+    let mut output = Vec::new();
+    let mut wait_for_runs = |n| {
+        let deadline = Instant::now() + Duration::from_secs(60);
+        while from_utf8(&output)
+            .unwrap()
+            .matches("Watching exercises/test_success.rs for changes")
+            .count()
+            < n
+        {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match output_receiver.recv_timeout(timeout) {
+                Ok(chunk) => output.extend_from_slice(&chunk),
+                Err(e) => panic!("{e}\n{}", String::from_utf8_lossy(&output)),
+            }
+        }
+        String::from_utf8_lossy(&output).into_owned()
+    };
+
+    assert!(wait_for_runs(1).contains("Successfully ran exercises/test_success.rs"));
+
+    // Rerun after a modification.
+    let exercise = fs::read_to_string(&exercise_path).unwrap();
+    fs::write(
+        &exercise_path,
+        exercise.replace(
+            "fn passes() {}",
+            "fn passes() {\n        panic!(\"Broken\");\n    }",
+        ),
+    )
+    .unwrap();
+    let output = wait_for_runs(2);
+    assert!(output.contains("Broken"), "{output}");
+    assert!(output.contains("with errors"), "{output}");
+
+    // Rerun after an editor saves by renaming a temporary file over the exercise file.
+    let tmp_path = test_dir.join("exercises/test_success.rs.tmp");
+    fs::write(&tmp_path, &exercise).unwrap();
+    fs::rename(&tmp_path, &exercise_path).unwrap();
+    let output = wait_for_runs(3);
+    assert!(
+        output.ends_with("Watching exercises/test_success.rs for changes. Press Ctrl+C to exit\n"),
+        "{output}",
+    );
+    assert_eq!(output.matches("Successfully ran").count(), 2, "{output}");
+
+    // Rerun after an editor saves by removing the file and creating a new one.
+    fs::remove_file(&exercise_path).unwrap();
+    fs::write(&exercise_path, &exercise).unwrap();
+    let output = wait_for_runs(4);
+    drop(child);
+    assert_eq!(output.matches("Successfully ran").count(), 3, "{output}");
+
+    // Stays on the exercise after it is done.
+    let state = fs::read_to_string(test_dir.join(".rustlings-state.txt")).unwrap();
+    assert!(
+        state.starts_with("DON'T EDIT THIS FILE!\n\ntest_success\n"),
+        "{state}",
+    );
+}
+
+#[test]
+fn watch_keys() {
+    let test_dir = temp_course();
+    let config_path = test_dir.path().join("rustlings.toml");
+    let test_dir_str = test_dir.path().to_str().unwrap();
+
+    fs::write(&config_path, "[keys]\nrun = \"R\"\nquit = \"Q\"\n").unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["--manual-run"])
+        .output(PartialStdout("R\u{1b}[0m:run / "))
+        .success();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .args(&["--manual-run"])
+        .output(PartialStdout("Q\u{1b}[0m:quit ? "))
+        .success();
+
+    fs::write(&config_path, "[keys]\nrun = \"x\"\n").unwrap();
+    Cmd::default()
+        .current_dir(test_dir_str)
+        .output(PartialStderr(
+            "The key `x` is used by the actions `reset` and `run`",
+        ))
+        .fail();
+}
+
+#[test]
+fn run_no_output() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "--no-output", "test_success"])
+        .output(FullStdout("PASS test_success\n"))
+        .success();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "--no-output", "test_failure"])
+        .output(FullStdout("FAIL test_failure\n"))
+        .fail();
+}
+
+#[test]
+fn completions() {
+    // The scripts are pre-generated by `build.rs`.
+    for (shell, file_name) in [
+        ("bash", "rustlings.bash"),
+        ("zsh", "_rustlings"),
+        ("fish", "rustlings.fish"),
+        ("powershell", "_rustlings.ps1"),
+        ("elvish", "rustlings.elv"),
+    ] {
+        let script = fs::read_to_string(format!("assets/completions/{file_name}")).unwrap();
+        assert!(script.contains("COMPLETE"), "{script}");
+        Cmd::default()
+            .args(&["completions", shell])
+            .output(FullStdout(&script))
+            .success();
+    }
+
+    // Validate the syntax of the generated script if Bash is installed.
+    if Command::new("bash").arg("--version").output().is_ok() {
+        let script = Command::new(rustlings_bin())
+            .args(["completions", "bash"])
+            .output()
+            .unwrap()
+            .stdout;
+        let mut bash = Command::new("bash")
+            .arg("-n")
+            .stdin(Stdio::piped())
+            .spawn()
+            .unwrap();
+        bash.stdin.take().unwrap().write_all(&script).unwrap();
+        assert!(bash.wait().unwrap().success());
+    }
+
+    Cmd::default().args(&["completions", "nu"]).fail();
+
+    // Dynamic completion of the exercise names.
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["--", "rustlings", "run", "test_"])
+        .envs(&[("COMPLETE", "fish")])
+        .output(FullStdout("test_success\ntest_failure\n"))
+        .success();
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["--", "rustlings", "hint", "compilation_s"])
+        .envs(&[("COMPLETE", "fish")])
+        .output(FullStdout("compilation_success\n"))
+        .success();
+}
+
+#[test]
+fn init() {
+    let test_dir = TempDir::new().unwrap();
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    Cmd::default().current_dir(test_dir).fail();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init"])
+        .success();
+
+    // Running `init` after a successful initialization.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init"])
+        .output(PartialStderr("`cd rustlings`"))
+        .fail();
+
+    let initialized_dir = format!("{test_dir}/rustlings");
+
+    // Running `init` in the initialized directory.
+    Cmd::default()
+        .current_dir(&initialized_dir)
+        .args(&["init"])
+        .output(PartialStderr("already initialized"))
+        .fail();
+}
+
+#[test]
+fn init_path() {
+    let test_dir = TempDir::new().unwrap();
+    let initialized_dir = test_dir.path().join("course/rustlings");
+    let test_dir = test_dir.path().to_str().unwrap();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init", "--force"])
+        .fail();
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init", "--path", "course/rustlings"])
+        .output(PartialStdout("Run `cd "))
+        .success();
+    assert!(initialized_dir
+        .join("exercises/00_intro/intro1.rs")
+        .exists());
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init", "--path", "course/rustlings"])
+        .output(PartialStderr("already initialized"))
+        .fail();
+
+    // Only missing files are added.
+    let edited_exercise = initialized_dir.join("exercises/00_intro/intro1.rs");
+    let deleted_exercise = initialized_dir.join("exercises/00_intro/intro2.rs");
+    fs::write(&edited_exercise, "// Edited").unwrap();
+    fs::remove_file(&deleted_exercise).unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init", "--path", "course/rustlings", "--force"])
+        .success();
+    assert_eq!(fs::read_to_string(&edited_exercise).unwrap(), "// Edited");
+    assert!(deleted_exercise.exists());
+
+    // Nothing is created inside of a Cargo project.
+    fs::create_dir_all(format!("{test_dir}/project/src")).unwrap();
+    fs::write(format!("{test_dir}/project/src/main.rs"), "fn main() {}\n").unwrap();
+    fs::write(
+        format!("{test_dir}/project/Cargo.toml"),
+        "[package]\nname = \"project\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init", "--path", "project/course/rustlings"])
+        .output(PartialStderr("is part of a Cargo project"))
+        .fail();
+    assert!(!Path::new(test_dir).join("project/course").exists());
+
+    // Writing into a non-empty directory needs a confirmation.
+    fs::create_dir(format!("{test_dir}/notes")).unwrap();
+    fs::write(format!("{test_dir}/notes/notes.md"), "# Notes\n").unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init", "--path", "notes"])
+        .stdin("\n")
+        .output(PartialStdout("The directory notes isn't empty"))
+        .success();
+    assert!(Path::new(test_dir)
+        .join("notes/exercises/00_intro/intro1.rs")
+        .exists());
+}
+
+#[test]
+fn init_custom_dirs() {
+    let test_dir = TempDir::new().unwrap();
+    let initialized_dir = test_dir.path().join("course");
+    let test_dir = test_dir.path().to_str().unwrap();
+    let envs = [
+        ("RUSTLINGS_EXERCISES_DIR", "custom/exercises/"),
+        ("RUSTLINGS_SOLUTIONS_DIR", "sols\\"),
+    ];
+
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["init", "--path", "course"])
+        .envs(&envs)
+        .success();
+    assert!(initialized_dir
+        .join("custom/exercises/00_intro/intro1.rs")
+        .exists());
+    assert!(initialized_dir.join("sols/00_intro/intro1.rs").exists());
+    assert!(!initialized_dir.join("exercises").exists());
+    assert!(!initialized_dir.join("solutions").exists());
+
+    // Resetting writes the original exercise to the custom directory.
+    let exercise = initialized_dir.join("custom/exercises/00_intro/intro1.rs");
+    let original = fs::read_to_string(&exercise).unwrap();
+    fs::write(&exercise, "// Edited").unwrap();
+    Cmd::default()
+        .current_dir(initialized_dir.to_str().unwrap())
+        .args(&["reset", "intro1", "--yes"])
+        .envs(&envs)
+        .success();
+    assert_eq!(fs::read_to_string(&exercise).unwrap(), original);
+}
+
+#[test]
+fn doctor() {
+    // An empty `PATH` makes the checks of the toolchain fail independently of the host.
+    let empty_dir = TempDir::new().unwrap();
+    let envs = [("PATH", empty_dir.path().to_str().unwrap())];
+
+    let test_dir = TempDir::new().unwrap();
+    let test_dir = test_dir.path().to_str().unwrap();
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["doctor"])
+        .envs(&envs)
+        .output(PartialStdout("No `exercises` directory found"))
+        .fail();
+    // Only `rustc`, `cargo`, `rustfmt` and `cargo-clippy` fail.
+    // The missing exercises directory is a warning.
+    Cmd::default()
+        .current_dir(test_dir)
+        .args(&["doctor"])
+        .envs(&envs)
+        .output(PartialStdout("\n4 checks failed."))
+        .fail();
+
+    let course_dir = temp_course();
+    Cmd::default()
+        .current_dir(course_dir.path().to_str().unwrap())
+        .args(&["doctor"])
+        .envs(&envs)
+        .output(PartialStdout("4 exercises in `info.toml`"))
         .fail();
 }